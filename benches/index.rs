@@ -0,0 +1,119 @@
+use {
+  bitcoin::Witness,
+  clap::Parser,
+  criterion::{criterion_group, criterion_main, BatchSize, Criterion},
+  mockcore::TransactionTemplate,
+  ord::{settings::Settings, Index, Inscription, Options},
+  ordinals::Runestone,
+  tempfile::TempDir,
+};
+
+const BLOCKS: usize = 2;
+const TRANSACTIONS: usize = 250;
+
+/// A chain of blocks whose transactions each carry an inscription envelope and
+/// a runestone, so that parsing them dominates indexing
+fn chain() -> mockcore::Handle {
+  let core = mockcore::builder()
+    .network(bitcoin::Network::Regtest)
+    .build();
+
+  core.mine_blocks(BLOCKS as u64 + 1);
+
+  for height in 1..=BLOCKS {
+    core.broadcast_tx(TransactionTemplate {
+      inputs: &[(height, 0, 0, Witness::new())],
+      outputs: TRANSACTIONS,
+      ..Default::default()
+    });
+  }
+
+  core.mine_blocks(1);
+
+  let funding = BLOCKS + 2;
+
+  let witness = Inscription {
+    content_type: Some("application/octet-stream".into()),
+    body: Some(vec![0; 8192]),
+    ..Default::default()
+  }
+  .to_witness();
+
+  let runestone = Runestone {
+    pointer: Some(0),
+    ..Default::default()
+  }
+  .encipher();
+
+  for block in 0..BLOCKS {
+    for output in 0..TRANSACTIONS {
+      core.broadcast_tx(TransactionTemplate {
+        inputs: &[(funding, block + 1, output, witness.clone())],
+        op_return: Some(runestone.clone()),
+        ..Default::default()
+      });
+    }
+
+    core.mine_blocks(1);
+  }
+
+  core
+}
+
+fn index(core: &mockcore::Handle, tempdir: &TempDir) -> Index {
+  let options = Options::try_parse_from([
+    "ord",
+    "--regtest",
+    "--index-sats",
+    "--index-runes",
+    "--bitcoin-rpc-url",
+    &core.url(),
+    "--cookie-file",
+    core.cookie_file().to_str().unwrap(),
+    "--datadir",
+    tempdir.path().to_str().unwrap(),
+  ])
+  .unwrap();
+
+  Index::open(&Settings::from_options(options).or_defaults().unwrap()).unwrap()
+}
+
+fn update(c: &mut Criterion) {
+  let core = chain();
+
+  let mut group = c.benchmark_group("update");
+
+  group.sample_size(10);
+
+  let mut threads = vec![1];
+
+  let available = std::thread::available_parallelism().map_or(1, |n| n.get());
+
+  if available > 1 {
+    threads.push(available);
+  }
+
+  for threads in threads {
+    let pool = rayon::ThreadPoolBuilder::new()
+      .num_threads(threads)
+      .build()
+      .unwrap();
+
+    group.bench_function(format!("{threads} threads"), |b| {
+      b.iter_batched(
+        || {
+          let tempdir = TempDir::new().unwrap();
+          let index = index(&core, &tempdir);
+          (tempdir, index)
+        },
+        |(_tempdir, index)| pool.install(|| index.update().unwrap()),
+        BatchSize::PerIteration,
+      )
+    });
+  }
+
+  group.finish();
+}
+
+criterion_group!(benches, update);
+criterion_main!(benches);
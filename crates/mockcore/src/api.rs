@@ -70,6 +70,13 @@ pub trait Api {
     avoid_reuse: Option<bool>,
   ) -> Result<LoadWalletResult, jsonrpc_core::Error>;
 
+  #[rpc(name = "estimatesmartfee")]
+  fn estimate_smart_fee(
+    &self,
+    conf_target: u16,
+    estimate_mode: Option<EstimateMode>,
+  ) -> Result<EstimateSmartFeeResult, jsonrpc_core::Error>;
+
   #[rpc(name = "fundrawtransaction")]
   fn fund_raw_transaction(
     &self,
@@ -125,6 +132,9 @@ pub trait Api {
     blockhash: Option<BlockHash>,
   ) -> Result<Value, jsonrpc_core::Error>;
 
+  #[rpc(name = "getrawmempool")]
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error>;
+
   #[rpc(name = "listunspent")]
   fn list_unspent(
     &self,
@@ -163,6 +173,12 @@ pub trait Api {
     address_type: Option<bitcoincore_rpc::json::AddressType>,
   ) -> Result<Address, jsonrpc_core::Error>;
 
+  #[rpc(name = "getaddressinfo")]
+  fn get_address_info(
+    &self,
+    address: Address<NetworkUnchecked>,
+  ) -> Result<Value, jsonrpc_core::Error>;
+
   #[rpc(name = "listtransactions")]
   fn list_transactions(
     &self,
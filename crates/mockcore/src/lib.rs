@@ -20,15 +20,15 @@ use {
     WPubkeyHash, Witness, Wtxid,
   },
   bitcoincore_rpc::json::{
-    Bip125Replaceable, CreateRawTransactionInput, EstimateMode, FeeRatePercentiles,
-    FinalizePsbtResult, GetBalancesResult, GetBalancesResultEntry, GetBlockHeaderResult,
-    GetBlockStatsResult, GetBlockchainInfoResult, GetDescriptorInfoResult, GetNetworkInfoResult,
-    GetRawTransactionResult, GetRawTransactionResultVout, GetRawTransactionResultVoutScriptPubKey,
-    GetTransactionResult, GetTransactionResultDetail, GetTransactionResultDetailCategory,
-    GetTxOutResult, GetWalletInfoResult, ImportDescriptors, ImportMultiResult,
-    ListTransactionResult, ListUnspentResultEntry, ListWalletDirItem, ListWalletDirResult,
-    LoadWalletResult, SignRawTransactionInput, SignRawTransactionResult, Timestamp,
-    WalletProcessPsbtResult, WalletTxInfo,
+    Bip125Replaceable, CreateRawTransactionInput, EstimateMode, EstimateSmartFeeResult,
+    FeeRatePercentiles, FinalizePsbtResult, GetBalancesResult, GetBalancesResultEntry,
+    GetBlockHeaderResult, GetBlockStatsResult, GetBlockchainInfoResult, GetDescriptorInfoResult,
+    GetNetworkInfoResult, GetRawTransactionResult, GetRawTransactionResultVout,
+    GetRawTransactionResultVoutScriptPubKey, GetTransactionResult, GetTransactionResultDetail,
+    GetTransactionResultDetailCategory, GetTxOutResult, GetWalletInfoResult, ImportDescriptors,
+    ImportMultiResult, ListTransactionResult, ListUnspentResultEntry, ListWalletDirItem,
+    ListWalletDirResult, LoadWalletResult, SignRawTransactionInput, SignRawTransactionResult,
+    Timestamp, WalletProcessPsbtResult, WalletTxInfo,
   },
   jsonrpc_core::{IoHandler, Value},
   jsonrpc_http_server::{CloseHandle, ServerBuilder},
@@ -267,6 +267,10 @@ impl Handle {
     self.state().broadcast_tx(template)
   }
 
+  pub fn set_fee_estimate(&self, sat_per_vb: u64) {
+    self.state().fee_estimate = Some(Amount::from_sat(sat_per_vb * 1000));
+  }
+
   pub fn height(&self) -> u64 {
     u64::try_from(self.state().blocks.len()).unwrap() - 1
   }
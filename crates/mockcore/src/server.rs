@@ -245,20 +245,47 @@ impl Api for Server {
     &self,
     txid: Txid,
     vout: u32,
-    _include_mempool: Option<bool>,
+    include_mempool: Option<bool>,
   ) -> Result<Option<GetTxOutResult>, jsonrpc_core::Error> {
     let state = self.state();
 
-    let Some(value) = state.utxos.get(&OutPoint { txid, vout }) else {
+    let outpoint = OutPoint { txid, vout };
+
+    let include_mempool = include_mempool.unwrap_or(true);
+
+    if include_mempool
+      && state
+        .mempool
+        .iter()
+        .flat_map(|tx| &tx.input)
+        .any(|input| input.previous_output == outpoint)
+    {
       return Ok(None);
+    }
+
+    let value = match state.utxos.get(&outpoint) {
+      Some(value) => *value,
+      None if include_mempool => {
+        let Some(tx_out) = state
+          .mempool
+          .iter()
+          .find(|tx| tx.compute_txid() == txid)
+          .and_then(|tx| tx.output.get(usize::try_from(vout).unwrap()))
+        else {
+          return Ok(None);
+        };
+
+        tx_out.value
+      }
+      None => return Ok(None),
     };
 
-    let mut confirmations = None;
+    let mut confirmations = 0;
 
     for (height, hash) in state.hashes.iter().enumerate() {
       for tx in &state.blocks[hash].txdata {
         if tx.compute_txid() == txid {
-          confirmations = Some(state.hashes.len() - height);
+          confirmations = state.hashes.len() - height;
         }
       }
     }
@@ -266,7 +293,7 @@ impl Api for Server {
     Ok(Some(GetTxOutResult {
       bestblock: BlockHash::all_zeros(),
       coinbase: false,
-      confirmations: confirmations.unwrap().try_into().unwrap(),
+      confirmations: confirmations.try_into().unwrap(),
       script_pub_key: GetRawTransactionResultVoutScriptPubKey {
         asm: String::new(),
         hex: Vec::new(),
@@ -275,7 +302,7 @@ impl Api for Server {
         addresses: Vec::new(),
         address: None,
       },
-      value: *value,
+      value,
     }))
   }
 
@@ -352,6 +379,22 @@ impl Api for Server {
     })
   }
 
+  fn estimate_smart_fee(
+    &self,
+    conf_target: u16,
+    _estimate_mode: Option<EstimateMode>,
+  ) -> Result<EstimateSmartFeeResult, jsonrpc_core::Error> {
+    let fee_estimate = self.state().fee_estimate;
+
+    Ok(EstimateSmartFeeResult {
+      fee_rate: fee_estimate,
+      errors: fee_estimate
+        .is_none()
+        .then(|| vec!["Insufficient data or no feerate found".into()]),
+      blocks: conf_target.into(),
+    })
+  }
+
   fn fund_raw_transaction(
     &self,
     tx: String,
@@ -387,17 +430,17 @@ impl Api for Server {
       .map(|txout| txout.value.to_sat())
       .sum();
 
-    let mut utxos = state
-      .utxos
-      .clone()
-      .into_iter()
-      .map(|(outpoint, value)| (value, outpoint))
+    let spendable = state.spendable();
+
+    let mut utxos = spendable
+      .iter()
+      .map(|(outpoint, tx_out)| (tx_out.value, *outpoint))
       .collect::<Vec<(Amount, OutPoint)>>();
 
     let mut input_value = transaction
       .input
       .iter()
-      .map(|txin| state.utxos.get(&txin.previous_output).unwrap().to_sat())
+      .map(|txin| spendable.get(&txin.previous_output).unwrap().value.to_sat())
       .sum::<u64>();
 
     utxos.sort();
@@ -409,9 +452,15 @@ impl Api for Server {
           continue;
         }
 
-        let tx = state.transactions.get(&outpoint.txid).unwrap();
+        if transaction
+          .input
+          .iter()
+          .any(|txin| txin.previous_output == outpoint)
+        {
+          continue;
+        }
 
-        let tx_out = &tx.output[usize::try_from(outpoint.vout).unwrap()];
+        let tx_out = &spendable[&outpoint];
 
         let Ok(address) = Address::from_script(&tx_out.script_pubkey, state.network) else {
           continue;
@@ -559,6 +608,20 @@ impl Api for Server {
       }
     }
 
+    // transactions funded by mockcore never spend unconfirmed outputs, so the
+    // whole mempool is treated as a single chain
+    if let Some(limit) = state.mempool_chain_limit {
+      if state.mempool.len() >= limit {
+        return Err(jsonrpc_core::Error {
+          code: jsonrpc_core::ErrorCode::ServerError(-26),
+          message: format!(
+            "too-long-mempool-chain, too many unconfirmed ancestors [limit: {limit}]"
+          ),
+          data: None,
+        });
+      }
+    }
+
     state.mempool.push(tx.clone());
 
     Ok(tx.compute_txid().to_string())
@@ -642,18 +705,22 @@ impl Api for Server {
   ) -> Result<Value, jsonrpc_core::Error> {
     let state = self.state();
 
-    let Some(tx) = state.transactions.get(&txid) else {
+    let Some(tx) = state
+      .transactions
+      .get(&txid)
+      .or_else(|| state.mempool.iter().find(|tx| tx.compute_txid() == txid))
+    else {
       return Err(jsonrpc_core::Error::new(
         jsonrpc_core::types::error::ErrorCode::ServerError(-8),
       ));
     };
 
-    let mut confirmations = None;
+    let mut confirmations = 0;
 
     'outer: for (height, hash) in state.hashes.iter().enumerate() {
       for tx in &state.blocks[hash].txdata {
         if tx.compute_txid() == txid {
-          confirmations = Some(state.hashes.len() - height);
+          confirmations = state.hashes.len() - height;
           break 'outer;
         }
       }
@@ -663,7 +730,7 @@ impl Api for Server {
       serde_json::to_value(GetTransactionResult {
         info: WalletTxInfo {
           txid,
-          confirmations: confirmations.unwrap().try_into().unwrap(),
+          confirmations: confirmations.try_into().unwrap(),
           time: 0,
           timereceived: 0,
           blockhash: None,
@@ -740,13 +807,28 @@ impl Api for Server {
         None => Err(Self::not_found()),
       }
     } else {
-      match state.transactions.get(&txid) {
+      match state
+        .transactions
+        .get(&txid)
+        .or_else(|| state.mempool.iter().find(|tx| tx.compute_txid() == txid))
+      {
         Some(tx) => Ok(Value::String(hex::encode(serialize(tx)))),
         None => Err(Self::not_found()),
       }
     }
   }
 
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error> {
+    Ok(
+      self
+        .state()
+        .mempool
+        .iter()
+        .map(|tx| tx.compute_txid())
+        .collect(),
+    )
+  }
+
   fn list_unspent(
     &self,
     minconf: Option<usize>,
@@ -859,11 +941,25 @@ impl Api for Server {
     Ok(self.state().new_address(false))
   }
 
+  fn get_address_info(
+    &self,
+    address: Address<NetworkUnchecked>,
+  ) -> Result<Value, jsonrpc_core::Error> {
+    let address = address.assume_checked();
+
+    Ok(serde_json::json!({
+      "address": address.to_string(),
+      "scriptPubKey": address.script_pubkey().to_hex_string(),
+      "ismine": self.state().is_wallet_address(&address),
+      "labels": [],
+    }))
+  }
+
   fn list_transactions(
     &self,
     _label: Option<String>,
     count: Option<u16>,
-    _skip: Option<usize>,
+    skip: Option<usize>,
     _include_watchonly: Option<bool>,
   ) -> Result<Vec<ListTransactionResult>, jsonrpc_core::Error> {
     let state = self.state();
@@ -871,10 +967,30 @@ impl Api for Server {
       state
         .transactions
         .iter()
-        .take(count.unwrap_or(u16::MAX).into())
         .map(|(txid, tx)| (*txid, tx))
         .chain(state.mempool.iter().map(|tx| (tx.compute_txid(), tx)))
-        .map(|(txid, tx)| ListTransactionResult {
+        // like bitcoin core, list transactions once per wallet output
+        .flat_map(|(txid, tx)| {
+          let vouts = tx
+            .output
+            .iter()
+            .enumerate()
+            .filter(|(_vout, tx_out)| {
+              Address::from_script(&tx_out.script_pubkey, state.network)
+                .is_ok_and(|address| state.is_wallet_address(&address))
+            })
+            .map(|(vout, _tx_out)| u32::try_from(vout).unwrap())
+            .collect::<Vec<u32>>();
+
+          if vouts.is_empty() {
+            vec![(txid, tx, 0)]
+          } else {
+            vouts.into_iter().map(|vout| (txid, tx, vout)).collect()
+          }
+        })
+        .skip(skip.unwrap_or_default())
+        .take(count.unwrap_or(u16::MAX).into())
+        .map(|(txid, tx, vout)| ListTransactionResult {
           info: WalletTxInfo {
             confirmations: state.get_confirmations(tx),
             blockhash: None,
@@ -892,7 +1008,7 @@ impl Api for Server {
             category: GetTransactionResultDetailCategory::Immature,
             amount: SignedAmount::from_sat(0),
             label: None,
-            vout: 0,
+            vout,
             fee: Some(SignedAmount::from_sat(0)),
             abandoned: None,
           },
@@ -908,8 +1024,6 @@ impl Api for Server {
     unlock: bool,
     outputs: Vec<JsonOutPoint>,
   ) -> Result<bool, jsonrpc_core::Error> {
-    assert!(!unlock);
-
     let mut state = self.state();
 
     if state.fail_lock_unspent {
@@ -921,7 +1035,11 @@ impl Api for Server {
         vout: output.vout,
         txid: output.txid,
       };
-      assert!(state.locked.insert(output));
+      if unlock {
+        assert!(state.locked.remove(&output));
+      } else {
+        assert!(state.locked.insert(output));
+      }
     }
 
     Ok(true)
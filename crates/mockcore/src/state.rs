@@ -5,10 +5,12 @@ pub struct State {
   pub blocks: BTreeMap<BlockHash, Block>,
   pub descriptors: Vec<(String, bitcoincore_rpc::json::Timestamp)>,
   pub fail_lock_unspent: bool,
+  pub fee_estimate: Option<Amount>,
   pub hashes: Vec<BlockHash>,
   pub loaded_wallets: BTreeSet<String>,
   pub locked: BTreeSet<OutPoint>,
   pub mempool: Vec<Transaction>,
+  pub mempool_chain_limit: Option<usize>,
   pub network: Network,
   pub nonce: u32,
   pub transactions: BTreeMap<Txid, Transaction>,
@@ -36,10 +38,12 @@ impl State {
       change_addresses: Vec::new(),
       descriptors: Vec::new(),
       fail_lock_unspent,
+      fee_estimate: None,
       hashes,
       loaded_wallets: BTreeSet::new(),
       locked: BTreeSet::new(),
       mempool: Vec::new(),
+      mempool_chain_limit: None,
       network,
       nonce: 0,
       receive_addresses: Vec::new(),
@@ -183,6 +187,41 @@ impl State {
     block
   }
 
+  /// Confirmed UTXOs not spent by mempool transactions, along with outputs
+  /// created by mempool transactions
+  pub(crate) fn spendable(&self) -> BTreeMap<OutPoint, TxOut> {
+    let mut spendable = self
+      .utxos
+      .keys()
+      .map(|outpoint| {
+        (
+          *outpoint,
+          self.transactions[&outpoint.txid].output[usize::try_from(outpoint.vout).unwrap()].clone(),
+        )
+      })
+      .collect::<BTreeMap<OutPoint, TxOut>>();
+
+    for tx in &self.mempool {
+      for input in &tx.input {
+        spendable.remove(&input.previous_output);
+      }
+
+      for (vout, tx_out) in tx.output.iter().enumerate() {
+        if !tx_out.script_pubkey.is_op_return() {
+          spendable.insert(
+            OutPoint {
+              txid: tx.compute_txid(),
+              vout: vout.try_into().unwrap(),
+            },
+            tx_out.clone(),
+          );
+        }
+      }
+    }
+
+    spendable
+  }
+
   pub(crate) fn pop_block(&mut self) -> BlockHash {
     let blockhash = self.hashes.pop().unwrap();
     self.blocks.remove(&blockhash);
@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Serialize, Eq, PartialEq, Deserialize, Debug)]
+#[derive(Clone, Serialize, Eq, PartialEq, Deserialize, Debug)]
 pub enum Artifact {
   Cenotaph(Cenotaph),
   Runestone(Runestone),
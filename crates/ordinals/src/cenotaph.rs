@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Serialize, Eq, PartialEq, Deserialize, Debug, Default)]
+#[derive(Clone, Serialize, Eq, PartialEq, Deserialize, Debug, Default)]
 pub struct Cenotaph {
   pub etching: Option<Rune>,
   pub flaw: Option<Flaw>,
@@ -1,5 +1,6 @@
 use {
   super::*,
+  crate::inscription::Tag,
   bitcoin::blockdata::{
     opcodes,
     script::{
@@ -10,19 +11,28 @@ use {
   std::iter::Peekable,
 };
 
-pub(crate) const PROTOCOL_ID: [u8; 3] = *b"ord";
-pub(crate) const BODY_TAG: [u8; 0] = [];
+/// Data push identifying an inscription envelope, following `OP_FALSE OP_IF`
+pub const PROTOCOL_ID: [u8; 3] = *b"ord";
+
+/// Tag separating an envelope's fields from its body
+pub const BODY_TAG: [u8; 0] = [];
 
 type Result<T> = std::result::Result<T, script::Error>;
-type RawEnvelope = Envelope<Vec<Vec<u8>>>;
-pub(crate) type ParsedEnvelope = Envelope<Inscription>;
+
+/// Envelope whose payload is the raw data pushes between `ord` and `OP_ENDIF`
+pub type RawEnvelope = Envelope<Vec<Vec<u8>>>;
+
+/// Envelope whose payload has been parsed into an inscription
+pub type ParsedEnvelope = Envelope<Inscription>;
 
 #[derive(Default, PartialEq, Clone, Serialize, Deserialize, Debug, Eq)]
 pub struct Envelope<T> {
   pub input: u32,
   pub offset: u32,
   pub payload: T,
+  /// Envelope contained pushnum opcodes, which are parsed as data pushes
   pub pushnum: bool,
+  /// Envelope was preceded by an unterminated `OP_FALSE`
   pub stutter: bool,
 }
 
@@ -54,6 +64,7 @@ impl From<RawEnvelope> for ParsedEnvelope {
     let metaprotocol = Tag::Metaprotocol.take(&mut fields);
     let parents = Tag::Parent.take_array(&mut fields);
     let pointer = Tag::Pointer.take(&mut fields);
+    let properties = Tag::Properties.take(&mut fields);
     let rune = Tag::Rune.take(&mut fields);
 
     let unrecognized_even_field = fields
@@ -78,6 +89,7 @@ impl From<RawEnvelope> for ParsedEnvelope {
         metaprotocol,
         parents,
         pointer,
+        properties,
         rune,
         unrecognized_even_field,
       },
@@ -90,7 +102,7 @@ impl From<RawEnvelope> for ParsedEnvelope {
 }
 
 impl ParsedEnvelope {
-  pub(crate) fn from_transaction(transaction: &Transaction) -> Vec<Self> {
+  pub fn from_transaction(transaction: &Transaction) -> Vec<Self> {
     RawEnvelope::from_transaction(transaction)
       .into_iter()
       .map(|envelope| envelope.into())
@@ -99,7 +111,7 @@ impl ParsedEnvelope {
 }
 
 impl RawEnvelope {
-  pub(crate) fn from_transaction(transaction: &Transaction) -> Vec<Self> {
+  pub fn from_transaction(transaction: &Transaction) -> Vec<Self> {
     let mut envelopes = Vec::new();
 
     for (i, input) in transaction.input.iter().enumerate() {
@@ -113,7 +125,8 @@ impl RawEnvelope {
     envelopes
   }
 
-  fn from_tapscript(tapscript: &Script, input: usize) -> Result<Vec<Self>> {
+  /// Envelopes in `tapscript`, spent by the transaction input at index `input`
+  pub fn from_tapscript(tapscript: &Script, input: usize) -> Result<Vec<Self>> {
     let mut envelopes = Vec::new();
 
     let mut instructions = tapscript.instructions().peekable();
@@ -257,7 +270,10 @@ impl RawEnvelope {
 
 #[cfg(test)]
 mod tests {
-  use super::*;
+  use {
+    super::*,
+    bitcoin::{absolute::LockTime, transaction::Version, Sequence, TxIn},
+  };
 
   fn parse(witnesses: &[Witness]) -> Vec<ParsedEnvelope> {
     ParsedEnvelope::from_transaction(&Transaction {
@@ -862,6 +878,27 @@ mod tests {
     );
   }
 
+  #[test]
+  fn properties_are_parsed_correctly_from_chunks() {
+    assert_eq!(
+      parse(&[envelope(&[
+        &PROTOCOL_ID,
+        &Tag::Properties.bytes(),
+        &[0],
+        &Tag::Properties.bytes(),
+        &[1]
+      ])]),
+      vec![ParsedEnvelope {
+        payload: Inscription {
+          properties: Some(vec![0, 1]),
+          duplicate_field: true,
+          ..default()
+        },
+        ..default()
+      }]
+    );
+  }
+
   #[test]
   fn metadata_is_parsed_correctly_from_chunks() {
     assert_eq!(
@@ -0,0 +1,608 @@
+pub use tag::Tag;
+
+use super::*;
+
+mod tag;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Default)]
+pub struct Inscription {
+  pub body: Option<Vec<u8>>,
+  pub content_encoding: Option<Vec<u8>>,
+  pub content_type: Option<Vec<u8>>,
+  pub delegate: Option<Vec<u8>>,
+  /// Envelope contained a field more than once
+  pub duplicate_field: bool,
+  /// Envelope contained a tag without a value
+  pub incomplete_field: bool,
+  pub metadata: Option<Vec<u8>>,
+  pub metaprotocol: Option<Vec<u8>>,
+  pub parents: Vec<Vec<u8>>,
+  pub pointer: Option<Vec<u8>>,
+  /// CBOR-encoded structured properties, such as gallery items
+  pub properties: Option<Vec<u8>>,
+  pub rune: Option<Vec<u8>>,
+  /// Envelope contained an even tag which is not recognized
+  pub unrecognized_even_field: bool,
+}
+
+impl Inscription {
+  pub fn pointer_value(pointer: u64) -> Vec<u8> {
+    let mut bytes = pointer.to_le_bytes().to_vec();
+
+    while bytes.last().copied() == Some(0) {
+      bytes.pop();
+    }
+
+    bytes
+  }
+
+  pub fn append_reveal_script_to_builder(&self, mut builder: script::Builder) -> script::Builder {
+    builder = builder
+      .push_opcode(opcodes::OP_FALSE)
+      .push_opcode(opcodes::all::OP_IF)
+      .push_slice(envelope::PROTOCOL_ID);
+
+    Tag::ContentType.append(&mut builder, &self.content_type);
+    Tag::ContentEncoding.append(&mut builder, &self.content_encoding);
+    Tag::Metaprotocol.append(&mut builder, &self.metaprotocol);
+    Tag::Parent.append_array(&mut builder, &self.parents);
+    Tag::Delegate.append(&mut builder, &self.delegate);
+    Tag::Pointer.append(&mut builder, &self.pointer);
+    Tag::Metadata.append(&mut builder, &self.metadata);
+    Tag::Properties.append(&mut builder, &self.properties);
+    Tag::Rune.append(&mut builder, &self.rune);
+
+    if let Some(body) = &self.body {
+      builder = builder.push_slice(envelope::BODY_TAG);
+      for chunk in body.chunks(MAX_SCRIPT_ELEMENT_SIZE) {
+        builder = builder.push_slice::<&script::PushBytes>(chunk.try_into().unwrap());
+      }
+    }
+
+    builder.push_opcode(opcodes::all::OP_ENDIF)
+  }
+
+  pub fn append_reveal_script(&self, builder: script::Builder) -> ScriptBuf {
+    self.append_reveal_script_to_builder(builder).into_script()
+  }
+
+  pub fn append_batch_reveal_script_to_builder(
+    inscriptions: &[Inscription],
+    mut builder: script::Builder,
+  ) -> script::Builder {
+    for inscription in inscriptions {
+      builder = inscription.append_reveal_script_to_builder(builder);
+    }
+
+    builder
+  }
+
+  pub fn append_batch_reveal_script(
+    inscriptions: &[Inscription],
+    builder: script::Builder,
+  ) -> ScriptBuf {
+    Inscription::append_batch_reveal_script_to_builder(inscriptions, builder).into_script()
+  }
+
+  fn inscription_id_field(field: Option<&[u8]>) -> Option<InscriptionId> {
+    let value = field.as_ref()?;
+
+    if value.len() < Txid::LEN {
+      return None;
+    }
+
+    if value.len() > Txid::LEN + 4 {
+      return None;
+    }
+
+    let (txid, index) = value.split_at(Txid::LEN);
+
+    if let Some(last) = index.last() {
+      // Accept fixed length encoding with 4 bytes (with potential trailing zeroes)
+      // or variable length (no trailing zeroes)
+      if index.len() != 4 && *last == 0 {
+        return None;
+      }
+    }
+
+    let txid = Txid::from_slice(txid).unwrap();
+
+    let index = [
+      index.first().copied().unwrap_or(0),
+      index.get(1).copied().unwrap_or(0),
+      index.get(2).copied().unwrap_or(0),
+      index.get(3).copied().unwrap_or(0),
+    ];
+
+    let index = u32::from_le_bytes(index);
+
+    Some(InscriptionId { txid, index })
+  }
+
+  pub fn body(&self) -> Option<&[u8]> {
+    Some(self.body.as_ref()?)
+  }
+
+  pub fn into_body(self) -> Option<Vec<u8>> {
+    self.body
+  }
+
+  pub fn content_length(&self) -> Option<usize> {
+    Some(self.body()?.len())
+  }
+
+  pub fn content_type(&self) -> Option<&str> {
+    str::from_utf8(self.content_type.as_ref()?).ok()
+  }
+
+  pub fn delegate(&self) -> Option<InscriptionId> {
+    Self::inscription_id_field(self.delegate.as_deref())
+  }
+
+  pub fn metaprotocol(&self) -> Option<&str> {
+    str::from_utf8(self.metaprotocol.as_ref()?).ok()
+  }
+
+  pub fn parents(&self) -> Vec<InscriptionId> {
+    self
+      .parents
+      .iter()
+      .filter_map(|parent| Self::inscription_id_field(Some(parent)))
+      .collect()
+  }
+
+  pub fn pointer(&self) -> Option<u64> {
+    let value = self.pointer.as_ref()?;
+
+    if value.iter().skip(8).copied().any(|byte| byte != 0) {
+      return None;
+    }
+
+    let pointer = [
+      value.first().copied().unwrap_or(0),
+      value.get(1).copied().unwrap_or(0),
+      value.get(2).copied().unwrap_or(0),
+      value.get(3).copied().unwrap_or(0),
+      value.get(4).copied().unwrap_or(0),
+      value.get(5).copied().unwrap_or(0),
+      value.get(6).copied().unwrap_or(0),
+      value.get(7).copied().unwrap_or(0),
+    ];
+
+    Some(u64::from_le_bytes(pointer))
+  }
+
+  /// Witness for a script path spend of a tapscript containing only this
+  /// inscription, with an empty control block
+  pub fn to_witness(&self) -> Witness {
+    let builder = script::Builder::new();
+
+    let script = self.append_reveal_script(builder);
+
+    let mut witness = Witness::new();
+
+    witness.push(script);
+    witness.push([]);
+
+    witness
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reveal_script_chunks_body() {
+    assert_eq!(
+      inscription("foo", [])
+        .append_reveal_script(script::Builder::new())
+        .instructions()
+        .count(),
+      7
+    );
+
+    assert_eq!(
+      inscription("foo", [0; 1])
+        .append_reveal_script(script::Builder::new())
+        .instructions()
+        .count(),
+      8
+    );
+
+    assert_eq!(
+      inscription("foo", [0; 520])
+        .append_reveal_script(script::Builder::new())
+        .instructions()
+        .count(),
+      8
+    );
+
+    assert_eq!(
+      inscription("foo", [0; 521])
+        .append_reveal_script(script::Builder::new())
+        .instructions()
+        .count(),
+      9
+    );
+
+    assert_eq!(
+      inscription("foo", [0; 1040])
+        .append_reveal_script(script::Builder::new())
+        .instructions()
+        .count(),
+      9
+    );
+
+    assert_eq!(
+      inscription("foo", [0; 1041])
+        .append_reveal_script(script::Builder::new())
+        .instructions()
+        .count(),
+      10
+    );
+  }
+
+  #[test]
+  fn reveal_script_chunks_metadata() {
+    assert_eq!(
+      Inscription {
+        metadata: None,
+        ..default()
+      }
+      .append_reveal_script(script::Builder::new())
+      .instructions()
+      .count(),
+      4
+    );
+
+    assert_eq!(
+      Inscription {
+        metadata: Some(Vec::new()),
+        ..default()
+      }
+      .append_reveal_script(script::Builder::new())
+      .instructions()
+      .count(),
+      4
+    );
+
+    assert_eq!(
+      Inscription {
+        metadata: Some(vec![0; 1]),
+        ..default()
+      }
+      .append_reveal_script(script::Builder::new())
+      .instructions()
+      .count(),
+      6
+    );
+
+    assert_eq!(
+      Inscription {
+        metadata: Some(vec![0; 520]),
+        ..default()
+      }
+      .append_reveal_script(script::Builder::new())
+      .instructions()
+      .count(),
+      6
+    );
+
+    assert_eq!(
+      Inscription {
+        metadata: Some(vec![0; 521]),
+        ..default()
+      }
+      .append_reveal_script(script::Builder::new())
+      .instructions()
+      .count(),
+      8
+    );
+  }
+
+  #[test]
+  fn inscription_with_no_parent_field_has_no_parent() {
+    assert!(Inscription {
+      parents: Vec::new(),
+      ..default()
+    }
+    .parents()
+    .is_empty());
+  }
+
+  #[test]
+  fn inscription_with_parent_field_shorter_than_txid_length_has_no_parent() {
+    assert!(Inscription {
+      parents: vec![Vec::new()],
+      ..default()
+    }
+    .parents()
+    .is_empty());
+  }
+
+  #[test]
+  fn inscription_with_parent_field_longer_than_txid_and_index_has_no_parent() {
+    assert!(Inscription {
+      parents: vec![vec![1; 37]],
+      ..default()
+    }
+    .parents()
+    .is_empty());
+  }
+
+  #[test]
+  fn inscription_with_parent_field_index_with_trailing_zeroes_and_fixed_length_has_parent() {
+    let mut parent = vec![1; 36];
+
+    parent[35] = 0;
+
+    assert!(!Inscription {
+      parents: vec![parent],
+      ..default()
+    }
+    .parents()
+    .is_empty());
+  }
+
+  #[test]
+  fn inscription_with_parent_field_index_with_trailing_zeroes_and_variable_length_has_no_parent() {
+    let mut parent = vec![1; 35];
+
+    parent[34] = 0;
+
+    assert!(Inscription {
+      parents: vec![parent],
+      ..default()
+    }
+    .parents()
+    .is_empty());
+  }
+
+  #[test]
+  fn inscription_delegate_txid_is_deserialized_correctly() {
+    assert_eq!(
+      Inscription {
+        delegate: Some(vec![
+          0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+          0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d,
+          0x1e, 0x1f,
+        ]),
+        ..default()
+      }
+      .delegate()
+      .unwrap()
+      .txid,
+      "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100"
+        .parse()
+        .unwrap()
+    );
+  }
+
+  #[test]
+  fn inscription_parent_txid_is_deserialized_correctly() {
+    assert_eq!(
+      Inscription {
+        parents: vec![vec![
+          0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+          0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d,
+          0x1e, 0x1f,
+        ]],
+        ..default()
+      }
+      .parents(),
+      [
+        "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100i0"
+          .parse()
+          .unwrap()
+      ],
+    );
+  }
+
+  #[test]
+  fn inscription_parent_with_zero_byte_index_field_is_deserialized_correctly() {
+    assert_eq!(
+      Inscription {
+        parents: vec![vec![1; 32]],
+        ..default()
+      }
+      .parents(),
+      [
+        "0101010101010101010101010101010101010101010101010101010101010101i0"
+          .parse()
+          .unwrap()
+      ],
+    );
+  }
+
+  #[test]
+  fn inscription_parent_with_one_byte_index_field_is_deserialized_correctly() {
+    assert_eq!(
+      Inscription {
+        parents: vec![vec![
+          0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+          0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+          0xff, 0xff, 0x01
+        ]],
+        ..default()
+      }
+      .parents(),
+      [
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffi1"
+          .parse()
+          .unwrap()
+      ],
+    );
+  }
+
+  #[test]
+  fn inscription_parent_with_two_byte_index_field_is_deserialized_correctly() {
+    assert_eq!(
+      Inscription {
+        parents: vec![vec![
+          0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+          0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+          0xff, 0xff, 0x01, 0x02
+        ]],
+        ..default()
+      }
+      .parents(),
+      [
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffi513"
+          .parse()
+          .unwrap()
+      ],
+    );
+  }
+
+  #[test]
+  fn inscription_parent_with_three_byte_index_field_is_deserialized_correctly() {
+    assert_eq!(
+      Inscription {
+        parents: vec![vec![
+          0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+          0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+          0xff, 0xff, 0x01, 0x02, 0x03
+        ]],
+        ..default()
+      }
+      .parents(),
+      [
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffi197121"
+          .parse()
+          .unwrap()
+      ],
+    );
+  }
+
+  #[test]
+  fn inscription_parent_with_four_byte_index_field_is_deserialized_correctly() {
+    assert_eq!(
+      Inscription {
+        parents: vec![vec![
+          0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+          0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+          0xff, 0xff, 0x01, 0x02, 0x03, 0x04,
+        ]],
+        ..default()
+      }
+      .parents(),
+      [
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffi67305985"
+          .parse()
+          .unwrap()
+      ],
+    );
+  }
+
+  #[test]
+  fn inscription_parent_returns_multiple_parents() {
+    assert_eq!(
+      Inscription {
+        parents: vec![
+          vec![
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0xff, 0x01, 0x02, 0x03, 0x04,
+          ],
+          vec![
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0xff, 0x00, 0x02, 0x03, 0x04,
+          ]
+        ],
+        ..default()
+      }
+      .parents(),
+      [
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffi67305985"
+          .parse()
+          .unwrap(),
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffi67305984"
+          .parse()
+          .unwrap()
+      ],
+    );
+  }
+
+  #[test]
+  fn pointer_decode() {
+    assert_eq!(
+      Inscription {
+        pointer: None,
+        ..default()
+      }
+      .pointer(),
+      None
+    );
+    assert_eq!(
+      Inscription {
+        pointer: Some(vec![0]),
+        ..default()
+      }
+      .pointer(),
+      Some(0),
+    );
+    assert_eq!(
+      Inscription {
+        pointer: Some(vec![1, 2, 3, 4, 5, 6, 7, 8]),
+        ..default()
+      }
+      .pointer(),
+      Some(0x0807060504030201),
+    );
+    assert_eq!(
+      Inscription {
+        pointer: Some(vec![1, 2, 3, 4, 5, 6]),
+        ..default()
+      }
+      .pointer(),
+      Some(0x0000060504030201),
+    );
+    assert_eq!(
+      Inscription {
+        pointer: Some(vec![1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, 0]),
+        ..default()
+      }
+      .pointer(),
+      Some(0x0807060504030201),
+    );
+    assert_eq!(
+      Inscription {
+        pointer: Some(vec![1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, 1]),
+        ..default()
+      }
+      .pointer(),
+      None,
+    );
+    assert_eq!(
+      Inscription {
+        pointer: Some(vec![1, 2, 3, 4, 5, 6, 7, 8, 1]),
+        ..default()
+      }
+      .pointer(),
+      None,
+    );
+  }
+
+  #[test]
+  fn pointer_encode() {
+    assert_eq!(
+      Inscription {
+        pointer: None,
+        ..default()
+      }
+      .to_witness(),
+      envelope(&[b"ord"]),
+    );
+
+    assert_eq!(
+      Inscription {
+        pointer: Some(vec![1, 2, 3]),
+        ..default()
+      }
+      .to_witness(),
+      envelope(&[b"ord", &[2], &[1, 2, 3]]),
+    );
+  }
+}
@@ -1,10 +1,11 @@
 use super::*;
 
-#[derive(Copy, Clone)]
+/// Envelope field tags. Unrecognized even tags make an inscription cursed,
+/// while unrecognized odd tags are ignored.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u8)]
-pub(crate) enum Tag {
+pub enum Tag {
   Pointer = 2,
-  #[allow(unused)]
   Unbound = 66,
 
   ContentType = 1,
@@ -14,22 +15,23 @@ pub(crate) enum Tag {
   ContentEncoding = 9,
   Delegate = 11,
   Rune = 13,
-  #[allow(unused)]
   Note = 15,
-  #[allow(unused)]
+  Properties = 17,
   Nop = 255,
 }
 
 impl Tag {
-  fn chunked(self) -> bool {
-    matches!(self, Self::Metadata)
+  /// Whether the field's value may be split across multiple pushes, each
+  /// preceded by the tag, to exceed the maximum script element size
+  pub fn chunked(self) -> bool {
+    matches!(self, Self::Metadata | Self::Properties)
   }
 
-  pub(crate) fn bytes(self) -> [u8; 1] {
+  pub fn bytes(self) -> [u8; 1] {
     [self as u8]
   }
 
-  pub(crate) fn append(self, builder: &mut script::Builder, value: &Option<Vec<u8>>) {
+  pub fn append(self, builder: &mut script::Builder, value: &Option<Vec<u8>>) {
     if let Some(value) = value {
       let mut tmp = script::Builder::new();
       mem::swap(&mut tmp, builder);
@@ -50,7 +52,7 @@ impl Tag {
     }
   }
 
-  pub(crate) fn append_array(self, builder: &mut script::Builder, values: &Vec<Vec<u8>>) {
+  pub fn append_array(self, builder: &mut script::Builder, values: &Vec<Vec<u8>>) {
     let mut tmp = script::Builder::new();
     mem::swap(&mut tmp, builder);
 
@@ -63,7 +65,7 @@ impl Tag {
     mem::swap(&mut tmp, builder);
   }
 
-  pub(crate) fn take(self, fields: &mut BTreeMap<&[u8], Vec<&[u8]>>) -> Option<Vec<u8>> {
+  pub fn take(self, fields: &mut BTreeMap<&[u8], Vec<&[u8]>>) -> Option<Vec<u8>> {
     if self.chunked() {
       let value = fields.remove(self.bytes().as_slice())?;
 
@@ -89,7 +91,7 @@ impl Tag {
     }
   }
 
-  pub(crate) fn take_array(self, fields: &mut BTreeMap<&[u8], Vec<&[u8]>>) -> Vec<Vec<u8>> {
+  pub fn take_array(self, fields: &mut BTreeMap<&[u8], Vec<&[u8]>>) -> Vec<Vec<u8>> {
     fields
       .remove(self.bytes().as_slice())
       .unwrap_or_default()
@@ -0,0 +1,157 @@
+use super::*;
+
+/// Constructs an inscription from content which has already been loaded,
+/// encoding each field as it appears in an envelope. Reading content from
+/// files, compressing it, and checking content size limits are left to the
+/// caller.
+#[derive(Debug, Default, Clone)]
+pub struct InscriptionBuilder {
+  inscription: Inscription,
+}
+
+impl InscriptionBuilder {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn content(mut self, content_type: &str, body: Vec<u8>) -> Self {
+    self.inscription.content_type = Some(content_type.into());
+    self.inscription.body = Some(body);
+    self
+  }
+
+  /// Encoding of the body, such as `br`, which must already have been applied
+  pub fn content_encoding(mut self, content_encoding: &str) -> Self {
+    self.inscription.content_encoding = Some(content_encoding.into());
+    self
+  }
+
+  pub fn delegate(mut self, delegate: InscriptionId) -> Self {
+    self.inscription.delegate = Some(delegate.value());
+    self
+  }
+
+  /// CBOR-encoded metadata
+  pub fn metadata(mut self, metadata: Vec<u8>) -> Self {
+    self.inscription.metadata = Some(metadata);
+    self
+  }
+
+  pub fn metaprotocol(mut self, metaprotocol: &str) -> Self {
+    self.inscription.metaprotocol = Some(metaprotocol.into());
+    self
+  }
+
+  pub fn parent(mut self, parent: InscriptionId) -> Self {
+    self.inscription.parents.push(parent.value());
+    self
+  }
+
+  pub fn parents(mut self, parents: impl IntoIterator<Item = InscriptionId>) -> Self {
+    self
+      .inscription
+      .parents
+      .extend(parents.into_iter().map(|parent| parent.value()));
+    self
+  }
+
+  pub fn pointer(mut self, pointer: u64) -> Self {
+    self.inscription.pointer = Some(Inscription::pointer_value(pointer));
+    self
+  }
+
+  /// CBOR-encoded properties
+  pub fn properties(mut self, properties: Vec<u8>) -> Self {
+    self.inscription.properties = Some(properties);
+    self
+  }
+
+  /// Commit to `rune`, so that the inscription's reveal may etch it
+  pub fn rune(mut self, rune: Rune) -> Self {
+    self.inscription.rune = Some(rune.commitment());
+    self
+  }
+
+  pub fn build(self) -> Inscription {
+    self.inscription
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, pretty_assertions::assert_eq};
+
+  fn inscription_id(n: u32) -> InscriptionId {
+    InscriptionId {
+      txid: Txid::all_zeros(),
+      index: n,
+    }
+  }
+
+  #[test]
+  fn empty() {
+    assert_eq!(InscriptionBuilder::new().build(), Inscription::default());
+  }
+
+  #[test]
+  fn fields_are_encoded() {
+    let inscription = InscriptionBuilder::new()
+      .content("text/plain", b"foo".to_vec())
+      .content_encoding("br")
+      .delegate(inscription_id(1))
+      .metadata(vec![0xa0])
+      .metaprotocol("bar")
+      .parent(inscription_id(2))
+      .parents([inscription_id(3)])
+      .pointer(256)
+      .properties(vec![0xa0])
+      .rune(Rune(1))
+      .build();
+
+    assert_eq!(inscription.body(), Some(b"foo".as_slice()));
+    assert_eq!(inscription.content_type(), Some("text/plain"));
+    assert_eq!(inscription.content_encoding, Some(b"br".to_vec()));
+    assert_eq!(inscription.delegate(), Some(inscription_id(1)));
+    assert_eq!(inscription.metadata, Some(vec![0xa0]));
+    assert_eq!(inscription.metaprotocol(), Some("bar"));
+    assert_eq!(
+      inscription.parents(),
+      [inscription_id(2), inscription_id(3)]
+    );
+    assert_eq!(inscription.pointer(), Some(256));
+    assert_eq!(inscription.properties, Some(vec![0xa0]));
+    assert_eq!(inscription.rune, Some(Rune(1).commitment()));
+  }
+
+  #[test]
+  fn built_inscriptions_round_trip_through_envelopes() {
+    let inscription = InscriptionBuilder::new()
+      .content("text/plain", b"foo".to_vec())
+      .parent(inscription_id(1))
+      .pointer(1)
+      .build();
+
+    let transaction = Transaction {
+      version: bitcoin::transaction::Version(2),
+      lock_time: bitcoin::absolute::LockTime::ZERO,
+      input: vec![bitcoin::TxIn {
+        previous_output: OutPoint::null(),
+        script_sig: ScriptBuf::new(),
+        sequence: bitcoin::Sequence::ZERO,
+        witness: inscription.to_witness(),
+      }],
+      output: Vec::new(),
+    };
+
+    assert_eq!(
+      ParsedEnvelope::from_transaction(&transaction),
+      [ParsedEnvelope {
+        payload: inscription,
+        input: 0,
+        offset: 0,
+        pushnum: false,
+        stutter: false,
+      }],
+    );
+  }
+}
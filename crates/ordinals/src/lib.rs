@@ -4,55 +4,131 @@
 use {
   bitcoin::{
     consensus::{Decodable, Encodable},
-    constants::{DIFFCHANGE_INTERVAL, SUBSIDY_HALVING_INTERVAL},
+    constants::{DIFFCHANGE_INTERVAL, MAX_SCRIPT_ELEMENT_SIZE, SUBSIDY_HALVING_INTERVAL},
+    hashes::Hash,
     opcodes,
     script::{self, Instruction},
-    Network, OutPoint, ScriptBuf, Transaction,
+    Network, OutPoint, Script, ScriptBuf, Transaction, Txid, Witness,
   },
   derive_more::{Display, FromStr},
   serde::{Deserialize, Serialize},
   serde_with::{DeserializeFromStr, SerializeDisplay},
   std::{
     cmp,
-    collections::{HashMap, VecDeque},
+    collections::{BTreeMap, HashMap, VecDeque},
     fmt::{self, Formatter},
+    mem,
     num::ParseIntError,
     ops::{Add, AddAssign, Sub},
+    str,
   },
   thiserror::Error,
 };
 
 pub use {
-  artifact::Artifact, cenotaph::Cenotaph, charm::Charm, decimal_sat::DecimalSat, degree::Degree,
-  edict::Edict, epoch::Epoch, etching::Etching, flaw::Flaw, height::Height, pile::Pile,
-  rarity::Rarity, rune::Rune, rune_id::RuneId, runestone::Runestone, sat::Sat, sat_point::SatPoint,
-  spaced_rune::SpacedRune, terms::Terms,
+  artifact::Artifact,
+  cenotaph::Cenotaph,
+  charm::Charm,
+  decimal_sat::DecimalSat,
+  degree::Degree,
+  edict::Edict,
+  envelope::{Envelope, ParsedEnvelope, RawEnvelope},
+  epoch::Epoch,
+  etching::Etching,
+  flaw::Flaw,
+  height::Height,
+  inscription::Inscription,
+  inscription_builder::InscriptionBuilder,
+  inscription_id::InscriptionId,
+  pile::Pile,
+  rarity::Rarity,
+  rune::Rune,
+  rune_id::RuneId,
+  runestone::{Explanation, ExplanationEdict, ExplanationField, Runestone},
+  runestone_builder::RunestoneBuilder,
+  sat::Sat,
+  sat_point::SatPoint,
+  spaced_rune::SpacedRune,
+  terms::Terms,
 };
 
 pub const COIN_VALUE: u64 = 100_000_000;
 pub const CYCLE_EPOCHS: u32 = 6;
+pub const MAX_STANDARD_OP_RETURN_SIZE: usize = 83;
 
 fn default<T: Default>() -> T {
   Default::default()
 }
 
+#[cfg(test)]
+fn txid(n: u32) -> Txid {
+  let hex = format!("{n:x}");
+
+  if hex.is_empty() || hex.len() > 1 {
+    panic!();
+  }
+
+  hex.repeat(64).parse().unwrap()
+}
+
+#[cfg(test)]
+fn inscription_id(n: u32) -> InscriptionId {
+  InscriptionId {
+    txid: txid(n),
+    index: n,
+  }
+}
+
+#[cfg(test)]
+fn inscription(content_type: &str, body: impl AsRef<[u8]>) -> Inscription {
+  Inscription {
+    content_type: Some(content_type.into()),
+    body: Some(body.as_ref().into()),
+    ..default()
+  }
+}
+
+#[cfg(test)]
+fn envelope(payload: &[&[u8]]) -> Witness {
+  let mut builder = script::Builder::new()
+    .push_opcode(opcodes::OP_FALSE)
+    .push_opcode(opcodes::all::OP_IF);
+
+  for data in payload {
+    let mut buf = script::PushBytesBuf::new();
+    buf.extend_from_slice(data).unwrap();
+    builder = builder.push_slice(buf);
+  }
+
+  let script = builder.push_opcode(opcodes::all::OP_ENDIF).into_script();
+
+  Witness::from_slice(&[script.into_bytes(), Vec::new()])
+}
+
 mod artifact;
 mod cenotaph;
 mod charm;
 mod decimal_sat;
 mod degree;
 mod edict;
+pub mod envelope;
 mod epoch;
 mod etching;
 mod flaw;
 mod height;
+pub mod inscription;
+pub mod inscription_builder;
+pub mod inscription_id;
 mod pile;
 mod rarity;
 mod rune;
 mod rune_id;
 mod runestone;
+pub mod runestone_builder;
 pub mod sat;
 pub mod sat_point;
 pub mod spaced_rune;
 mod terms;
 pub mod varint;
+#[cfg(feature = "wasm")]
+pub mod wasm;
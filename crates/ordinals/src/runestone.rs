@@ -1,10 +1,13 @@
 use {super::*, flag::Flag, message::Message, tag::Tag};
 
+pub use explanation::{Explanation, ExplanationEdict, ExplanationField};
+
+mod explanation;
 mod flag;
 mod message;
 mod tag;
 
-#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[derive(Clone, Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct Runestone {
   pub edicts: Vec<Edict>,
   pub etching: Option<Etching>,
@@ -41,11 +44,24 @@ impl Runestone {
       }));
     };
 
+    let (artifact, _fields) =
+      Runestone::from_message(transaction, Message::from_integers(transaction, &integers));
+
+    Some(artifact)
+  }
+
+  /// Decipher the artifact encoded by `message`, returning it along with any
+  /// fields which were not consumed, which make it a cenotaph if their tags
+  /// are even.
+  fn from_message(
+    transaction: &Transaction,
+    message: Message,
+  ) -> (Artifact, HashMap<u128, VecDeque<u128>>) {
     let Message {
       mut flaw,
       edicts,
       mut fields,
-    } = Message::from_integers(transaction, &integers);
+    } = message;
 
     let mut flags = Tag::Flags
       .take(&mut fields, |[flags]| Some(flags))
@@ -111,19 +127,25 @@ impl Runestone {
     }
 
     if let Some(flaw) = flaw {
-      return Some(Artifact::Cenotaph(Cenotaph {
-        flaw: Some(flaw),
-        mint,
-        etching: etching.and_then(|etching| etching.rune),
-      }));
+      return (
+        Artifact::Cenotaph(Cenotaph {
+          flaw: Some(flaw),
+          mint,
+          etching: etching.and_then(|etching| etching.rune),
+        }),
+        fields,
+      );
     }
 
-    Some(Artifact::Runestone(Self {
-      edicts,
-      etching,
-      mint,
-      pointer,
-    }))
+    (
+      Artifact::Runestone(Self {
+        edicts,
+        etching,
+        mint,
+        pointer,
+      }),
+      fields,
+    )
   }
 
   pub fn encipher(&self) -> ScriptBuf {
@@ -0,0 +1,418 @@
+use super::*;
+
+/// Step-by-step account of how a transaction's runestone is deciphered. All
+/// offsets are byte offsets into the runestone payload, the concatenation of
+/// the data pushes following `OP_RETURN OP_13`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Explanation {
+  pub edicts: Vec<ExplanationEdict>,
+  pub fields: Vec<ExplanationField>,
+  pub flaw: Option<Flaw>,
+  pub flaw_offset: Option<usize>,
+  pub payload: usize,
+  pub vout: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct ExplanationField {
+  pub name: Option<String>,
+  pub offset: usize,
+  pub tag: u128,
+  pub value: Option<u128>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct ExplanationEdict {
+  pub amount: u128,
+  pub id: RuneId,
+  pub offset: usize,
+  pub output: u128,
+}
+
+impl Runestone {
+  /// Explain how the runestone in `transaction`, if any, is deciphered,
+  /// including the location of the flaw that makes it a cenotaph.
+  pub fn explain(transaction: &Transaction) -> Option<Explanation> {
+    let (vout, output) = transaction.output.iter().enumerate().find(|(_, output)| {
+      let mut instructions = output.script_pubkey.instructions();
+      instructions.next() == Some(Ok(Instruction::Op(opcodes::all::OP_RETURN)))
+        && instructions.next() == Some(Ok(Instruction::Op(Runestone::MAGIC_NUMBER)))
+    })?;
+
+    let mut explanation = Explanation {
+      edicts: Vec::new(),
+      fields: Vec::new(),
+      flaw: None,
+      flaw_offset: None,
+      payload: 0,
+      vout: vout.try_into().unwrap(),
+    };
+
+    let mut payload = Vec::new();
+
+    for result in output.script_pubkey.instructions().skip(2) {
+      let flaw = match result {
+        Ok(Instruction::PushBytes(push)) => {
+          payload.extend_from_slice(push.as_bytes());
+          continue;
+        }
+        Ok(Instruction::Op(_)) => Flaw::Opcode,
+        Err(_) => Flaw::InvalidScript,
+      };
+
+      explanation.flaw = Some(flaw);
+      explanation.flaw_offset = Some(payload.len());
+      explanation.payload = payload.len();
+      return Some(explanation);
+    }
+
+    explanation.payload = payload.len();
+
+    // byte offset of each integer, followed by the offset at which decoding
+    // integers stopped, which precedes the end of the payload if a varint is
+    // invalid
+    let mut offsets = vec![0];
+    while let Some(&offset) = offsets.last().filter(|&&offset| offset < payload.len()) {
+      let Ok((_, length)) = varint::decode(&payload[offset..]) else {
+        break;
+      };
+      offsets.push(offset + length);
+    }
+
+    let end = *offsets.last().unwrap();
+
+    let integers = Runestone::integers(&payload[..end]).unwrap();
+
+    let mut body = None;
+
+    let mut i = 0;
+    while i < integers.len() {
+      let tag = integers[i];
+
+      if Tag::Body == tag {
+        body = Some(i);
+
+        let mut id = RuneId::default();
+        for (j, chunk) in integers[i + 1..].chunks_exact(4).enumerate() {
+          let Some(next) = id.next(chunk[0], chunk[1]) else {
+            break;
+          };
+
+          explanation.edicts.push(ExplanationEdict {
+            amount: chunk[2],
+            id: next,
+            offset: offsets[i + 1 + j * 4],
+            output: chunk[3],
+          });
+
+          id = next;
+        }
+        break;
+      }
+
+      explanation.fields.push(ExplanationField {
+        name: Tag::name(tag).map(str::to_string),
+        offset: offsets[i],
+        tag,
+        value: integers.get(i + 1).copied(),
+      });
+
+      i += 2;
+    }
+
+    if end < payload.len() {
+      explanation.flaw = Some(Flaw::Varint);
+      explanation.flaw_offset = Some(end);
+      return Some(explanation);
+    }
+
+    let message = Message::from_integers(transaction, &integers);
+
+    if let Some(flaw) = message.flaw {
+      // message flaws are found at the truncated field, or at the first edict
+      // which could not be decoded
+      let i = match flaw {
+        Flaw::TruncatedField => integers.len() - 1,
+        _ => body.unwrap() + 1 + message.edicts.len() * 4,
+      };
+
+      explanation.flaw = Some(flaw);
+      explanation.flaw_offset = Some(offsets[i]);
+      return Some(explanation);
+    }
+
+    let (
+      Artifact::Cenotaph(Cenotaph {
+        flaw: Some(flaw), ..
+      }),
+      unconsumed,
+    ) = Runestone::from_message(transaction, message)
+    else {
+      return Some(explanation);
+    };
+
+    // offset of the `n`th field with `tag`
+    let field = |tag: u128, n: usize| {
+      explanation
+        .fields
+        .iter()
+        .filter(|field| field.tag == tag)
+        .nth(n)
+        .map(|field| field.offset)
+    };
+
+    let flaw_offset = match flaw {
+      Flaw::SupplyOverflow => [Tag::Premine, Tag::Cap, Tag::Amount]
+        .into_iter()
+        .filter_map(|tag| field(tag.into(), 0))
+        .max(),
+      Flaw::UnrecognizedFlag => field(Tag::Flags.into(), 0),
+      // fields are consumed in order, so the first unconsumed field with each
+      // tag follows those which were consumed
+      Flaw::UnrecognizedEvenTag => unconsumed
+        .iter()
+        .filter(|(tag, _)| *tag % 2 == 0)
+        .filter_map(|(&tag, values)| {
+          let count = explanation
+            .fields
+            .iter()
+            .filter(|field| field.tag == tag)
+            .count();
+          field(tag, count - values.len())
+        })
+        .min(),
+      _ => None,
+    };
+
+    explanation.flaw = Some(flaw);
+    explanation.flaw_offset = flaw_offset;
+
+    Some(explanation)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    bitcoin::{absolute::LockTime, script::PushBytes, transaction::Version, Amount, TxOut},
+    pretty_assertions::assert_eq,
+  };
+
+  fn transaction(integers: &[u128]) -> Transaction {
+    let mut payload = Vec::new();
+
+    for integer in integers {
+      varint::encode_to_vec(*integer, &mut payload);
+    }
+
+    script_transaction(
+      script::Builder::new()
+        .push_opcode(opcodes::all::OP_RETURN)
+        .push_opcode(Runestone::MAGIC_NUMBER)
+        .push_slice::<&PushBytes>(payload.as_slice().try_into().unwrap())
+        .into_script(),
+    )
+  }
+
+  fn script_transaction(script_pubkey: ScriptBuf) -> Transaction {
+    Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output: vec![TxOut {
+        script_pubkey,
+        value: Amount::from_sat(0),
+      }],
+    }
+  }
+
+  #[test]
+  fn no_runestone() {
+    assert_eq!(
+      Runestone::explain(&script_transaction(ScriptBuf::new())),
+      None
+    );
+  }
+
+  #[test]
+  fn fields_and_edicts() {
+    assert_eq!(
+      Runestone::explain(&transaction(&[
+        Tag::Pointer.into(),
+        0,
+        Tag::Nop.into(),
+        300,
+        Tag::Body.into(),
+        1,
+        2,
+        3,
+        1
+      ]))
+      .unwrap(),
+      Explanation {
+        edicts: vec![ExplanationEdict {
+          amount: 3,
+          id: RuneId { block: 1, tx: 2 },
+          offset: 6,
+          output: 1,
+        }],
+        fields: vec![
+          ExplanationField {
+            name: Some("pointer".into()),
+            offset: 0,
+            tag: 22,
+            value: Some(0),
+          },
+          ExplanationField {
+            name: Some("nop".into()),
+            offset: 2,
+            tag: 127,
+            value: Some(300),
+          },
+        ],
+        flaw: None,
+        flaw_offset: None,
+        payload: 10,
+        vout: 0,
+      },
+    );
+  }
+
+  #[test]
+  fn opcode() {
+    let explanation = Runestone::explain(&script_transaction(
+      script::Builder::new()
+        .push_opcode(opcodes::all::OP_RETURN)
+        .push_opcode(Runestone::MAGIC_NUMBER)
+        .push_slice([1, 2])
+        .push_opcode(opcodes::all::OP_VERIFY)
+        .into_script(),
+    ))
+    .unwrap();
+
+    assert_eq!(explanation.flaw, Some(Flaw::Opcode));
+    assert_eq!(explanation.flaw_offset, Some(2));
+  }
+
+  #[test]
+  fn varint() {
+    let explanation = Runestone::explain(&script_transaction(
+      script::Builder::new()
+        .push_opcode(opcodes::all::OP_RETURN)
+        .push_opcode(Runestone::MAGIC_NUMBER)
+        .push_slice([22, 0, 128])
+        .into_script(),
+    ))
+    .unwrap();
+
+    assert_eq!(explanation.fields.len(), 1);
+    assert_eq!(explanation.flaw, Some(Flaw::Varint));
+    assert_eq!(explanation.flaw_offset, Some(2));
+  }
+
+  #[test]
+  fn truncated_field() {
+    let explanation = Runestone::explain(&transaction(&[Tag::Pointer.into(), 0, 3])).unwrap();
+
+    assert_eq!(explanation.fields[1].value, None);
+    assert_eq!(explanation.flaw, Some(Flaw::TruncatedField));
+    assert_eq!(explanation.flaw_offset, Some(2));
+  }
+
+  #[test]
+  fn unrecognized_even_tag() {
+    let explanation = Runestone::explain(&transaction(&[
+      Tag::Pointer.into(),
+      0,
+      Tag::Mint.into(),
+      1,
+      Tag::Mint.into(),
+      1,
+      Tag::Cenotaph.into(),
+      0,
+    ]))
+    .unwrap();
+
+    assert_eq!(explanation.flaw, Some(Flaw::UnrecognizedEvenTag));
+    assert_eq!(explanation.flaw_offset, Some(6));
+  }
+
+  #[test]
+  fn unconsumed_even_tags() {
+    let explanation = Runestone::explain(&transaction(&[
+      Tag::Nop.into(),
+      0,
+      Tag::Pointer.into(),
+      0,
+      Tag::Pointer.into(),
+      0,
+    ]))
+    .unwrap();
+
+    assert_eq!(explanation.flaw, Some(Flaw::UnrecognizedEvenTag));
+    assert_eq!(explanation.flaw_offset, Some(4));
+
+    let explanation =
+      Runestone::explain(&transaction(&[Tag::Nop.into(), 0, Tag::Rune.into(), 1])).unwrap();
+
+    assert_eq!(explanation.flaw, Some(Flaw::UnrecognizedEvenTag));
+    assert_eq!(explanation.flaw_offset, Some(2));
+  }
+
+  #[test]
+  fn trailing_integers() {
+    let explanation =
+      Runestone::explain(&transaction(&[Tag::Body.into(), 1, 1, 1, 0, 1, 1])).unwrap();
+
+    assert_eq!(explanation.edicts.len(), 1);
+    assert_eq!(explanation.flaw, Some(Flaw::TrailingIntegers));
+    assert_eq!(explanation.flaw_offset, Some(5));
+  }
+
+  #[test]
+  fn offsets_account_for_overlong_varints() {
+    let explanation = Runestone::explain(&script_transaction(
+      script::Builder::new()
+        .push_opcode(opcodes::all::OP_RETURN)
+        .push_opcode(Runestone::MAGIC_NUMBER)
+        .push_slice([0x96, 0x00, 0x80, 0x00, 0x7e, 0x00])
+        .into_script(),
+    ))
+    .unwrap();
+
+    assert_eq!(explanation.fields[0].tag, 22);
+    assert_eq!(explanation.fields[0].value, Some(0));
+    assert_eq!(explanation.fields[1].offset, 4);
+    assert_eq!(explanation.flaw, Some(Flaw::UnrecognizedEvenTag));
+    assert_eq!(explanation.flaw_offset, Some(4));
+  }
+
+  #[test]
+  fn supply_overflow() {
+    let explanation = Runestone::explain(&transaction(&[
+      Tag::Flags.into(),
+      Flag::mask(Flag::Etching) | Flag::mask(Flag::Terms),
+      Tag::Premine.into(),
+      u128::MAX,
+      Tag::Cap.into(),
+      1,
+      Tag::Amount.into(),
+      1,
+    ]))
+    .unwrap();
+
+    assert_eq!(explanation.flaw, Some(Flaw::SupplyOverflow));
+    assert_eq!(explanation.flaw_offset, Some(explanation.fields[3].offset));
+  }
+
+  #[test]
+  fn edict_output() {
+    let explanation =
+      Runestone::explain(&transaction(&[Tag::Body.into(), 1, 1, 1, 0, 0, 1, 1, 2])).unwrap();
+
+    assert_eq!(explanation.edicts.len(), 2);
+    assert_eq!(explanation.flaw, Some(Flaw::EdictOutput));
+    assert_eq!(explanation.flaw_offset, Some(5));
+  }
+}
@@ -14,17 +14,40 @@ pub(super) enum Tag {
   OffsetEnd = 18,
   Mint = 20,
   Pointer = 22,
-  #[allow(unused)]
   Cenotaph = 126,
 
   Divisibility = 1,
   Spacers = 3,
   Symbol = 5,
-  #[allow(unused)]
   Nop = 127,
 }
 
 impl Tag {
+  pub(super) fn name(tag: u128) -> Option<&'static str> {
+    [
+      (Self::Body, "body"),
+      (Self::Flags, "flags"),
+      (Self::Rune, "rune"),
+      (Self::Premine, "premine"),
+      (Self::Cap, "cap"),
+      (Self::Amount, "amount"),
+      (Self::HeightStart, "height-start"),
+      (Self::HeightEnd, "height-end"),
+      (Self::OffsetStart, "offset-start"),
+      (Self::OffsetEnd, "offset-end"),
+      (Self::Mint, "mint"),
+      (Self::Pointer, "pointer"),
+      (Self::Cenotaph, "cenotaph"),
+      (Self::Divisibility, "divisibility"),
+      (Self::Spacers, "spacers"),
+      (Self::Symbol, "symbol"),
+      (Self::Nop, "nop"),
+    ]
+    .into_iter()
+    .find(|(candidate, _)| *candidate == tag)
+    .map(|(_, name)| name)
+  }
+
   pub(super) fn take<const N: usize, T>(
     self,
     fields: &mut HashMap<u128, VecDeque<u128>>,
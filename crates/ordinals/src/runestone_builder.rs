@@ -0,0 +1,365 @@
+use {
+  super::*,
+  bitcoin::{transaction::Version, Amount, TxOut},
+};
+
+/// Incrementally constructs a runestone, checking that it is standard and
+/// will not be deciphered as a cenotaph before it is broadcast.
+#[derive(Debug, Default, Clone)]
+pub struct RunestoneBuilder {
+  edicts: Vec<Edict>,
+  etching: Option<Etching>,
+  mint: Option<RuneId>,
+  no_limit: bool,
+  pointer: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Error)]
+pub enum Error {
+  #[error("runestone would be deciphered as a cenotaph: {0}")]
+  Cenotaph(Flaw),
+  #[error("edict output {output} greater than transaction output count {outputs}")]
+  EdictOutput { output: u32, outputs: usize },
+  #[error("pointer {pointer} out of range of transaction output count {outputs}")]
+  Pointer { pointer: u32, outputs: usize },
+  #[error(
+    "runestone size {size} over maximum standard OP_RETURN size {MAX_STANDARD_OP_RETURN_SIZE}"
+  )]
+  Size { size: usize },
+}
+
+impl RunestoneBuilder {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn edict(mut self, edict: Edict) -> Self {
+    self.edicts.push(edict);
+    self
+  }
+
+  pub fn edicts(mut self, edicts: impl IntoIterator<Item = Edict>) -> Self {
+    self.edicts.extend(edicts);
+    self
+  }
+
+  pub fn etching(mut self, etching: Etching) -> Self {
+    self.etching = Some(etching);
+    self
+  }
+
+  pub fn mint(mut self, id: RuneId) -> Self {
+    self.mint = Some(id);
+    self
+  }
+
+  /// Allow runestones larger than `MAX_STANDARD_OP_RETURN_SIZE`, which will
+  /// not be relayed by default by Bitcoin Core
+  pub fn no_limit(mut self, no_limit: bool) -> Self {
+    self.no_limit = no_limit;
+    self
+  }
+
+  pub fn pointer(mut self, pointer: u32) -> Self {
+    self.pointer = Some(pointer);
+    self
+  }
+
+  /// Edicts in the order they will be encoded. Edicts are sorted by rune ID,
+  /// which minimizes the size of their delta encoding, and adjacent edicts
+  /// with the same rune ID and output are merged. Sorting is stable and edicts
+  /// with an amount of zero are never merged, so the resulting allocation is
+  /// the same as that of the edicts as given.
+  pub fn encoded_edicts(&self) -> Vec<Edict> {
+    let mut edicts = self.edicts.clone();
+
+    edicts.sort_by_key(|edict| edict.id);
+
+    let mut merged = Vec::<Edict>::with_capacity(edicts.len());
+
+    for edict in edicts {
+      if let Some(last) = merged.last_mut() {
+        if last.id == edict.id && last.output == edict.output && last.amount > 0 && edict.amount > 0
+        {
+          if let Some(amount) = last.amount.checked_add(edict.amount) {
+            last.amount = amount;
+            continue;
+          }
+        }
+      }
+
+      merged.push(edict);
+    }
+
+    merged
+  }
+
+  pub fn runestone(&self) -> Runestone {
+    Runestone {
+      edicts: self.encoded_edicts(),
+      etching: self.etching,
+      mint: self.mint,
+      pointer: self.pointer,
+    }
+  }
+
+  /// Size of the runestone's OP_RETURN output script in bytes
+  pub fn size(&self) -> usize {
+    self.runestone().encipher().len()
+  }
+
+  pub fn is_standard(&self) -> bool {
+    self.size() <= MAX_STANDARD_OP_RETURN_SIZE
+  }
+
+  /// Flaw with which the runestone would be deciphered as a cenotaph in a
+  /// transaction with `outputs` outputs, including the runestone's own
+  /// OP_RETURN output, or `None` if it would be deciphered as a runestone.
+  pub fn flaw(&self, outputs: usize) -> Option<Flaw> {
+    let mut output = vec![TxOut {
+      value: Amount::from_sat(0),
+      script_pubkey: self.runestone().encipher(),
+    }];
+
+    output.resize(
+      outputs.max(1),
+      TxOut {
+        value: Amount::from_sat(0),
+        script_pubkey: ScriptBuf::new(),
+      },
+    );
+
+    let transaction = Transaction {
+      version: Version(2),
+      lock_time: bitcoin::absolute::LockTime::ZERO,
+      input: Vec::new(),
+      output,
+    };
+
+    match Runestone::decipher(&transaction) {
+      Some(Artifact::Cenotaph(cenotaph)) => cenotaph.flaw,
+      Some(Artifact::Runestone(_)) | None => None,
+    }
+  }
+
+  /// Build a runestone for a transaction with `outputs` outputs, including the
+  /// runestone's own OP_RETURN output.
+  pub fn build(self, outputs: usize) -> Result<Runestone, Error> {
+    for edict in &self.edicts {
+      if usize::try_from(edict.output).unwrap() > outputs {
+        return Err(Error::EdictOutput {
+          output: edict.output,
+          outputs,
+        });
+      }
+    }
+
+    if let Some(pointer) = self.pointer {
+      if usize::try_from(pointer).unwrap() >= outputs {
+        return Err(Error::Pointer { pointer, outputs });
+      }
+    }
+
+    if let Some(flaw) = self.flaw(outputs) {
+      return Err(Error::Cenotaph(flaw));
+    }
+
+    let size = self.size();
+
+    if !self.no_limit && size > MAX_STANDARD_OP_RETURN_SIZE {
+      return Err(Error::Size { size });
+    }
+
+    Ok(self.runestone())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, pretty_assertions::assert_eq};
+
+  fn edict(tx: u32, amount: u128, output: u32) -> Edict {
+    Edict {
+      id: RuneId { block: 1, tx },
+      amount,
+      output,
+    }
+  }
+
+  #[test]
+  fn build_returns_runestone() {
+    assert_eq!(
+      RunestoneBuilder::new()
+        .edict(edict(0, 1, 1))
+        .pointer(0)
+        .build(2)
+        .unwrap(),
+      Runestone {
+        edicts: vec![edict(0, 1, 1)],
+        pointer: Some(0),
+        ..default()
+      },
+    );
+  }
+
+  #[test]
+  fn edicts_are_sorted_by_rune_id() {
+    assert_eq!(
+      RunestoneBuilder::new()
+        .edicts([edict(2, 1, 0), edict(0, 2, 0), edict(1, 3, 0)])
+        .encoded_edicts(),
+      [edict(0, 2, 0), edict(1, 3, 0), edict(2, 1, 0)],
+    );
+  }
+
+  #[test]
+  fn sorting_is_stable() {
+    assert_eq!(
+      RunestoneBuilder::new()
+        .edicts([edict(1, 1, 1), edict(0, 1, 0), edict(1, 1, 0)])
+        .encoded_edicts(),
+      [edict(0, 1, 0), edict(1, 1, 1), edict(1, 1, 0)],
+    );
+  }
+
+  #[test]
+  fn adjacent_edicts_with_same_output_are_merged() {
+    assert_eq!(
+      RunestoneBuilder::new()
+        .edicts([
+          edict(0, 1, 0),
+          edict(0, 2, 0),
+          edict(0, 3, 1),
+          edict(0, 4, 0)
+        ])
+        .encoded_edicts(),
+      [edict(0, 3, 0), edict(0, 3, 1), edict(0, 4, 0)],
+    );
+  }
+
+  #[test]
+  fn edicts_with_zero_amount_are_not_merged() {
+    assert_eq!(
+      RunestoneBuilder::new()
+        .edicts([edict(0, 0, 0), edict(0, 1, 0)])
+        .encoded_edicts(),
+      [edict(0, 0, 0), edict(0, 1, 0)],
+    );
+  }
+
+  #[test]
+  fn edicts_which_would_overflow_are_not_merged() {
+    assert_eq!(
+      RunestoneBuilder::new()
+        .edicts([edict(0, u128::MAX, 0), edict(0, 1, 0)])
+        .encoded_edicts(),
+      [edict(0, u128::MAX, 0), edict(0, 1, 0)],
+    );
+  }
+
+  #[test]
+  fn merging_reduces_size() {
+    let builder = RunestoneBuilder::new().edicts([edict(0, 1, 0), edict(0, 1, 0)]);
+
+    assert!(
+      builder.size()
+        < Runestone {
+          edicts: vec![edict(0, 1, 0), edict(0, 1, 0)],
+          ..default()
+        }
+        .encipher()
+        .len()
+    );
+  }
+
+  #[test]
+  fn edict_output_may_equal_output_count() {
+    RunestoneBuilder::new()
+      .edict(edict(0, 1, 2))
+      .build(2)
+      .unwrap();
+  }
+
+  #[test]
+  fn edict_output_over_output_count_is_an_error() {
+    assert_eq!(
+      RunestoneBuilder::new().edict(edict(0, 1, 3)).build(2),
+      Err(Error::EdictOutput {
+        output: 3,
+        outputs: 2,
+      }),
+    );
+  }
+
+  #[test]
+  fn pointer_out_of_range_is_an_error() {
+    assert_eq!(
+      RunestoneBuilder::new().pointer(2).build(2),
+      Err(Error::Pointer {
+        pointer: 2,
+        outputs: 2,
+      }),
+    );
+  }
+
+  #[test]
+  fn oversize_runestone_is_an_error() {
+    let builder = RunestoneBuilder::new().edicts((0..20).map(|tx| edict(tx, u128::MAX, 1)));
+
+    let size = builder.size();
+
+    assert!(size > MAX_STANDARD_OP_RETURN_SIZE);
+    assert!(!builder.is_standard());
+
+    assert_eq!(builder.clone().build(2), Err(Error::Size { size }));
+
+    builder.no_limit(true).build(2).unwrap();
+  }
+
+  #[test]
+  fn flaw_predicts_edict_output() {
+    assert_eq!(
+      RunestoneBuilder::new().edict(edict(0, 1, 3)).flaw(2),
+      Some(Flaw::EdictOutput),
+    );
+  }
+
+  #[test]
+  fn flaw_predicts_edict_rune_id() {
+    let builder = RunestoneBuilder::new().edict(Edict {
+      id: RuneId { block: 0, tx: 1 },
+      amount: 1,
+      output: 0,
+    });
+
+    assert_eq!(builder.flaw(1), Some(Flaw::EdictRuneId));
+    assert_eq!(builder.build(1), Err(Error::Cenotaph(Flaw::EdictRuneId)));
+  }
+
+  #[test]
+  fn flaw_predicts_supply_overflow() {
+    let builder = RunestoneBuilder::new().etching(Etching {
+      premine: Some(u128::MAX),
+      terms: Some(Terms {
+        amount: Some(1),
+        cap: Some(1),
+        ..default()
+      }),
+      ..default()
+    });
+
+    assert_eq!(builder.flaw(1), Some(Flaw::SupplyOverflow));
+    assert_eq!(builder.build(1), Err(Error::Cenotaph(Flaw::SupplyOverflow)));
+  }
+
+  #[test]
+  fn valid_runestone_has_no_flaw() {
+    assert_eq!(
+      RunestoneBuilder::new()
+        .edict(edict(0, 1, 0))
+        .mint(RuneId { block: 1, tx: 0 })
+        .flaw(1),
+      None,
+    );
+  }
+}
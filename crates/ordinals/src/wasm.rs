@@ -0,0 +1,100 @@
+//! JavaScript bindings, enabled with the `wasm` feature.
+
+use {
+  super::*,
+  bitcoin::consensus,
+  wasm_bindgen::{prelude::wasm_bindgen, JsError},
+};
+
+/// Decipher a runestone from a consensus-serialized transaction, returning the
+/// resulting artifact as JSON, or `undefined` if the transaction has no
+/// runestone. JSON is returned instead of an object because rune amounts may
+/// exceed `Number.MAX_SAFE_INTEGER`.
+#[wasm_bindgen(js_name = decipherRunestone)]
+pub fn decipher_runestone(transaction: &[u8]) -> Result<Option<String>, JsError> {
+  let transaction = consensus::deserialize::<Transaction>(transaction)?;
+
+  Runestone::decipher(&transaction)
+    .map(|artifact| serde_json::to_string(&artifact))
+    .transpose()
+    .map_err(JsError::from)
+}
+
+/// Minimum rune name which may be etched in a block at `height` on `network`,
+/// one of `bitcoin`, `testnet`, `testnet4`, `signet`, or `regtest`.
+#[wasm_bindgen(js_name = minimumRuneAtHeight)]
+pub fn minimum_rune_at_height(network: &str, height: u32) -> Result<String, JsError> {
+  Ok(Rune::minimum_at_height(network.parse::<Network>()?, Height(height)).to_string())
+}
+
+#[wasm_bindgen(js_name = Sat)]
+pub struct JsSat(Sat);
+
+#[wasm_bindgen(js_class = Sat)]
+impl JsSat {
+  #[wasm_bindgen(constructor)]
+  pub fn new(n: u64) -> Result<JsSat, JsError> {
+    let sat = Sat(n);
+
+    if sat > Sat::LAST {
+      return Err(JsError::new(&format!("sat {n} out of range")));
+    }
+
+    Ok(Self(sat))
+  }
+
+  /// Parse a sat from any of the formats accepted by `ord`, such as a number,
+  /// name, decimal, degree, or percentile.
+  pub fn parse(s: &str) -> Result<JsSat, JsError> {
+    Ok(Self(s.parse()?))
+  }
+
+  #[wasm_bindgen(getter)]
+  pub fn n(&self) -> u64 {
+    self.0.n()
+  }
+
+  pub fn name(&self) -> String {
+    self.0.name()
+  }
+
+  pub fn rarity(&self) -> String {
+    self.0.rarity().to_string()
+  }
+
+  pub fn charms(&self) -> Vec<String> {
+    Charm::charms(self.0.charms())
+      .into_iter()
+      .map(|charm| charm.to_string())
+      .collect()
+  }
+}
+
+#[wasm_bindgen(js_name = SpacedRune)]
+pub struct JsSpacedRune(SpacedRune);
+
+#[wasm_bindgen(js_class = SpacedRune)]
+impl JsSpacedRune {
+  /// Parse a rune name, which may contain `.` or `•` as spacers
+  #[wasm_bindgen(constructor)]
+  pub fn new(s: &str) -> Result<JsSpacedRune, JsError> {
+    Ok(Self(s.parse()?))
+  }
+
+  /// Rune name without spacers
+  #[wasm_bindgen(getter)]
+  pub fn rune(&self) -> String {
+    self.0.rune.to_string()
+  }
+
+  /// Bitfield of spacer positions
+  #[wasm_bindgen(getter)]
+  pub fn spacers(&self) -> u32 {
+    self.0.spacers
+  }
+
+  #[wasm_bindgen(js_name = toString)]
+  pub fn to_js_string(&self) -> String {
+    self.0.to_string()
+  }
+}
@@ -0,0 +1,81 @@
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use {
+  bitcoin::{absolute::LockTime, consensus, transaction::Version, Amount, Transaction, TxOut},
+  ordinals::{
+    wasm::{decipher_runestone, minimum_rune_at_height, JsSat, JsSpacedRune},
+    Edict, RuneId, Runestone,
+  },
+  wasm_bindgen_test::wasm_bindgen_test,
+};
+
+#[wasm_bindgen_test]
+fn sat() {
+  let sat = JsSat::new(0).unwrap();
+  assert_eq!(sat.n(), 0);
+  assert_eq!(sat.name(), "nvtdijuwxlp");
+  assert_eq!(sat.rarity(), "mythic");
+  assert_eq!(sat.charms(), ["coin", "mythic", "palindrome"]);
+
+  assert_eq!(JsSat::parse("nvtdijuwxlo").unwrap().n(), 1);
+
+  assert!(JsSat::new(u64::MAX).is_err());
+}
+
+#[wasm_bindgen_test]
+fn spaced_rune() {
+  let spaced_rune = JsSpacedRune::new("A•B.C").unwrap();
+  assert_eq!(spaced_rune.rune(), "ABC");
+  assert_eq!(spaced_rune.spacers(), 0b11);
+  assert_eq!(spaced_rune.to_js_string(), "A•B•C");
+
+  assert!(JsSpacedRune::new("A••B").is_err());
+}
+
+#[wasm_bindgen_test]
+fn minimum_rune() {
+  assert_eq!(
+    minimum_rune_at_height("testnet", 0).unwrap(),
+    "AAAAAAAAAAAAA"
+  );
+
+  assert!(minimum_rune_at_height("foo", 0).is_err());
+}
+
+#[wasm_bindgen_test]
+fn runestone() {
+  let transaction = |script_pubkey| Transaction {
+    version: Version(2),
+    lock_time: LockTime::ZERO,
+    input: Vec::new(),
+    output: vec![TxOut {
+      script_pubkey,
+      value: Amount::ZERO,
+    }],
+  };
+
+  let runestone = Runestone {
+    edicts: vec![Edict {
+      id: RuneId::new(1, 1).unwrap(),
+      amount: u128::MAX,
+      output: 0,
+    }],
+    ..Default::default()
+  };
+
+  let json = decipher_runestone(&consensus::serialize(&transaction(runestone.encipher())))
+    .unwrap()
+    .unwrap();
+
+  assert_eq!(
+    serde_json::from_str::<ordinals::Artifact>(&json).unwrap(),
+    ordinals::Artifact::Runestone(runestone),
+  );
+
+  assert_eq!(
+    decipher_runestone(&consensus::serialize(&transaction(Default::default()))).unwrap(),
+    None,
+  );
+
+  assert!(decipher_runestone(&[0]).is_err());
+}
@@ -49,6 +49,7 @@ pub struct BlockInfo {
   pub bits: u32,
   #[serde(with = "SerHex::<Strict>")]
   pub chainwork: [u8; 32],
+  pub checksum: Option<sha256::Hash>,
   pub confirmations: i32,
   pub difficulty: f64,
   pub hash: BlockHash,
@@ -104,6 +105,7 @@ pub struct Inscription {
   pub next: Option<InscriptionId>,
   pub number: i32,
   pub parents: Vec<InscriptionId>,
+  pub pending_spend: Option<Txid>,
   pub previous: Option<InscriptionId>,
   pub rune: Option<SpacedRune>,
   pub sat: Option<ordinals::Sat>,
@@ -159,12 +161,30 @@ pub struct UtxoRecursive {
   pub value: u64,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct MempoolInscriptions {
+  pub created: Vec<PendingInscription>,
+  pub moving: BTreeMap<InscriptionId, Txid>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct MempoolRunes {
+  pub transactions: BTreeMap<Txid, MempoolRuneTransaction>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct MempoolRuneTransaction {
+  pub artifact: Option<Artifact>,
+  pub spent: BTreeMap<SpacedRune, Pile>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct Output {
   pub address: Option<Address<NetworkUnchecked>>,
   pub indexed: bool,
   pub inscriptions: Option<Vec<InscriptionId>>,
   pub outpoint: OutPoint,
+  pub pending_spend: Option<Txid>,
   pub runes: Option<BTreeMap<SpacedRune, Pile>>,
   pub sat_ranges: Option<Vec<(u64, u64)>>,
   pub script_pubkey: ScriptBuf,
@@ -192,6 +212,7 @@ impl Output {
       indexed,
       inscriptions,
       outpoint,
+      pending_spend: None,
       runes,
       sat_ranges,
       script_pubkey: tx_out.script_pubkey,
@@ -202,6 +223,15 @@ impl Output {
   }
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct PendingInscription {
+  pub content_length: Option<usize>,
+  pub content_type: Option<String>,
+  pub id: InscriptionId,
+  pub metaprotocol: Option<String>,
+  pub parents: Vec<InscriptionId>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Sat {
   pub address: Option<String>,
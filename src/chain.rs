@@ -1,6 +1,8 @@
 use {super::*, clap::ValueEnum};
 
-#[derive(Default, ValueEnum, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(
+  Default, ValueEnum, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
 #[serde(rename_all = "kebab-case")]
 pub enum Chain {
   #[default]
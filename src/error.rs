@@ -15,7 +15,7 @@ pub enum SnafuError {
   },
   #[snafu(display("Failed to parse inscription ID `{}`", input))]
   InscriptionIdParse {
-    source: inscriptions::inscription_id::ParseError,
+    source: inscription_id::ParseError,
     input: String,
   },
   #[snafu(display("Failed to parse integer `{}`", input))]
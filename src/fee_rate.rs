@@ -1,8 +1,26 @@
-use super::*;
+use {super::*, bitcoincore_rpc::json::EstimateMode};
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct FeeRate(f64);
 
+impl Display for FeeRate {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl Serialize for FeeRate {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(self.0)
+  }
+}
+
+impl<'de> Deserialize<'de> for FeeRate {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    Self::try_from(f64::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+  }
+}
+
 impl FromStr for FeeRate {
   type Err = Error;
 
@@ -34,6 +52,77 @@ impl FeeRate {
   }
 }
 
+/// Help text for `--fee-rate` arguments which accept a `FeeRateTarget`
+pub(crate) const FEE_RATE_HELP: &str = "Use fee rate of <FEE_RATE> sats/vB, or estimate fee rate \
+  for `economy`, `normal`, `priority`, or `conf:<BLOCKS>` confirmation target.";
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum FeeRateTarget {
+  Confirmations(u16),
+  Economy,
+  Normal,
+  Priority,
+  Rate(FeeRate),
+}
+
+impl FromStr for FeeRateTarget {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "economy" => Ok(Self::Economy),
+      "normal" => Ok(Self::Normal),
+      "priority" => Ok(Self::Priority),
+      _ => {
+        if let Some(blocks) = s.strip_prefix("conf:") {
+          let blocks = blocks
+            .parse::<u16>()
+            .with_context(|| format!("invalid confirmation target: {blocks}"))?;
+
+          ensure!(blocks > 0, "confirmation target must be at least one block");
+
+          Ok(Self::Confirmations(blocks))
+        } else {
+          Ok(Self::Rate(s.parse()?))
+        }
+      }
+    }
+  }
+}
+
+impl FeeRateTarget {
+  pub(crate) fn resolve(self, client: &Client, max: Option<FeeRate>) -> Result<FeeRate> {
+    let (blocks, mode) = match self {
+      Self::Confirmations(blocks) => (blocks, EstimateMode::Unset),
+      Self::Economy => (144, EstimateMode::Economical),
+      Self::Normal => (6, EstimateMode::Conservative),
+      Self::Priority => (2, EstimateMode::Conservative),
+      Self::Rate(fee_rate) => return Ok(fee_rate),
+    };
+
+    let estimate = client.estimate_smart_fee(blocks, Some(mode))?;
+
+    let Some(per_kvb) = estimate.fee_rate else {
+      bail!(
+        "failed to estimate fee rate for {blocks} block confirmation target: {}",
+        estimate.errors.unwrap_or_default().join(", ")
+      );
+    };
+
+    #[allow(clippy::cast_precision_loss)]
+    let fee_rate = FeeRate::try_from(per_kvb.to_sat() as f64 / 1000.0)?;
+
+    if let Some(max) = max {
+      ensure!(
+        fee_rate.0 <= max.0,
+        "estimated fee rate of {fee_rate} sats/vB exceeds maximum fee rate of {max} sats/vB",
+      );
+    }
+
+    Ok(fee_rate)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -48,6 +137,47 @@ mod tests {
     assert!(FeeRate::try_from(f64::NAN).is_err());
   }
 
+  #[test]
+  fn parse_target() {
+    assert_eq!(
+      "economy".parse::<FeeRateTarget>().unwrap(),
+      FeeRateTarget::Economy
+    );
+    assert_eq!(
+      "normal".parse::<FeeRateTarget>().unwrap(),
+      FeeRateTarget::Normal
+    );
+    assert_eq!(
+      "priority".parse::<FeeRateTarget>().unwrap(),
+      FeeRateTarget::Priority
+    );
+    assert_eq!(
+      "conf:3".parse::<FeeRateTarget>().unwrap(),
+      FeeRateTarget::Confirmations(3)
+    );
+    assert_eq!(
+      "2.5".parse::<FeeRateTarget>().unwrap(),
+      FeeRateTarget::Rate(FeeRate(2.5))
+    );
+    assert!("conf:0".parse::<FeeRateTarget>().is_err());
+    assert!("conf:foo".parse::<FeeRateTarget>().is_err());
+    assert!("fast".parse::<FeeRateTarget>().is_err());
+  }
+
+  #[test]
+  fn serde() {
+    assert_eq!(serde_json::to_string(&FeeRate(2.5)).unwrap(), "2.5");
+    assert_eq!(
+      serde_json::from_str::<FeeRate>("2.5").unwrap(),
+      FeeRate(2.5)
+    );
+    assert_eq!(
+      serde_yaml::from_str::<FeeRate>("50").unwrap(),
+      FeeRate(50.0)
+    );
+    assert!(serde_json::from_str::<FeeRate>("-1").is_err());
+  }
+
   #[test]
   fn fee() {
     assert_eq!(
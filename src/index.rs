@@ -1,10 +1,12 @@
 use {
   self::{
+    checksum::Checksum,
     entry::{
       Entry, HeaderValue, InscriptionEntry, InscriptionEntryValue, InscriptionIdValue,
       OutPointValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange, TxidValue,
     },
     event::Event,
+    follower::Follower,
     lot::Lot,
     reorg::Reorg,
     updater::Updater,
@@ -32,16 +34,23 @@ use {
   std::{
     collections::HashMap,
     io::{BufWriter, Write},
-    sync::Once,
+    sync::{Once, RwLock},
   },
 };
 
+pub(crate) use self::backfill::Feature;
 pub use self::entry::RuneEntry;
 
+mod backfill;
+mod checksum;
 pub(crate) mod entry;
 pub mod event;
 mod fetcher;
+mod follower;
 mod lot;
+pub(crate) mod mempool;
+mod migration;
+mod prune;
 mod reorg;
 mod rtx;
 mod updater;
@@ -50,12 +59,13 @@ mod utxo_entry;
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 30;
+const SCHEMA_VERSION: u64 = 32;
 
 define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
 define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
 define_multimap_table! { SCRIPT_PUBKEY_TO_OUTPOINT, &[u8], OutPointValue }
 define_table! { HEIGHT_TO_BLOCK_HEADER, u32, &HeaderValue }
+define_table! { HEIGHT_TO_CHECKSUM, u32, &[u8; 32] }
 define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u32, u32 }
 define_table! { HOME_INSCRIPTIONS, u32, InscriptionIdValue }
 define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
@@ -66,6 +76,7 @@ define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
 define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
 define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
 define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY, u32, InscriptionEntryValue }
+define_table! { SEQUENCE_NUMBER_TO_PROPERTIES, u32, &[u8] }
 define_table! { SEQUENCE_NUMBER_TO_RUNE_ID, u32, RuneIdValue }
 define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
 define_table! { STATISTIC_TO_COUNT, u64, u64 }
@@ -92,6 +103,9 @@ pub(crate) enum Statistic {
   SatRanges = 14,
   UnboundInscriptions = 16,
   LastSavepointHeight = 17,
+  BackfillFeature = 18,
+  BackfillProgress = 19,
+  PrunedHeight = 20,
 }
 
 impl Statistic {
@@ -119,7 +133,7 @@ pub struct Info {
   page_size: usize,
   sat_ranges: u64,
   stored_bytes: u64,
-  tables: BTreeMap<String, TableInfo>,
+  pub tables: BTreeMap<String, TableInfo>,
   total_bytes: u64,
   pub transactions: Vec<TransactionInfo>,
   tree_height: u32,
@@ -127,21 +141,23 @@ pub struct Info {
 }
 
 #[derive(Serialize)]
-pub(crate) struct TableInfo {
-  branch_pages: u64,
-  fragmented_bytes: u64,
-  leaf_pages: u64,
-  metadata_bytes: u64,
-  proportion: f64,
-  stored_bytes: u64,
-  total_bytes: u64,
-  tree_height: u32,
+pub struct TableInfo {
+  pub branch_pages: u64,
+  pub entries: u64,
+  pub fragmented_bytes: u64,
+  pub leaf_pages: u64,
+  pub metadata_bytes: u64,
+  pub proportion: f64,
+  pub stored_bytes: u64,
+  pub total_bytes: u64,
+  pub tree_height: u32,
 }
 
 impl From<TableStats> for TableInfo {
   fn from(stats: TableStats) -> Self {
     Self {
       branch_pages: stats.branch_pages(),
+      entries: 0,
       fragmented_bytes: stats.fragmented_bytes(),
       leaf_pages: stats.leaf_pages(),
       metadata_bytes: stats.metadata_bytes(),
@@ -193,9 +209,10 @@ impl<T> BitcoinCoreRpcResultExt<T> for Result<T, bitcoincore_rpc::Error> {
 
 pub struct Index {
   pub(crate) client: Client,
-  database: Database,
+  database: RwLock<Arc<Database>>,
   durability: redb::Durability,
   event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
+  follower: Option<Follower>,
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
   height_limit: Option<u32>,
@@ -216,9 +233,24 @@ impl Index {
     Index::open_with_event_sender(settings, None)
   }
 
+  /// Open the snapshot of an index published by a separate `ord index update
+  /// --snapshot` process. The index file is never opened, and `update` loads
+  /// newly published snapshots instead of indexing new blocks.
+  pub fn open_read_only(settings: &Settings) -> Result<Self> {
+    Index::open_inner(settings, None, true)
+  }
+
   pub fn open_with_event_sender(
     settings: &Settings,
     event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
+  ) -> Result<Self> {
+    Index::open_inner(settings, event_sender, false)
+  }
+
+  fn open_inner(
+    settings: &Settings,
+    event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
+    read_only: bool,
   ) -> Result<Self> {
     let client = settings.bitcoin_rpc_client(None)?;
 
@@ -262,154 +294,106 @@ impl Index {
       }
     };
 
-    let database = match Database::builder()
-      .set_cache_size(index_cache_size)
-      .set_repair_callback(repair_callback)
-      .open(&path)
-    {
-      Ok(database) => {
-        {
-          let schema_version = database
-            .begin_read()?
-            .open_table(STATISTIC_TO_COUNT)?
-            .get(&Statistic::Schema.key())?
-            .map(|x| x.value())
-            .unwrap_or(0);
-
-          match schema_version.cmp(&SCHEMA_VERSION) {
-            cmp::Ordering::Less =>
-              bail!(
-                "index at `{}` appears to have been built with an older, incompatible version of ord, consider deleting and rebuilding the index: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
-                path.display()
-              ),
-            cmp::Ordering::Greater =>
-              bail!(
-                "index at `{}` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
-                path.display()
-              ),
-            cmp::Ordering::Equal => {
-            }
-          }
-        }
+    let mut follower = None;
 
-        database
-      }
-      Err(DatabaseError::Storage(StorageError::Io(error)))
-        if error.kind() == io::ErrorKind::NotFound =>
+    let database = if read_only {
+      let (database, loaded) = follower::open(&path, index_cache_size)?;
+      follower = Some(loaded);
+      database
+    } else {
+      match Database::builder()
+        .set_cache_size(index_cache_size)
+        .set_repair_callback(repair_callback)
+        .open(&path)
       {
-        let database = Database::builder()
-          .set_cache_size(index_cache_size)
-          .create(&path)?;
-
-        let mut tx = database.begin_write()?;
-
-        tx.set_durability(durability);
-        tx.set_quick_repair(true);
-
-        tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
-        tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
-        tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
-        tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
-        tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
-        tx.open_table(HOME_INSCRIPTIONS)?;
-        tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
-        tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
-        tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
-        tx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
-        tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
-        tx.open_table(RUNE_TO_RUNE_ID)?;
-        tx.open_table(SAT_TO_SATPOINT)?;
-        tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
-        tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
-        tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
-        tx.open_table(TRANSACTION_ID_TO_RUNE)?;
-        tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
-
-        {
-          let mut statistics = tx.open_table(STATISTIC_TO_COUNT)?;
-
-          Self::set_statistic(
-            &mut statistics,
-            Statistic::IndexAddresses,
-            u64::from(settings.index_addresses_raw()),
-          )?;
-
-          Self::set_statistic(
-            &mut statistics,
-            Statistic::IndexInscriptions,
-            u64::from(settings.index_inscriptions_raw()),
-          )?;
-
-          Self::set_statistic(
-            &mut statistics,
-            Statistic::IndexRunes,
-            u64::from(settings.index_runes_raw()),
-          )?;
-
-          Self::set_statistic(
-            &mut statistics,
-            Statistic::IndexSats,
-            u64::from(settings.index_sats_raw()),
-          )?;
-
-          Self::set_statistic(
-            &mut statistics,
-            Statistic::IndexTransactions,
-            u64::from(settings.index_transactions_raw()),
-          )?;
-
-          Self::set_statistic(&mut statistics, Statistic::Schema, SCHEMA_VERSION)?;
+        Ok(database) => {
+          migration::migrate(&database, &path, settings)?;
+          Self::check_schema(&database, &path)?;
+          database
         }
+        Err(DatabaseError::Storage(StorageError::Io(error)))
+          if error.kind() == io::ErrorKind::NotFound =>
+        {
+          let database = Database::builder()
+            .set_cache_size(index_cache_size)
+            .create(&path)?;
+
+          let mut tx = database.begin_write()?;
+
+          tx.set_durability(durability);
+          tx.set_quick_repair(true);
+
+          tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+          tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+          tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+          tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+          tx.open_table(HEIGHT_TO_CHECKSUM)?;
+          tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+          tx.open_table(HOME_INSCRIPTIONS)?;
+          tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+          tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+          tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+          tx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+          tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+          tx.open_table(RUNE_TO_RUNE_ID)?;
+          tx.open_table(SAT_TO_SATPOINT)?;
+          tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+          tx.open_table(SEQUENCE_NUMBER_TO_PROPERTIES)?;
+          tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
+          tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+          tx.open_table(TRANSACTION_ID_TO_RUNE)?;
+          tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
+
+          {
+            let mut statistics = tx.open_table(STATISTIC_TO_COUNT)?;
+
+            Self::set_statistic(
+              &mut statistics,
+              Statistic::IndexAddresses,
+              u64::from(settings.index_addresses_raw()),
+            )?;
+
+            Self::set_statistic(
+              &mut statistics,
+              Statistic::IndexInscriptions,
+              u64::from(settings.index_inscriptions_raw()),
+            )?;
+
+            Self::set_statistic(
+              &mut statistics,
+              Statistic::IndexRunes,
+              u64::from(settings.index_runes_raw()),
+            )?;
+
+            Self::set_statistic(
+              &mut statistics,
+              Statistic::IndexSats,
+              u64::from(settings.index_sats_raw()),
+            )?;
+
+            Self::set_statistic(
+              &mut statistics,
+              Statistic::IndexTransactions,
+              u64::from(settings.index_transactions_raw()),
+            )?;
+
+            Self::set_statistic(&mut statistics, Statistic::Schema, SCHEMA_VERSION)?;
+          }
 
-        if settings.index_runes_raw() && settings.chain() == Chain::Mainnet {
-          let rune = Rune(2055900680524219742);
-
-          let id = RuneId { block: 1, tx: 0 };
-          let etching = Txid::all_zeros();
-
-          tx.open_table(RUNE_TO_RUNE_ID)?
-            .insert(rune.store(), id.store())?;
-
-          let mut statistics = tx.open_table(STATISTIC_TO_COUNT)?;
-
-          Self::set_statistic(&mut statistics, Statistic::Runes, 1)?;
-
-          tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?.insert(
-            id.store(),
-            RuneEntry {
-              block: id.block,
-              burned: 0,
-              divisibility: 0,
-              etching,
-              terms: Some(Terms {
-                amount: Some(1),
-                cap: Some(u128::MAX),
-                height: (
-                  Some((SUBSIDY_HALVING_INTERVAL * 4).into()),
-                  Some((SUBSIDY_HALVING_INTERVAL * 5).into()),
-                ),
-                offset: (None, None),
-              }),
-              mints: 0,
-              number: 0,
-              premine: 0,
-              spaced_rune: SpacedRune { rune, spacers: 128 },
-              symbol: Some('\u{29C9}'),
-              timestamp: 0,
-              turbo: true,
-            }
-            .store(),
-          )?;
-
-          tx.open_table(TRANSACTION_ID_TO_RUNE)?
-            .insert(&etching.store(), rune.store())?;
-        }
+          if settings.index_runes_raw() && settings.chain() == Chain::Mainnet {
+            Self::insert_genesis_rune(&tx)?;
+          }
 
-        tx.commit()?;
+          tx.commit()?;
 
-        database
+          database
+        }
+        Err(DatabaseError::DatabaseAlreadyOpen) => bail!(
+          "failed to open index: index at `{}` is in use by another process, such as `ord server`",
+          path.display()
+        ),
+        Err(error) => bail!("failed to open index: {error}"),
       }
-      Err(error) => bail!("failed to open index: {error}"),
     };
 
     let index_addresses;
@@ -444,10 +428,11 @@ impl Index {
     Ok(Self {
       genesis_block_coinbase_txid: genesis_block_coinbase_transaction.compute_txid(),
       client,
-      database,
+      database: RwLock::new(Arc::new(database)),
       durability,
       event_sender,
       first_index_height,
+      follower,
       genesis_block_coinbase_transaction,
       height_limit: settings.height_limit(),
       index_addresses,
@@ -462,6 +447,80 @@ impl Index {
     })
   }
 
+  /// On mainnet the first rune, UNCOMMON•GOODS, is hardcoded rather than etched
+  fn insert_genesis_rune(tx: &WriteTransaction) -> Result {
+    let rune = Rune(2055900680524219742);
+
+    let id = RuneId { block: 1, tx: 0 };
+    let etching = Txid::all_zeros();
+
+    tx.open_table(RUNE_TO_RUNE_ID)?
+      .insert(rune.store(), id.store())?;
+
+    let mut statistics = tx.open_table(STATISTIC_TO_COUNT)?;
+
+    Self::set_statistic(&mut statistics, Statistic::Runes, 1)?;
+
+    tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?.insert(
+      id.store(),
+      RuneEntry {
+        block: id.block,
+        burned: 0,
+        divisibility: 0,
+        etching,
+        terms: Some(Terms {
+          amount: Some(1),
+          cap: Some(u128::MAX),
+          height: (
+            Some((SUBSIDY_HALVING_INTERVAL * 4).into()),
+            Some((SUBSIDY_HALVING_INTERVAL * 5).into()),
+          ),
+          offset: (None, None),
+        }),
+        mints: 0,
+        number: 0,
+        premine: 0,
+        spaced_rune: SpacedRune { rune, spacers: 128 },
+        symbol: Some('\u{29C9}'),
+        timestamp: 0,
+        turbo: true,
+      }
+      .store(),
+    )?;
+
+    tx.open_table(TRANSACTION_ID_TO_RUNE)?
+      .insert(&etching.store(), rune.store())?;
+
+    Ok(())
+  }
+
+  fn check_schema(database: &Database, path: &Path) -> Result {
+    let schema_version = database
+      .begin_read()?
+      .open_table(STATISTIC_TO_COUNT)?
+      .get(&Statistic::Schema.key())?
+      .map(|x| x.value())
+      .unwrap_or(0);
+
+    match schema_version.cmp(&SCHEMA_VERSION) {
+      cmp::Ordering::Less =>
+        bail!(
+          "index at `{}` appears to have been built with an older, incompatible version of ord, consider deleting and rebuilding the index: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
+          path.display()
+        ),
+      cmp::Ordering::Greater =>
+        bail!(
+          "index at `{}` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
+          path.display()
+        ),
+      cmp::Ordering::Equal => Ok(()),
+    }
+  }
+
+  fn database(&self) -> Arc<Database> {
+    self.database.read().unwrap().clone()
+  }
+
   pub fn have_full_utxo_index(&self) -> bool {
     self.first_index_height == 0
   }
@@ -483,7 +542,7 @@ impl Index {
   pub fn contains_output(&self, output: &OutPoint) -> Result<bool> {
     Ok(
       self
-        .database
+        .database()
         .begin_read()?
         .open_table(OUTPOINT_TO_UTXO_ENTRY)?
         .get(&output.store())?
@@ -508,7 +567,7 @@ impl Index {
   }
 
   pub fn status(&self, json_api: bool) -> Result<StatusHtml> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let statistic_to_count = rtx.open_table(STATISTIC_TO_COUNT)?;
 
@@ -555,27 +614,44 @@ impl Index {
       started: self.started,
       transaction_index: statistic(Statistic::IndexTransactions)? != 0,
       unrecoverably_reorged: self.unrecoverably_reorged.load(atomic::Ordering::Relaxed),
+      replication_lag: self.follower.as_ref().map(|_| {
+        follower::committed(&self.path)
+          .unwrap_or_default()
+          .saturating_sub(next_height)
+      }),
       uptime: (Utc::now() - self.started).to_std()?,
     })
   }
 
   pub fn info(&self) -> Result<Info> {
-    let stats = self.database.begin_write()?.stats()?;
+    let stats = self.database().begin_write()?.stats()?;
 
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let mut tables: BTreeMap<String, TableInfo> = BTreeMap::new();
 
     for handle in rtx.list_tables()? {
       let name = handle.name().into();
-      let stats = rtx.open_untyped_table(handle)?.stats()?;
-      tables.insert(name, stats.into());
+      let table = rtx.open_untyped_table(handle)?;
+      tables.insert(
+        name,
+        TableInfo {
+          entries: table.len()?,
+          ..table.stats()?.into()
+        },
+      );
     }
 
     for handle in rtx.list_multimap_tables()? {
       let name = handle.name().into();
-      let stats = rtx.open_untyped_multimap_table(handle)?.stats()?;
-      tables.insert(name, stats.into());
+      let table = rtx.open_untyped_multimap_table(handle)?;
+      tables.insert(
+        name,
+        TableInfo {
+          entries: table.len()?,
+          ..table.stats()?.into()
+        },
+      );
     }
 
     for table in rtx.list_tables()? {
@@ -645,7 +721,72 @@ impl Index {
     Ok(info)
   }
 
+  /// Compact the index file. Reorg savepoints prevent compaction, so they
+  /// are deleted first, and a single savepoint is created at the current
+  /// height once compaction is complete, so reorgs of blocks below that height
+  /// can no longer be rolled back. Returns the height of the new savepoint, if
+  /// one was created.
+  pub(crate) fn compact(&self) -> Result<Option<u32>> {
+    ensure!(self.follower.is_none(), "cannot compact a read-only index");
+
+    Reorg::delete_savepoints(self)?;
+
+    Arc::get_mut(&mut self.database.write().unwrap())
+      .context("cannot compact index while it is in use")?
+      .compact()?;
+
+    let height = self.block_count()?;
+
+    Ok(Reorg::create_savepoint(self, height.into())?.then_some(height))
+  }
+
+  /// Publish a snapshot of the index for read-only servers to load. Once
+  /// every change has been made durable, the index file is copied, and the
+  /// copy is opened and closed so that read-only servers can open it without
+  /// repairing it. The copy then atomically replaces the previous snapshot, so
+  /// that read-only servers never see a partially written index. redb cannot
+  /// share a file which another process is writing, so this is the only copy
+  /// of the index which is made.
+  pub(crate) fn publish_snapshot(&self) -> Result {
+    let mut wtx = self.begin_write()?;
+    wtx.set_durability(redb::Durability::Immediate);
+    wtx.commit()?;
+
+    let snapshot = follower::snapshot_path(&self.path);
+
+    let mut copy = tempfile::NamedTempFile::new_in(snapshot.parent().unwrap())?;
+
+    io::copy(&mut File::open(&self.path)?, copy.as_file_mut())?;
+
+    Database::builder()
+      .set_cache_size(self.settings.index_cache_size())
+      .open(copy.path())?;
+
+    copy.as_file().sync_all()?;
+
+    copy.persist(&snapshot)?;
+
+    follower::record_committed(&self.path, self.block_count()?)?;
+
+    Ok(())
+  }
+
   pub fn update(&self) -> Result {
+    if let Some(follower) = &self.follower {
+      if let Some(database) = follower.refresh(&self.path, self.settings.index_cache_size())? {
+        *self.database.write().unwrap() = Arc::new(database);
+        log::info!("Reloaded read-only index");
+      }
+
+      return Ok(());
+    }
+
+    if let Some(feature) = self.backfill_in_progress()? {
+      bail!(
+        "index update is disabled while backfilling `{feature}`, run `ord index enable {feature}` to finish it"
+      );
+    }
+
     loop {
       let wtx = self.begin_write()?;
 
@@ -687,7 +828,7 @@ impl Index {
 
   pub fn export(&self, filename: &String, include_addresses: bool) -> Result {
     let mut writer = BufWriter::new(File::create(filename)?);
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let blocks_indexed = rtx
       .open_table(HEIGHT_TO_BLOCK_HEADER)?
@@ -769,11 +910,12 @@ impl Index {
   }
 
   fn begin_read(&self) -> Result<rtx::Rtx> {
-    Ok(rtx::Rtx(self.database.begin_read()?))
+    Ok(rtx::Rtx(self.database().begin_read()?))
   }
 
   fn begin_write(&self) -> Result<WriteTransaction> {
-    let mut tx = self.database.begin_write()?;
+    ensure!(self.follower.is_none(), "index is read-only");
+    let mut tx = self.database().begin_write()?;
     tx.set_durability(self.durability);
     tx.set_quick_repair(true);
     Ok(tx)
@@ -815,7 +957,7 @@ impl Index {
   #[cfg(test)]
   pub(crate) fn statistic(&self, statistic: Statistic) -> u64 {
     self
-      .database
+      .database()
       .begin_read()
       .unwrap()
       .open_table(STATISTIC_TO_COUNT)
@@ -869,7 +1011,7 @@ impl Index {
   }
 
   pub fn rare_sat_satpoints(&self) -> Result<Vec<(Sat, SatPoint)>> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let sat_to_satpoint = rtx.open_table(SAT_TO_SATPOINT)?;
 
@@ -886,7 +1028,7 @@ impl Index {
   pub fn rare_sat_satpoint(&self, sat: Sat) -> Result<Option<SatPoint>> {
     Ok(
       self
-        .database
+        .database()
         .begin_read()?
         .open_table(SAT_TO_SATPOINT)?
         .get(&sat.n())?
@@ -897,7 +1039,7 @@ impl Index {
   pub fn get_rune_by_id(&self, id: RuneId) -> Result<Option<Rune>> {
     Ok(
       self
-        .database
+        .database()
         .begin_read()?
         .open_table(RUNE_ID_TO_RUNE_ENTRY)?
         .get(&id.store())?
@@ -907,7 +1049,7 @@ impl Index {
 
   pub fn get_rune_by_number(&self, number: usize) -> Result<Option<Rune>> {
     match self
-      .database
+      .database()
       .begin_read()?
       .open_table(RUNE_ID_TO_RUNE_ENTRY)?
       .iter()?
@@ -923,7 +1065,7 @@ impl Index {
   }
 
   pub fn rune(&self, rune: Rune) -> Result<Option<(RuneId, RuneEntry, Option<InscriptionId>)>> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let Some(id) = rtx
       .open_table(RUNE_TO_RUNE_ID)?
@@ -959,7 +1101,7 @@ impl Index {
     let mut entries = Vec::new();
 
     for result in self
-      .database
+      .database()
       .begin_read()?
       .open_table(RUNE_ID_TO_RUNE_ENTRY)?
       .iter()?
@@ -979,7 +1121,7 @@ impl Index {
     let mut entries = Vec::new();
 
     for result in self
-      .database
+      .database()
       .begin_read()?
       .open_table(RUNE_ID_TO_RUNE_ENTRY)?
       .iter()?
@@ -1025,7 +1167,7 @@ impl Index {
       return Ok(None);
     }
 
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
 
@@ -1061,7 +1203,7 @@ impl Index {
   pub fn get_rune_balance_map(&self) -> Result<BTreeMap<SpacedRune, BTreeMap<OutPoint, Pile>>> {
     let outpoint_balances = self.get_rune_balances()?;
 
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
 
@@ -1117,7 +1259,7 @@ impl Index {
     let mut result = Vec::new();
 
     for entry in self
-      .database
+      .database()
       .begin_read()?
       .open_table(OUTPOINT_TO_RUNE_BALANCES)?
       .iter()?
@@ -1172,7 +1314,7 @@ impl Index {
     page_size: usize,
     page_index: usize,
   ) -> Result<(Vec<InscriptionId>, bool)> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let sequence_number_to_inscription_entry =
       rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
@@ -1207,7 +1349,7 @@ impl Index {
     &self,
     inscription_id: InscriptionId,
   ) -> Result<Vec<InscriptionId>> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let Some(sequence_number) = rtx
       .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
@@ -1227,7 +1369,7 @@ impl Index {
     &self,
     inscription_id: InscriptionId,
   ) -> Vec<InscriptionId> {
-    let rtx = self.database.begin_read().unwrap();
+    let rtx = self.database().begin_read().unwrap();
 
     let sequence_number = rtx
       .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)
@@ -1271,7 +1413,7 @@ impl Index {
     page_size: usize,
     page_index: usize,
   ) -> Result<(Vec<InscriptionId>, bool)> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
@@ -1306,7 +1448,7 @@ impl Index {
     page_index: usize,
   ) -> Result<(Vec<InscriptionId>, bool)> {
     const PAGE_SIZE: usize = 100;
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
@@ -1332,7 +1474,7 @@ impl Index {
   }
 
   pub fn get_etching(&self, txid: Txid) -> Result<Option<SpacedRune>> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let transaction_id_to_rune = rtx.open_table(TRANSACTION_ID_TO_RUNE)?;
     let Some(rune) = transaction_id_to_rune.get(&txid.store())? else {
@@ -1349,7 +1491,7 @@ impl Index {
   }
 
   pub fn get_inscription_ids_by_sat(&self, sat: Sat) -> Result<Vec<InscriptionId>> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let sequence_number_to_inscription_entry =
       rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
@@ -1378,7 +1520,7 @@ impl Index {
     page_size: u64,
     page_index: u64,
   ) -> Result<(Vec<InscriptionId>, bool)> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let sequence_number_to_inscription_entry =
       rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
@@ -1414,7 +1556,7 @@ impl Index {
     sat: Sat,
     inscription_index: isize,
   ) -> Result<Option<InscriptionId>> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let sequence_number_to_inscription_entry =
       rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
@@ -1448,7 +1590,7 @@ impl Index {
     &self,
     inscription_number: i32,
   ) -> Result<Option<InscriptionId>> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let Some(sequence_number) = rtx
       .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?
@@ -1470,7 +1612,7 @@ impl Index {
     &self,
     inscription_id: InscriptionId,
   ) -> Result<Option<SatPoint>> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let Some(sequence_number) = rtx
       .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
@@ -1488,6 +1630,28 @@ impl Index {
     Ok(satpoint)
   }
 
+  pub fn get_inscription_properties(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Option<Properties>> {
+    let rtx = self.database().begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&inscription_id.store())?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    let properties = rtx
+      .open_table(SEQUENCE_NUMBER_TO_PROPERTIES)?
+      .get(sequence_number)?
+      .and_then(|properties| Properties::from_cbor(properties.value()));
+
+    Ok(properties)
+  }
+
   pub fn get_inscription_by_id(
     &self,
     inscription_id: InscriptionId,
@@ -1514,7 +1678,7 @@ impl Index {
 
     Ok(
       self
-        .database
+        .database()
         .begin_read()?
         .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
         .range::<&InscriptionIdValue>(&start.store()..&end.store())?
@@ -1527,7 +1691,7 @@ impl Index {
   pub fn inscription_exists(&self, inscription_id: InscriptionId) -> Result<bool> {
     Ok(
       self
-        .database
+        .database()
         .begin_read()?
         .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
         .get(&inscription_id.store())?
@@ -1543,7 +1707,7 @@ impl Index {
       return Ok(None);
     }
 
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
     let outpoint_to_utxo_entry = rtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
     let sequence_number_to_inscription_entry =
       rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
@@ -1598,7 +1762,7 @@ impl Index {
 
     if self.index_transactions {
       if let Some(transaction) = self
-        .database
+        .database()
         .begin_read()?
         .open_table(TRANSACTION_ID_TO_TRANSACTION)?
         .get(&txid.store())?
@@ -1701,7 +1865,7 @@ impl Index {
 
     Ok(
       self
-        .database
+        .database()
         .begin_read()?
         .open_table(OUTPOINT_TO_UTXO_ENTRY)?
         .get(&outpoint.store())?
@@ -1723,7 +1887,7 @@ impl Index {
         && outpoint != self.settings.chain().genesis_coinbase_outpoint()
         && if self.have_full_utxo_index() {
           self
-            .database
+            .database()
             .begin_read()?
             .open_table(OUTPOINT_TO_UTXO_ENTRY)?
             .get(&outpoint.store())?
@@ -1768,7 +1932,7 @@ impl Index {
   pub fn block_time(&self, height: Height) -> Result<Blocktime> {
     let height = height.n();
 
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let height_to_block_header = rtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
 
@@ -1776,6 +1940,13 @@ impl Index {
       return Ok(Blocktime::confirmed(Header::load(*guard.value()).time));
     }
 
+    if self.is_pruned(height)? {
+      let hash = self.client.get_block_hash(height.into())?;
+      return Ok(Blocktime::confirmed(
+        self.client.get_block_header(&hash)?.time,
+      ));
+    }
+
     let current = height_to_block_header
       .range(0..)?
       .next_back()
@@ -1804,7 +1975,7 @@ impl Index {
     page_size: u32,
     page_index: u32,
   ) -> Result<(Vec<InscriptionId>, bool)> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let sequence_number_to_inscription_entry =
       rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
@@ -1836,7 +2007,7 @@ impl Index {
   }
 
   pub fn get_inscriptions_in_block(&self, block_height: u32) -> Result<Vec<InscriptionId>> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
     let sequence_number_to_inscription_entry =
@@ -1866,7 +2037,7 @@ impl Index {
   }
 
   pub fn get_runes_in_block(&self, block_height: u64) -> Result<Vec<SpacedRune>> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
 
@@ -1922,7 +2093,7 @@ impl Index {
   pub fn get_home_inscriptions(&self) -> Result<Vec<InscriptionId>> {
     Ok(
       self
-        .database
+        .database()
         .begin_read()?
         .open_table(HOME_INSCRIPTIONS)?
         .iter()?
@@ -1935,7 +2106,7 @@ impl Index {
   pub fn get_feed_inscriptions(&self, n: usize) -> Result<Vec<(u32, InscriptionId)>> {
     Ok(
       self
-        .database
+        .database()
         .begin_read()?
         .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
         .iter()?
@@ -1953,7 +2124,7 @@ impl Index {
     query: query::Inscription,
     child: Option<usize>,
   ) -> Result<Option<(api::Inscription, Option<TxOut>, Inscription)>> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let sequence_number = match query {
       query::Inscription::Id(id) => rtx
@@ -2142,6 +2313,7 @@ impl Index {
         next,
         number: entry.inscription_number,
         parents,
+        pending_spend: None,
         previous,
         rune,
         sat: entry.sat,
@@ -2159,7 +2331,7 @@ impl Index {
     &self,
     inscription_id: InscriptionId,
   ) -> Result<Option<InscriptionEntry>> {
-    let rtx = self.database.begin_read()?;
+    let rtx = self.database().begin_read()?;
 
     let Some(sequence_number) = rtx
       .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
@@ -2184,7 +2356,7 @@ impl Index {
     satpoint: SatPoint,
     sat: Option<u64>,
   ) {
-    let rtx = self.database.begin_read().unwrap();
+    let rtx = self.database().begin_read().unwrap();
 
     let outpoint_to_utxo_entry = rtx.open_table(OUTPOINT_TO_UTXO_ENTRY).unwrap();
 
@@ -2294,7 +2466,7 @@ impl Index {
 
   pub fn get_address_info(&self, address: &Address) -> Result<Vec<OutPoint>> {
     self
-      .database
+      .database()
       .begin_read()?
       .open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?
       .get(address.script_pubkey().as_bytes())?
@@ -2344,7 +2516,7 @@ impl Index {
 
   pub(crate) fn get_sat_balances_for_outputs(&self, outputs: &Vec<OutPoint>) -> Result<u64> {
     let outpoint_to_utxo_entry = self
-      .database
+      .database()
       .begin_read()?
       .open_table(OUTPOINT_TO_UTXO_ENTRY)?;
 
@@ -2363,7 +2535,7 @@ impl Index {
     outpoint: OutPoint,
   ) -> Result<Option<api::UtxoRecursive>> {
     let Some(utxo_entry) = self
-      .database
+      .database()
       .begin_read()?
       .open_table(OUTPOINT_TO_UTXO_ENTRY)?
       .get(&outpoint.store())?
@@ -2705,6 +2877,78 @@ mod tests {
     );
   }
 
+  #[test]
+  fn list_transaction_spending_output_from_same_block() {
+    let context = Context::builder().arg("--index-sats").build();
+
+    context.mine_blocks(2);
+
+    let independent = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, Default::default())],
+      fee: 10,
+      ..default()
+    });
+
+    let parent = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      fee: 10,
+      ..default()
+    });
+
+    let child = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: OutPoint::new(parent, 0),
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        value: Amount::from_sat(50 * COIN_VALUE - 30),
+        script_pubkey: ScriptBuf::new(),
+      }],
+    };
+
+    context.core.state().mempool.push(child.clone());
+
+    let coinbase_txid = context.mine_blocks(1)[0].txdata[0].compute_txid();
+
+    assert_eq!(
+      context
+        .index
+        .list(OutPoint::new(independent, 0))
+        .unwrap()
+        .unwrap(),
+      &[(10000000000, 14999999990)],
+    );
+
+    assert_eq!(context.index.list(OutPoint::new(parent, 0)).unwrap(), None);
+
+    assert_eq!(
+      context
+        .index
+        .list(OutPoint::new(child.compute_txid(), 0))
+        .unwrap()
+        .unwrap(),
+      &[(5000000000, 9999999970)],
+    );
+
+    assert_eq!(
+      context
+        .index
+        .list(OutPoint::new(coinbase_txid, 0))
+        .unwrap()
+        .unwrap(),
+      &[
+        (15000000000, 20000000000),
+        (14999999990, 15000000000),
+        (9999999990, 10000000000),
+        (9999999970, 9999999990),
+      ],
+    );
+  }
+
   #[test]
   fn list_null_output() {
     let context = Context::builder().arg("--index-sats").build();
@@ -3454,7 +3698,7 @@ mod tests {
     let tempdir = {
       let context = Context::builder().build();
 
-      let wtx = context.index.database.begin_write().unwrap();
+      let wtx = context.index.database().begin_write().unwrap();
 
       wtx
         .open_table(STATISTIC_TO_COUNT)
@@ -3481,7 +3725,7 @@ mod tests {
     let tempdir = {
       let context = Context::builder().build();
 
-      let wtx = context.index.database.begin_write().unwrap();
+      let wtx = context.index.database().begin_write().unwrap();
 
       wtx
         .open_table(STATISTIC_TO_COUNT)
@@ -4648,6 +4892,53 @@ mod tests {
     }
   }
 
+  #[test]
+  fn compacted_index_recovers_from_reorg() {
+    let mut context = Context::builder().build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(2);
+
+    assert_eq!(context.index.compact().unwrap(), Some(3));
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    assert!(context.index.inscription_exists(id).unwrap());
+
+    context.core.invalidate_tip();
+    context.mine_blocks(2);
+
+    assert!(!context.index.inscription_exists(id).unwrap());
+    assert_eq!(context.index.block_count().unwrap(), 5);
+  }
+
+  #[test]
+  fn reorg_without_savepoints_is_an_error() {
+    let mut context = Context::builder().build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(2);
+
+    Reorg::delete_savepoints(&context.index).unwrap();
+
+    context.core.invalidate_tip();
+    context.core.mine_blocks(2);
+
+    assert_eq!(
+      context.index.update().unwrap_err().to_string(),
+      "no savepoint to roll back to, the index must be rebuilt",
+    );
+  }
+
   #[test]
   fn recover_from_3_block_deep_and_consecutive_reorg() {
     for mut context in Context::configurations() {
@@ -6729,6 +7020,109 @@ mod tests {
     );
   }
 
+  #[test]
+  fn read_only_index_follows_published_snapshots() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+    context.index.publish_snapshot().unwrap();
+
+    let follower = Index::open_read_only(&context.index.settings).unwrap();
+
+    assert_eq!(follower.block_count().unwrap(), 2);
+
+    assert_eq!(follower.status(true).unwrap().replication_lag, Some(0));
+
+    context.mine_blocks(2);
+
+    follower.update().unwrap();
+
+    assert_eq!(follower.block_count().unwrap(), 2);
+
+    assert_eq!(follower.status(true).unwrap().replication_lag, Some(2));
+
+    context.index.publish_snapshot().unwrap();
+
+    follower.update().unwrap();
+
+    assert_eq!(follower.block_count().unwrap(), 4);
+
+    assert_eq!(follower.status(true).unwrap().replication_lag, Some(0));
+
+    assert_eq!(
+      follower.begin_write().err().unwrap().to_string(),
+      "index is read-only"
+    );
+  }
+
+  #[test]
+  fn read_only_indexes_open_snapshots_in_place() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+    context.index.publish_snapshot().unwrap();
+
+    let snapshot = follower::snapshot_path(&context.index.path);
+
+    let published = fs::read(&snapshot).unwrap();
+
+    let files = || {
+      fs::read_dir(context.index.settings.index().parent().unwrap())
+        .unwrap()
+        .count()
+    };
+
+    let before = files();
+
+    let a = Index::open_read_only(&context.index.settings).unwrap();
+    let b = Index::open_read_only(&context.index.settings).unwrap();
+
+    assert_eq!(a.block_count().unwrap(), 2);
+    assert_eq!(b.block_count().unwrap(), 2);
+
+    assert_eq!(files(), before);
+
+    drop(a);
+    drop(b);
+
+    assert_eq!(fs::read(&snapshot).unwrap(), published);
+  }
+
+  #[test]
+  fn read_only_index_serves_loaded_snapshot_after_it_is_replaced() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+    context.index.publish_snapshot().unwrap();
+
+    let follower = Index::open_read_only(&context.index.settings).unwrap();
+
+    context.mine_blocks(1);
+    context.index.publish_snapshot().unwrap();
+
+    assert_eq!(follower.block_count().unwrap(), 2);
+
+    follower.update().unwrap();
+
+    assert_eq!(follower.block_count().unwrap(), 3);
+  }
+
+  #[test]
+  fn read_only_index_requires_snapshot() {
+    let context = Context::builder().build();
+
+    assert_eq!(
+      Index::open_read_only(&context.index.settings)
+        .err()
+        .unwrap()
+        .to_string(),
+      format!(
+        "index snapshot at `{}.snapshot` does not exist, run `ord index update --snapshot` to publish one before opening the index read-only",
+        context.index.settings.index().display(),
+      ),
+    );
+  }
+
   #[test]
   fn assert_schema_statistic_key_is_zero() {
     // other schema statistic keys may chenge when the schema changes, but for
@@ -0,0 +1,613 @@
+use {super::*, updater::BlockData};
+
+/// Blocks, or inscriptions when backfilling transactions, processed per commit
+const BATCH_SIZE: u32 = if cfg!(test) { 1 } else { 1000 };
+
+/// An optional index which can be enabled on an existing database
+#[derive(clap::ValueEnum, Debug, PartialEq, Copy, Clone)]
+pub(crate) enum Feature {
+  Addresses = 1,
+  Runes = 2,
+  Transactions = 3,
+}
+
+impl Feature {
+  fn from_code(code: u64) -> Option<Self> {
+    match code {
+      1 => Some(Self::Addresses),
+      2 => Some(Self::Runes),
+      3 => Some(Self::Transactions),
+      _ => None,
+    }
+  }
+
+  fn statistic(self) -> Statistic {
+    match self {
+      Self::Addresses => Statistic::IndexAddresses,
+      Self::Runes => Statistic::IndexRunes,
+      Self::Transactions => Statistic::IndexTransactions,
+    }
+  }
+}
+
+impl Display for Feature {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Addresses => "addresses",
+        Self::Runes => "runes",
+        Self::Transactions => "transactions",
+      }
+    )
+  }
+}
+
+impl Index {
+  /// Populate the tables of `feature` on an index created without it, and
+  /// enable it once they are complete. Progress is committed in batches, so an
+  /// interrupted backfill resumes where it left off, and the index is not
+  /// updated with new blocks until it finishes.
+  pub(crate) fn backfill(&self, feature: Feature) -> Result {
+    let enabled = match feature {
+      Feature::Addresses => self.index_addresses,
+      Feature::Runes => self.index_runes,
+      Feature::Transactions => self.index_transactions,
+    };
+
+    ensure!(!enabled, "`{feature}` index is already enabled");
+
+    ensure!(
+      self.pruned_height()? == 0,
+      "cannot backfill `{feature}` on a pruned index, reindex with `--index-{feature}` instead"
+    );
+
+    match feature {
+      Feature::Addresses => ensure!(
+        self.index_sats,
+        "backfilling `addresses` requires an index created with `--index-sats`, otherwise reindex with `--index-addresses`"
+      ),
+      Feature::Transactions => ensure!(
+        self.index_inscriptions,
+        "backfilling `transactions` requires the inscription index"
+      ),
+      Feature::Runes => {}
+    }
+
+    let mut progress_bar = None;
+
+    loop {
+      let (progress, end) = self.backfill_batch(feature)?;
+
+      if !(cfg!(test) || log_enabled!(log::Level::Info) || self.settings.integration_test()) {
+        progress_bar
+          .get_or_insert_with(|| {
+            let progress_bar = ProgressBar::new(end.into());
+            progress_bar.set_style(
+              ProgressStyle::with_template(&format!(
+                "[backfilling {feature}] {{wide_bar}} {{pos}}/{{len}}"
+              ))
+              .unwrap(),
+            );
+            progress_bar
+          })
+          .set_position(progress.into());
+      }
+
+      if progress == end {
+        if let Some(progress_bar) = progress_bar {
+          progress_bar.finish_and_clear();
+        }
+
+        return Ok(());
+      }
+
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        bail!("backfill of `{feature}` interrupted, run `ord index enable {feature}` to resume it");
+      }
+    }
+  }
+
+  /// The feature whose backfill was started but not finished, if any
+  pub(crate) fn backfill_in_progress(&self) -> Result<Option<Feature>> {
+    Ok(
+      self
+        .database()
+        .begin_read()?
+        .open_table(STATISTIC_TO_COUNT)?
+        .get(&Statistic::BackfillFeature.key())?
+        .and_then(|code| Feature::from_code(code.value())),
+    )
+  }
+
+  /// Backfill the next batch of `feature` in a single write transaction,
+  /// returning the progress made and the point at which it will be complete
+  fn backfill_batch(&self, feature: Feature) -> Result<(u32, u32)> {
+    let wtx = self.begin_write()?;
+
+    let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
+
+    let code = statistic_to_count
+      .get(&Statistic::BackfillFeature.key())?
+      .map(|code| code.value())
+      .unwrap_or_default();
+
+    let start = if code == 0 {
+      Self::set_statistic(
+        &mut statistic_to_count,
+        Statistic::BackfillFeature,
+        feature as u64,
+      )?;
+
+      match feature {
+        Feature::Addresses => {
+          let mut outpoint_to_utxo_entry = wtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+          let mut script_pubkey_to_outpoint = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+
+          for outpoint in [OutPoint::null(), unbound_outpoint()] {
+            let Some(entry) = outpoint_to_utxo_entry
+              .get(&outpoint.store())?
+              .map(|entry| entry.value().with_script_pubkey(&[], self))
+            else {
+              continue;
+            };
+
+            outpoint_to_utxo_entry.insert(&outpoint.store(), entry.as_ref())?;
+            script_pubkey_to_outpoint.insert([].as_slice(), &outpoint.store())?;
+          }
+
+          0
+        }
+        Feature::Runes => {
+          if self.settings.chain() == Chain::Mainnet {
+            drop(statistic_to_count);
+            Self::insert_genesis_rune(&wtx)?;
+            statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
+          }
+
+          self.settings.first_rune_height()
+        }
+        Feature::Transactions => 0,
+      }
+    } else {
+      let in_progress = Feature::from_code(code)
+        .with_context(|| format!("unknown backfill feature code {code}"))?;
+
+      ensure!(
+        in_progress == feature,
+        "backfill of `{in_progress}` in progress, run `ord index enable {in_progress}` to finish it first"
+      );
+
+      statistic_to_count
+        .get(&Statistic::BackfillProgress.key())?
+        .map(|progress| progress.value())
+        .unwrap_or_default()
+        .try_into()?
+    };
+
+    let height_to_block_header = wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+
+    let end = match feature {
+      Feature::Addresses | Feature::Runes => height_to_block_header.len()?,
+      Feature::Transactions => wtx
+        .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+        .len()?,
+    }
+    .try_into()?;
+
+    let start = start.min(end);
+
+    let next = start.saturating_add(BATCH_SIZE).min(end);
+
+    let get_block = |height: u32| -> Result<Block> {
+      let header = Header::load(
+        *height_to_block_header
+          .get(height)?
+          .with_context(|| format!("missing header for block {height}"))?
+          .value(),
+      );
+
+      Ok(self.client.get_block(&header.block_hash())?)
+    };
+
+    match feature {
+      Feature::Addresses => {
+        let mut outpoint_to_utxo_entry = wtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+        let mut script_pubkey_to_outpoint = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+
+        for height in start..next {
+          for tx in get_block(height)?.txdata {
+            let txid = tx.compute_txid();
+
+            for (vout, txout) in tx.output.iter().enumerate() {
+              let outpoint = OutPoint {
+                txid,
+                vout: vout.try_into()?,
+              };
+
+              let Some(entry) = outpoint_to_utxo_entry.get(&outpoint.store())?.map(|entry| {
+                entry
+                  .value()
+                  .with_script_pubkey(txout.script_pubkey.as_bytes(), self)
+              }) else {
+                continue;
+              };
+
+              // outputs of duplicate coinbase transactions are only rewritten once
+              if script_pubkey_to_outpoint
+                .insert(txout.script_pubkey.as_bytes(), &outpoint.store())?
+              {
+                continue;
+              }
+
+              outpoint_to_utxo_entry.insert(&outpoint.store(), entry.as_ref())?;
+            }
+          }
+        }
+      }
+      Feature::Runes => {
+        let mut inscription_id_to_sequence_number =
+          wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+
+        for height in start..next {
+          // checksums of blocks which have already been indexed are not
+          // updated, so they only cover what was indexed at the time
+          Updater::index_runes(
+            self,
+            height,
+            &BlockData::from(get_block(height)?),
+            &wtx,
+            &mut Checksum::new(None),
+            &mut inscription_id_to_sequence_number,
+            &mut statistic_to_count,
+          )?;
+        }
+      }
+      Feature::Transactions => {
+        let sequence_number_to_inscription_entry =
+          wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+        let mut transaction_id_to_transaction = wtx.open_table(TRANSACTION_ID_TO_TRANSACTION)?;
+
+        let mut buffer = Vec::new();
+
+        for sequence_number in start..next {
+          let txid = InscriptionEntry::load(
+            sequence_number_to_inscription_entry
+              .get(sequence_number)?
+              .with_context(|| format!("missing inscription entry {sequence_number}"))?
+              .value(),
+          )
+          .id
+          .txid;
+
+          if transaction_id_to_transaction.get(&txid.store())?.is_some() {
+            continue;
+          }
+
+          self
+            .client
+            .get_raw_transaction(&txid, None)?
+            .consensus_encode(&mut buffer)?;
+
+          transaction_id_to_transaction.insert(&txid.store(), buffer.as_slice())?;
+
+          buffer.clear();
+        }
+      }
+    }
+
+    if next == end {
+      Self::set_statistic(&mut statistic_to_count, feature.statistic(), 1)?;
+      statistic_to_count.remove(&Statistic::BackfillFeature.key())?;
+      statistic_to_count.remove(&Statistic::BackfillProgress.key())?;
+    } else {
+      Self::set_statistic(
+        &mut statistic_to_count,
+        Statistic::BackfillProgress,
+        next.into(),
+      )?;
+    }
+
+    drop(statistic_to_count);
+    drop(height_to_block_header);
+
+    wtx.commit()?;
+
+    Ok((next, end))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  const RUNE: u128 = 99246114928149462;
+
+  fn reopen(context: Context) -> Context {
+    let Context {
+      index,
+      core,
+      tempdir,
+    } = context;
+
+    let settings = index.settings.clone();
+
+    drop(index);
+
+    Context {
+      index: Index::open(&settings).unwrap(),
+      core,
+      tempdir,
+    }
+  }
+
+  fn table<K: redb::Key + 'static, V: redb::Value + 'static>(
+    index: &Index,
+    definition: TableDefinition<K, V>,
+  ) -> Vec<(Vec<u8>, Vec<u8>)> {
+    index
+      .database()
+      .begin_read()
+      .unwrap()
+      .open_table(definition)
+      .unwrap()
+      .iter()
+      .unwrap()
+      .map(|result| {
+        let (key, value) = result.unwrap();
+        let key = K::as_bytes(&key.value()).as_ref().to_vec();
+        let value = V::as_bytes(&value.value()).as_ref().to_vec();
+        (key, value)
+      })
+      .collect()
+  }
+
+  #[test]
+  fn runes_backfill() {
+    let context = Context::builder().build();
+
+    let (txid, id) = context.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(u128::MAX),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    assert_eq!(context.index.runes().unwrap(), []);
+
+    context.index.backfill(Feature::Runes).unwrap();
+
+    let context = reopen(context);
+
+    assert!(context.index.index_runes);
+    assert_eq!(context.index.statistic(Statistic::BackfillFeature), 0);
+    assert_eq!(context.index.statistic(Statistic::BackfillProgress), 0);
+    assert_eq!(context.index.statistic(Statistic::Runes), 1);
+
+    let entry = RuneEntry {
+      block: id.block,
+      etching: txid,
+      premine: u128::MAX,
+      spaced_rune: SpacedRune {
+        rune: Rune(RUNE),
+        spacers: 0,
+      },
+      timestamp: id.block,
+      ..default()
+    };
+
+    context.assert_runes(
+      [(id, entry)],
+      [(OutPoint { txid, vout: 0 }, vec![(id, u128::MAX)])],
+    );
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(id.block.try_into().unwrap(), 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id,
+            amount: u128::MAX,
+            output: 1,
+          }],
+          ..default()
+        }
+        .encipher(),
+      ),
+      outputs: 2,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    context.assert_runes(
+      [(id, entry)],
+      [(OutPoint { txid, vout: 1 }, vec![(id, u128::MAX)])],
+    );
+  }
+
+  #[test]
+  fn addresses_backfill_matches_indexing() {
+    let expected = Context::builder()
+      .args(["--index-sats", "--index-addresses"])
+      .build();
+    let context = Context::builder().arg("--index-sats").build();
+
+    for context in [&expected, &context] {
+      context.mine_blocks(2);
+      context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, Default::default())],
+        outputs: 2,
+        fee: 1000,
+        ..default()
+      });
+      context.mine_blocks(1);
+    }
+
+    context.index.backfill(Feature::Addresses).unwrap();
+
+    let context = reopen(context);
+
+    assert!(context.index.index_addresses);
+    assert_eq!(
+      table(&context.index, OUTPOINT_TO_UTXO_ENTRY),
+      table(&expected.index, OUTPOINT_TO_UTXO_ENTRY),
+    );
+
+    let script_pubkey_to_outpoint = |index: &Index| {
+      index
+        .database()
+        .begin_read()
+        .unwrap()
+        .open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)
+        .unwrap()
+        .iter()
+        .unwrap()
+        .flat_map(|result| {
+          let (script_pubkey, outpoints) = result.unwrap();
+          let script_pubkey = script_pubkey.value().to_vec();
+          outpoints
+            .map(|outpoint| (script_pubkey.clone(), outpoint.unwrap().value()))
+            .collect::<Vec<(Vec<u8>, OutPointValue)>>()
+        })
+        .collect::<Vec<(Vec<u8>, OutPointValue)>>()
+    };
+
+    assert_eq!(
+      script_pubkey_to_outpoint(&context.index),
+      script_pubkey_to_outpoint(&expected.index),
+    );
+
+    expected.mine_blocks(1);
+    context.mine_blocks(1);
+
+    assert_eq!(
+      table(&context.index, OUTPOINT_TO_UTXO_ENTRY),
+      table(&expected.index, OUTPOINT_TO_UTXO_ENTRY),
+    );
+  }
+
+  #[test]
+  fn transactions_backfill_matches_indexing() {
+    let expected = Context::builder().arg("--index-transactions").build();
+    let context = Context::builder().build();
+
+    for context in [&expected, &context] {
+      context.mine_blocks(1);
+      context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      });
+      context.mine_blocks(1);
+    }
+
+    assert_eq!(table(&context.index, TRANSACTION_ID_TO_TRANSACTION), []);
+
+    context.index.backfill(Feature::Transactions).unwrap();
+
+    let context = reopen(context);
+
+    assert!(context.index.index_transactions);
+    assert_eq!(
+      table(&context.index, TRANSACTION_ID_TO_TRANSACTION).len(),
+      1
+    );
+    assert_eq!(
+      table(&context.index, TRANSACTION_ID_TO_TRANSACTION),
+      table(&expected.index, TRANSACTION_ID_TO_TRANSACTION),
+    );
+  }
+
+  #[test]
+  fn interrupted_backfill_resumes_and_blocks_updates() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(3);
+
+    assert_eq!(
+      context.index.backfill_batch(Feature::Runes).unwrap(),
+      (1, 4)
+    );
+    assert_eq!(
+      context.index.backfill_in_progress().unwrap(),
+      Some(Feature::Runes)
+    );
+
+    context.core.mine_blocks(1);
+
+    assert_eq!(
+      context.index.update().unwrap_err().to_string(),
+      "index update is disabled while backfilling `runes`, run `ord index enable runes` to finish it",
+    );
+
+    assert_eq!(
+      context
+        .index
+        .backfill_batch(Feature::Transactions)
+        .unwrap_err()
+        .to_string(),
+      "backfill of `runes` in progress, run `ord index enable runes` to finish it first",
+    );
+
+    assert_eq!(
+      context.index.backfill_batch(Feature::Runes).unwrap(),
+      (2, 4)
+    );
+
+    context.index.backfill(Feature::Runes).unwrap();
+
+    assert_eq!(context.index.backfill_in_progress().unwrap(), None);
+    assert_eq!(context.index.statistic(Statistic::IndexRunes), 1);
+
+    let context = reopen(context);
+
+    context.index.update().unwrap();
+
+    assert_eq!(context.index.block_count().unwrap(), 5);
+  }
+
+  #[test]
+  fn unsupported_backfills_are_rejected() {
+    let context = Context::builder().arg("--index-runes").build();
+
+    assert_eq!(
+      context
+        .index
+        .backfill(Feature::Runes)
+        .unwrap_err()
+        .to_string(),
+      "`runes` index is already enabled",
+    );
+
+    assert_eq!(
+      context
+        .index
+        .backfill(Feature::Addresses)
+        .unwrap_err()
+        .to_string(),
+      "backfilling `addresses` requires an index created with `--index-sats`, otherwise reindex with `--index-addresses`",
+    );
+
+    let context = Context::builder().arg("--no-index-inscriptions").build();
+
+    assert_eq!(
+      context
+        .index
+        .backfill(Feature::Transactions)
+        .unwrap_err()
+        .to_string(),
+      "backfilling `transactions` requires the inscription index",
+    );
+  }
+}
@@ -0,0 +1,240 @@
+use {super::*, bitcoin::hashes::HashEngine};
+
+#[repr(u8)]
+enum Record {
+  InscriptionCreated,
+  InscriptionTransferred,
+  RuneBalance,
+  RuneEntry,
+}
+
+/// Rolling commitment to the changes a block makes to the index. Each block's
+/// checksum is the SHA-256 hash of the previous block's checksum followed by
+/// the entries written for inscription creations and transfers, rune entries,
+/// and rune balances, in the order they are written, so two indexes with the
+/// same checksum at a height agree on every block up to and including it.
+pub(crate) struct Checksum(sha256::HashEngine);
+
+impl Checksum {
+  pub(super) fn new(previous: Option<sha256::Hash>) -> Self {
+    let mut engine = sha256::Hash::engine();
+    engine.input(
+      &previous
+        .unwrap_or_else(sha256::Hash::all_zeros)
+        .to_byte_array(),
+    );
+    Self(engine)
+  }
+
+  pub(super) fn finish(self) -> sha256::Hash {
+    sha256::Hash::from_engine(self.0)
+  }
+
+  pub(super) fn inscription_created(
+    &mut self,
+    entry: &InscriptionEntryValue,
+    location: Option<SatPoint>,
+  ) {
+    self.record(Record::InscriptionCreated);
+    self.value::<InscriptionEntryValue>(entry);
+    self.value::<Option<&SatPointValue>>(&location.map(|location| location.store()).as_ref());
+  }
+
+  pub(super) fn inscription_transferred(
+    &mut self,
+    sequence_number: u32,
+    old_location: SatPoint,
+    new_location: SatPoint,
+  ) {
+    self.record(Record::InscriptionTransferred);
+    self.value::<u32>(&sequence_number);
+    self.value::<&SatPointValue>(&&old_location.store());
+    self.value::<&SatPointValue>(&&new_location.store());
+  }
+
+  pub(super) fn rune_balance(&mut self, outpoint: OutPoint, balances: &[u8]) {
+    self.record(Record::RuneBalance);
+    self.value::<&OutPointValue>(&&outpoint.store());
+    self.value::<&[u8]>(&balances);
+  }
+
+  pub(super) fn rune_entry(&mut self, id: RuneId, entry: RuneEntryValue) {
+    self.record(Record::RuneEntry);
+    self.value::<RuneIdValue>(&id.store());
+    self.value::<RuneEntryValue>(&entry);
+  }
+
+  fn record(&mut self, record: Record) {
+    self.0.input(&[record as u8]);
+  }
+
+  /// Hash `value` as it is stored in the index, prefixed with its length
+  fn value<'a, V: redb::Value + 'a>(&mut self, value: &V::SelfType<'a>) {
+    let bytes = V::as_bytes(value);
+    let bytes = bytes.as_ref();
+    self
+      .0
+      .input(&u64::try_from(bytes.len()).unwrap().to_le_bytes());
+    self.0.input(bytes);
+  }
+}
+
+impl Index {
+  /// Checksum of the changes made to the index by the block at `height` and
+  /// every block before it, or `None` if the block hasn't been indexed or has
+  /// been pruned
+  pub fn block_checksum(&self, height: u32) -> Result<Option<sha256::Hash>> {
+    Ok(
+      self
+        .database()
+        .begin_read()?
+        .open_table(HEIGHT_TO_CHECKSUM)?
+        .get(height)?
+        .map(|checksum| sha256::Hash::from_byte_array(*checksum.value())),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  const RUNE: u128 = 99246114928149462;
+
+  fn checksums(context: &Context) -> Vec<sha256::Hash> {
+    (0..context.index.block_count().unwrap())
+      .map(|height| context.index.block_checksum(height).unwrap().unwrap())
+      .collect()
+  }
+
+  fn inscribe(context: &Context, content: &str) {
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", content).to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+  }
+
+  #[test]
+  fn blocks_without_changes_extend_previous_checksum() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(2);
+
+    let checksums = checksums(&context);
+
+    assert_eq!(checksums.len(), 3);
+    assert_eq!(checksums[0], sha256::Hash::hash(&[0; 32]));
+    assert_eq!(
+      checksums[1],
+      sha256::Hash::hash(checksums[0].as_byte_array())
+    );
+    assert_eq!(
+      checksums[2],
+      sha256::Hash::hash(checksums[1].as_byte_array())
+    );
+
+    assert_eq!(context.index.block_checksum(3).unwrap(), None);
+  }
+
+  #[test]
+  fn indexes_with_same_changes_have_same_checksums() {
+    let a = Context::builder().build();
+    let b = Context::builder().build();
+
+    for context in [&a, &b] {
+      context.mine_blocks(1);
+      inscribe(context, "foo");
+      context.mine_blocks(1);
+    }
+
+    assert_eq!(checksums(&a), checksums(&b));
+    assert_ne!(
+      checksums(&a)[2],
+      sha256::Hash::hash(checksums(&a)[1].as_byte_array())
+    );
+  }
+
+  #[test]
+  fn checksums_diverge_at_first_block_with_different_changes() {
+    let a = Context::builder().build();
+    let b = Context::builder().build();
+
+    a.mine_blocks(1);
+    b.mine_blocks(1);
+
+    inscribe(&a, "foo");
+    b.mine_blocks(1);
+
+    a.mine_blocks(1);
+    b.mine_blocks(1);
+
+    let a = checksums(&a);
+    let b = checksums(&b);
+
+    assert_eq!(a[..2], b[..2]);
+    assert_ne!(a[2], b[2]);
+    assert_ne!(a[3], b[3]);
+  }
+
+  #[test]
+  fn checksum_commits_to_inscription_transfers() {
+    let a = Context::builder().build();
+    let b = Context::builder().build();
+
+    for context in [&a, &b] {
+      context.mine_blocks(1);
+      inscribe(context, "foo");
+    }
+
+    a.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    a.mine_blocks(1);
+    b.mine_blocks(1);
+
+    let a = checksums(&a);
+    let b = checksums(&b);
+
+    assert_eq!(a[..3], b[..3]);
+    assert_ne!(a[3], b[3]);
+  }
+
+  #[test]
+  fn checksum_commits_to_runes() {
+    let a = Context::builder().arg("--index-runes").build();
+    let b = Context::builder().arg("--index-runes").build();
+
+    let mut etched = Vec::new();
+
+    for (context, premine) in [(&a, 1000), (&b, 2000)] {
+      let (_txid, id) = context.etch(
+        Runestone {
+          etching: Some(Etching {
+            premine: Some(premine),
+            rune: Some(Rune(RUNE)),
+            ..default()
+          }),
+          ..default()
+        },
+        1,
+      );
+
+      etched.push(id);
+    }
+
+    assert_eq!(etched[0], etched[1]);
+
+    let height = usize::try_from(etched[0].block).unwrap();
+
+    let a = checksums(&a);
+    let b = checksums(&b);
+
+    assert_eq!(a.len(), height + 1);
+    assert_eq!(a[..height], b[..height]);
+    assert_ne!(a[height], b[height]);
+  }
+}
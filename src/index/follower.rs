@@ -0,0 +1,341 @@
+use {
+  super::*,
+  redb::StorageBackend,
+  std::{collections::btree_map, ops::Range},
+};
+
+/// Path of the snapshot which `ord index update --snapshot` publishes for
+/// read-only servers
+pub(crate) fn snapshot_path(index: &Path) -> PathBuf {
+  sibling(index, ".snapshot")
+}
+
+/// Path of the file in which the indexer records how many blocks it has
+/// committed, so that read-only servers can report how far behind it they are
+fn committed_path(index: &Path) -> PathBuf {
+  sibling(index, ".committed")
+}
+
+fn sibling(index: &Path, extension: &str) -> PathBuf {
+  let mut path = index.as_os_str().to_owned();
+  path.push(extension);
+  path.into()
+}
+
+/// Record that the indexer has committed `block_count` blocks. Nothing is
+/// recorded unless a snapshot has been published, since otherwise no
+/// read-only server can be following the index.
+pub(super) fn record_committed(index: &Path, block_count: u32) -> Result {
+  if !snapshot_path(index).exists() {
+    return Ok(());
+  }
+
+  let committed = committed_path(index);
+
+  let mut file = tempfile::NamedTempFile::new_in(committed.parent().unwrap())?;
+
+  write!(file, "{block_count}")?;
+
+  file.persist(committed)?;
+
+  Ok(())
+}
+
+/// The number of blocks which the indexer has committed, if it has recorded
+/// it
+pub(super) fn committed(index: &Path) -> Option<u32> {
+  fs::read_to_string(committed_path(index)).ok()?.parse().ok()
+}
+
+/// Tracks which snapshot, published by a separate `ord index update
+/// --snapshot` process, a read-only index has loaded
+pub(crate) struct Follower {
+  version: Mutex<Version>,
+}
+
+impl Follower {
+  /// Load the snapshot if a new one has been published since it was last
+  /// loaded. If it cannot be loaded, the previous snapshot continues to be
+  /// served. The lock is only held to compare versions, so that loading a
+  /// snapshot does not block requests.
+  pub(super) fn refresh(&self, path: &Path, cache_size: usize) -> Result<Option<Database>> {
+    let version = Version::load(&snapshot_path(path))?;
+
+    if version == *self.version.lock().unwrap() {
+      return Ok(None);
+    }
+
+    let (database, follower) = open(path, cache_size)?;
+
+    *self.version.lock().unwrap() = follower.version.into_inner().unwrap();
+
+    Ok(Some(database))
+  }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+struct Version {
+  len: u64,
+  modified: SystemTime,
+}
+
+impl Version {
+  fn load(snapshot: &Path) -> Result<Self> {
+    Self::new(fs::metadata(snapshot).map_err(|err| error(err, snapshot))?)
+  }
+
+  fn new(metadata: fs::Metadata) -> Result<Self> {
+    Ok(Self {
+      len: metadata.len(),
+      modified: metadata.modified()?,
+    })
+  }
+}
+
+fn error(err: io::Error, snapshot: &Path) -> Error {
+  if err.kind() == io::ErrorKind::NotFound {
+    anyhow!(
+      "index snapshot at `{}` does not exist, run `ord index update --snapshot` to publish one before opening the index read-only",
+      snapshot.display()
+    )
+  } else {
+    anyhow!(
+      "failed to read index snapshot `{}`: {err}",
+      snapshot.display()
+    )
+  }
+}
+
+/// Open the snapshot published for the index at `path` in place. Snapshots
+/// are never modified once published, since newer snapshots replace them
+/// atomically, and the open file continues to refer to the snapshot which was
+/// loaded.
+pub(super) fn open(path: &Path, cache_size: usize) -> Result<(Database, Follower)> {
+  let snapshot = snapshot_path(path);
+
+  let file = File::open(&snapshot).map_err(|err| error(err, &snapshot))?;
+
+  let version = Version::new(file.metadata()?)?;
+
+  let database = Database::builder()
+    .set_cache_size(cache_size)
+    .create_with_backend(SnapshotBackend::new(file)?)
+    .with_context(|| format!("failed to open index snapshot `{}`", snapshot.display()))?;
+
+  Index::check_schema(&database, path)?;
+
+  Ok((
+    database,
+    Follower {
+      version: Mutex::new(version),
+    },
+  ))
+}
+
+/// Storage which reads a snapshot without modifying it. redb writes to the
+/// header of databases it opens and closes, so writes are kept in memory,
+/// private to this process, in blocks which shadow the snapshot.
+#[derive(Debug)]
+struct SnapshotBackend {
+  file: File,
+  state: Mutex<SnapshotState>,
+}
+
+#[derive(Debug)]
+struct SnapshotState {
+  blocks: BTreeMap<u64, Vec<u8>>,
+  len: u64,
+  /// How much of the file is visible, less than its length if storage has
+  /// been truncated
+  visible: u64,
+}
+
+impl SnapshotBackend {
+  const BLOCK_SIZE: u64 = 4096;
+
+  fn new(file: File) -> io::Result<Self> {
+    let len = file.metadata()?.len();
+
+    Ok(Self {
+      file,
+      state: Mutex::new(SnapshotState {
+        blocks: BTreeMap::new(),
+        len,
+        visible: len,
+      }),
+    })
+  }
+
+  /// Read `buffer.len()` bytes at `offset` from the visible part of the file,
+  /// leaving the rest of the buffer zeroed
+  fn read_file(&self, visible: u64, offset: u64, buffer: &mut [u8]) -> io::Result<()> {
+    let end = (offset + u64::try_from(buffer.len()).unwrap()).min(visible);
+
+    if offset >= end {
+      return Ok(());
+    }
+
+    let buffer = &mut buffer[..usize::try_from(end - offset).unwrap()];
+
+    #[cfg(unix)]
+    {
+      std::os::unix::fs::FileExt::read_exact_at(&self.file, buffer, offset)
+    }
+
+    #[cfg(windows)]
+    {
+      let mut read = 0;
+      while read < buffer.len() {
+        match std::os::windows::fs::FileExt::seek_read(
+          &self.file,
+          &mut buffer[read..],
+          offset + u64::try_from(read).unwrap(),
+        )? {
+          0 => return Err(io::ErrorKind::UnexpectedEof.into()),
+          n => read += n,
+        }
+      }
+      Ok(())
+    }
+  }
+
+  /// Blocks overlapping `len` bytes at `offset`, along with the range of
+  /// those bytes within the block and the position of the first of them
+  /// relative to `offset`
+  fn blocks(offset: u64, len: usize) -> impl Iterator<Item = (u64, Range<usize>, usize)> {
+    let end = offset + u64::try_from(len).unwrap();
+
+    (offset / Self::BLOCK_SIZE..end.div_ceil(Self::BLOCK_SIZE)).map(move |block| {
+      let start = block * Self::BLOCK_SIZE;
+      let from = offset.max(start);
+      let to = end.min(start + Self::BLOCK_SIZE);
+      (
+        block,
+        usize::try_from(from - start).unwrap()..usize::try_from(to - start).unwrap(),
+        usize::try_from(from - offset).unwrap(),
+      )
+    })
+  }
+}
+
+impl StorageBackend for SnapshotBackend {
+  fn len(&self) -> io::Result<u64> {
+    Ok(self.state.lock().unwrap().len)
+  }
+
+  fn read(&self, offset: u64, len: usize) -> io::Result<Vec<u8>> {
+    let (visible, shadowed) = {
+      let state = self.state.lock().unwrap();
+
+      let shadowed = Self::blocks(offset, len)
+        .filter_map(|(block, range, position)| {
+          Some((state.blocks.get(&block)?[range].to_vec(), position))
+        })
+        .collect::<Vec<(Vec<u8>, usize)>>();
+
+      (state.visible, shadowed)
+    };
+
+    let mut buffer = vec![0; len];
+
+    self.read_file(visible, offset, &mut buffer)?;
+
+    for (data, position) in shadowed {
+      buffer[position..position + data.len()].copy_from_slice(&data);
+    }
+
+    Ok(buffer)
+  }
+
+  fn set_len(&self, len: u64) -> io::Result<()> {
+    let mut state = self.state.lock().unwrap();
+
+    if len < state.len {
+      state.visible = state.visible.min(len);
+
+      state
+        .blocks
+        .retain(|block, _| block * Self::BLOCK_SIZE < len);
+
+      if let Some((block, data)) = state.blocks.iter_mut().next_back() {
+        let end = usize::try_from(len - block * Self::BLOCK_SIZE).unwrap();
+        if end < data.len() {
+          data[end..].fill(0);
+        }
+      }
+    }
+
+    state.len = len;
+
+    Ok(())
+  }
+
+  fn sync_data(&self, _eventual: bool) -> io::Result<()> {
+    Ok(())
+  }
+
+  fn write(&self, offset: u64, data: &[u8]) -> io::Result<()> {
+    let mut state = self.state.lock().unwrap();
+
+    let visible = state.visible;
+
+    for (block, range, position) in Self::blocks(offset, data.len()) {
+      let contents = match state.blocks.entry(block) {
+        btree_map::Entry::Occupied(entry) => entry.into_mut(),
+        btree_map::Entry::Vacant(entry) => {
+          let mut contents = vec![0; usize::try_from(Self::BLOCK_SIZE).unwrap()];
+          self.read_file(visible, block * Self::BLOCK_SIZE, &mut contents)?;
+          entry.insert(contents)
+        }
+      };
+
+      contents[range.clone()].copy_from_slice(&data[position..position + range.len()]);
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn snapshot_backend_shadows_writes() {
+    let tempdir = TempDir::new().unwrap();
+
+    let path = tempdir.path().join("snapshot");
+
+    let contents = (0..10_000u32)
+      .map(|i| u8::try_from(i % 256).unwrap())
+      .collect::<Vec<u8>>();
+
+    fs::write(&path, &contents).unwrap();
+
+    let backend = SnapshotBackend::new(File::open(&path).unwrap()).unwrap();
+
+    assert_eq!(backend.read(4000, 200).unwrap(), contents[4000..4200]);
+
+    backend.write(4090, &[0xff; 10]).unwrap();
+
+    let mut expected = contents.clone();
+    expected[4090..4100].fill(0xff);
+
+    assert_eq!(backend.read(4000, 200).unwrap(), expected[4000..4200]);
+
+    backend.set_len(12_000).unwrap();
+
+    assert_eq!(backend.len().unwrap(), 12_000);
+    assert_eq!(backend.read(9_990, 20).unwrap()[10..], [0; 10]);
+
+    backend.set_len(4095).unwrap();
+    backend.set_len(8192).unwrap();
+
+    assert_eq!(
+      backend.read(4090, 10).unwrap(),
+      [0xff, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0, 0]
+    );
+
+    assert_eq!(fs::read(&path).unwrap(), contents);
+  }
+}
@@ -0,0 +1,176 @@
+use {super::*, std::sync::RwLock};
+
+/// An in-memory overlay of inscriptions and runes in unconfirmed transactions,
+/// kept in sync with Bitcoin Core's mempool by `update`. Nothing is written to
+/// the database.
+#[derive(Default)]
+pub(crate) struct Mempool {
+  state: RwLock<State>,
+}
+
+#[derive(Default)]
+struct State {
+  spends: HashMap<OutPoint, Txid>,
+  transactions: HashMap<Txid, Entry>,
+}
+
+struct Entry {
+  artifact: Option<Artifact>,
+  created: Vec<api::PendingInscription>,
+  inputs: Vec<OutPoint>,
+  moving: Vec<InscriptionId>,
+  runes: BTreeMap<SpacedRune, Pile>,
+}
+
+impl Mempool {
+  pub(crate) fn update(&self, index: &Index) -> Result {
+    let txids = index
+      .client
+      .get_raw_mempool()?
+      .into_iter()
+      .collect::<HashSet<Txid>>();
+
+    let unknown = {
+      let state = self.state.read().unwrap();
+
+      txids
+        .iter()
+        .filter(|txid| !state.transactions.contains_key(*txid))
+        .copied()
+        .collect::<Vec<Txid>>()
+    };
+
+    let mut entries = Vec::new();
+
+    for txid in unknown {
+      // transaction may have been evicted or mined since mempool was fetched
+      let Some(transaction) = index
+        .client
+        .get_raw_transaction(&txid, None)
+        .into_option()?
+      else {
+        continue;
+      };
+
+      entries.push((txid, Self::entry(index, &transaction)?));
+    }
+
+    let mut state = self.state.write().unwrap();
+
+    state.transactions.retain(|txid, _| txids.contains(txid));
+
+    state.transactions.extend(entries);
+
+    state.spends = state
+      .transactions
+      .iter()
+      .flat_map(|(txid, entry)| entry.inputs.iter().map(|outpoint| (*outpoint, *txid)))
+      .collect();
+
+    Ok(())
+  }
+
+  /// Inscriptions and rune balances on inputs are looked up when a transaction
+  /// is first seen, so only confirmed inputs are taken into account.
+  fn entry(index: &Index, transaction: &Transaction) -> Result<Entry> {
+    let txid = transaction.compute_txid();
+
+    let inputs = transaction
+      .input
+      .iter()
+      .map(|input| input.previous_output)
+      .collect::<Vec<OutPoint>>();
+
+    let mut moving = Vec::new();
+    let mut runes = BTreeMap::<SpacedRune, Pile>::new();
+
+    for outpoint in &inputs {
+      moving.extend(
+        index
+          .get_inscriptions_for_output(*outpoint)?
+          .unwrap_or_default(),
+      );
+
+      for (rune, pile) in index
+        .get_rune_balances_for_output(*outpoint)?
+        .unwrap_or_default()
+      {
+        runes
+          .entry(rune)
+          .and_modify(|total| total.amount = total.amount.saturating_add(pile.amount))
+          .or_insert(pile);
+      }
+    }
+
+    let mut created = Vec::new();
+
+    for (i, envelope) in ParsedEnvelope::from_transaction(transaction)
+      .into_iter()
+      .enumerate()
+    {
+      created.push(api::PendingInscription {
+        content_length: envelope.payload.content_length(),
+        content_type: envelope.payload.content_type().map(str::to_string),
+        id: InscriptionId {
+          txid,
+          index: i.try_into()?,
+        },
+        metaprotocol: envelope.payload.metaprotocol().map(str::to_string),
+        parents: envelope.payload.parents(),
+      });
+    }
+
+    Ok(Entry {
+      artifact: Runestone::decipher(transaction),
+      created,
+      inputs,
+      moving,
+      runes,
+    })
+  }
+
+  pub(crate) fn pending_spend(&self, outpoint: OutPoint) -> Option<Txid> {
+    self.state.read().unwrap().spends.get(&outpoint).copied()
+  }
+
+  pub(crate) fn inscriptions(&self) -> api::MempoolInscriptions {
+    let state = self.state.read().unwrap();
+
+    let mut created = Vec::new();
+    let mut moving = BTreeMap::new();
+
+    for (txid, entry) in &state.transactions {
+      created.extend(entry.created.iter().cloned());
+
+      for inscription_id in &entry.moving {
+        moving.insert(*inscription_id, *txid);
+      }
+    }
+
+    created.sort_by_key(|inscription| inscription.id);
+
+    api::MempoolInscriptions { created, moving }
+  }
+
+  pub(crate) fn runes(&self) -> api::MempoolRunes {
+    api::MempoolRunes {
+      transactions: self
+        .state
+        .read()
+        .unwrap()
+        .transactions
+        .iter()
+        .filter(|(_, entry)| entry.artifact.is_some() || !entry.runes.is_empty())
+        .map(|(txid, entry)| {
+          (
+            *txid,
+            api::MempoolRuneTransaction {
+              artifact: entry.artifact.clone(),
+              spent: entry.runes.clone(),
+            },
+          )
+        })
+        .collect(),
+    }
+  }
+}
@@ -0,0 +1,583 @@
+use super::*;
+
+/// Upgrade steps for indexes built with an older schema. When bumping
+/// `SCHEMA_VERSION`, register a step from the previous version here if the
+/// change can be computed from the existing tables, so that existing indexes
+/// are migrated on open instead of having to be rebuilt.
+const MIGRATIONS: &[Migration] = &[
+  Migration {
+    from: 30,
+    description: "add SEQUENCE_NUMBER_TO_PROPERTIES",
+    run: add_sequence_number_to_properties,
+  },
+  Migration {
+    from: 31,
+    description: "add HEIGHT_TO_CHECKSUM",
+    run: add_height_to_checksum,
+  },
+];
+
+pub(super) struct Migration {
+  /// Schema version migrated from, to `from + 1`
+  pub(super) from: u64,
+  pub(super) description: &'static str,
+  pub(super) run: fn(&WriteTransaction) -> Result,
+}
+
+/// Envelopes of inscriptions which are already indexed aren't parsed again,
+/// so the table starts empty and properties are recorded for inscriptions
+/// indexed after migrating.
+fn add_sequence_number_to_properties(wtx: &WriteTransaction) -> Result {
+  wtx.open_table(SEQUENCE_NUMBER_TO_PROPERTIES)?;
+  Ok(())
+}
+
+/// Checksums cover every change made to the index up to a block, which can't
+/// be recomputed for blocks which are already indexed, so the table starts
+/// empty and checksums start from the first block indexed after migrating.
+fn add_height_to_checksum(wtx: &WriteTransaction) -> Result {
+  wtx.open_table(HEIGHT_TO_CHECKSUM)?;
+  Ok(())
+}
+
+pub(super) fn migrate(database: &Database, path: &Path, settings: &Settings) -> Result {
+  migrate_with(database, path, settings, MIGRATIONS, SCHEMA_VERSION)
+}
+
+/// Migrate `database` to schema `to` if every step from its current schema is
+/// in `migrations`. Each step is committed separately, and if one fails, the
+/// database is restored to the state it was in before the first step.
+/// Otherwise, the schema is left as it was, to be reported by
+/// `Index::check_schema`.
+fn migrate_with(
+  database: &Database,
+  path: &Path,
+  settings: &Settings,
+  migrations: &[Migration],
+  to: u64,
+) -> Result {
+  let from = database
+    .begin_read()?
+    .open_table(STATISTIC_TO_COUNT)?
+    .get(&Statistic::Schema.key())?
+    .map(|schema| schema.value())
+    .unwrap_or(0);
+
+  let Some(steps) = (from..to)
+    .map(|schema| migrations.iter().find(|migration| migration.from == schema))
+    .collect::<Option<Vec<&Migration>>>()
+  else {
+    return Ok(());
+  };
+
+  if steps.is_empty() {
+    return Ok(());
+  }
+
+  println!(
+    "Migrating index `{}` from schema {from} to {to}",
+    path.display()
+  );
+
+  let backup = {
+    let wtx = database.begin_write()?;
+    let savepoint = wtx.ephemeral_savepoint()?;
+    wtx.abort()?;
+    savepoint
+  };
+
+  let progress_bar = if cfg!(test) || log_enabled!(log::Level::Info) || settings.integration_test()
+  {
+    None
+  } else {
+    let progress_bar = ProgressBar::new(steps.len().try_into().unwrap());
+    progress_bar
+      .set_style(ProgressStyle::with_template("[migrating index] {wide_bar} {pos}/{len}").unwrap());
+    Some(progress_bar)
+  };
+
+  for migration in steps {
+    log::info!(
+      "Migrating index from schema {} to {}: {}",
+      migration.from,
+      migration.from + 1,
+      migration.description
+    );
+
+    let result = (|| -> Result {
+      let wtx = database.begin_write()?;
+
+      (migration.run)(&wtx)?;
+
+      wtx
+        .open_table(STATISTIC_TO_COUNT)?
+        .insert(&Statistic::Schema.key(), &(migration.from + 1))?;
+
+      wtx.commit()?;
+
+      Ok(())
+    })();
+
+    if let Err(err) = result {
+      let mut wtx = database.begin_write()?;
+      wtx.restore_savepoint(&backup)?;
+      wtx.commit()?;
+
+      return Err(err.context(format!(
+        "failed to migrate index from schema {} to {}: {}, index restored to schema {from}",
+        migration.from,
+        migration.from + 1,
+        migration.description
+      )));
+    }
+
+    if let Some(progress_bar) = &progress_bar {
+      progress_bar.inc(1);
+    }
+  }
+
+  if let Some(progress_bar) = progress_bar {
+    progress_bar.finish_and_clear();
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::{index::testing::Context, inscriptions::properties::Attributes},
+    tempfile::TempDir,
+  };
+
+  define_table! { OLD_HEIGHT_TO_BLOCK_HEADER, u64, &HeaderValue }
+
+  /// The previous schema keyed block headers by `u64`
+  fn rewrite_block_headers(wtx: &WriteTransaction) -> Result {
+    let mut height_to_block_header = wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+
+    for result in wtx.open_table(OLD_HEIGHT_TO_BLOCK_HEADER)?.iter()? {
+      let (height, header) = result?;
+      height_to_block_header.insert(u32::try_from(height.value())?, header.value())?;
+    }
+
+    wtx.delete_table(OLD_HEIGHT_TO_BLOCK_HEADER)?;
+
+    Ok(())
+  }
+
+  /// The schema before that didn't record the last sequence number of each
+  /// block
+  fn add_height_to_last_sequence_number(wtx: &WriteTransaction) -> Result {
+    let mut last_sequence_numbers = BTreeMap::new();
+
+    for result in wtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .iter()?
+    {
+      let (sequence_number, entry) = result?;
+      last_sequence_numbers.insert(
+        InscriptionEntry::load(entry.value()).height,
+        sequence_number.value() + 1,
+      );
+    }
+
+    let blocks = wtx
+      .open_table(OLD_HEIGHT_TO_BLOCK_HEADER)?
+      .iter()?
+      .next_back()
+      .transpose()?
+      .map(|(height, _header)| u32::try_from(height.value() + 1))
+      .transpose()?
+      .unwrap_or_default();
+
+    let mut height_to_last_sequence_number = wtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+
+    let mut last = 0;
+    for height in 0..blocks {
+      if let Some(sequence_number) = last_sequence_numbers.get(&height) {
+        last = *sequence_number;
+      }
+
+      height_to_last_sequence_number.insert(height, last)?;
+    }
+
+    Ok(())
+  }
+
+  fn fail(_wtx: &WriteTransaction) -> Result {
+    bail!("no space left on device")
+  }
+
+  const ADD_HEIGHT_TO_LAST_SEQUENCE_NUMBER: Migration = Migration {
+    from: SCHEMA_VERSION - 2,
+    description: "add HEIGHT_TO_LAST_SEQUENCE_NUMBER",
+    run: add_height_to_last_sequence_number,
+  };
+
+  const REWRITE_BLOCK_HEADERS: Migration = Migration {
+    from: SCHEMA_VERSION - 1,
+    description: "key HEIGHT_TO_BLOCK_HEADER by u32",
+    run: rewrite_block_headers,
+  };
+
+  type Rows = Vec<(Vec<u8>, Vec<u8>)>;
+
+  fn table<K: redb::Key + 'static, V: redb::Value + 'static>(
+    database: &Database,
+    definition: TableDefinition<K, V>,
+  ) -> Rows {
+    database
+      .begin_read()
+      .unwrap()
+      .open_table(definition)
+      .unwrap()
+      .iter()
+      .unwrap()
+      .map(|result| {
+        let (key, value) = result.unwrap();
+        let key = K::as_bytes(&key.value()).as_ref().to_vec();
+        let value = V::as_bytes(&value.value()).as_ref().to_vec();
+        (key, value)
+      })
+      .collect()
+  }
+
+  fn tables(database: &Database) -> Vec<String> {
+    database
+      .begin_read()
+      .unwrap()
+      .list_tables()
+      .unwrap()
+      .map(|handle| handle.name().to_string())
+      .collect()
+  }
+
+  fn schema(database: &Database) -> u64 {
+    database
+      .begin_read()
+      .unwrap()
+      .open_table(STATISTIC_TO_COUNT)
+      .unwrap()
+      .get(&Statistic::Schema.key())
+      .unwrap()
+      .unwrap()
+      .value()
+  }
+
+  struct OldIndex {
+    core: mockcore::Handle,
+    database: Database,
+    headers: Rows,
+    last_sequence_numbers: Rows,
+    settings: Settings,
+    #[allow(unused)]
+    tempdir: TempDir,
+  }
+
+  /// Build an index with inscriptions in a few blocks, and rewrite it in the
+  /// layout of two schema versions ago
+  fn old_index() -> OldIndex {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(2);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "bar").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let Context {
+      index,
+      core,
+      tempdir,
+    } = context;
+
+    let settings = index.settings.clone();
+
+    drop(index);
+
+    let database = Database::open(settings.index()).unwrap();
+
+    let headers = table(&database, HEIGHT_TO_BLOCK_HEADER);
+    let last_sequence_numbers = table(&database, HEIGHT_TO_LAST_SEQUENCE_NUMBER);
+
+    let wtx = database.begin_write().unwrap();
+
+    {
+      let mut old_height_to_block_header = wtx.open_table(OLD_HEIGHT_TO_BLOCK_HEADER).unwrap();
+
+      for result in wtx
+        .open_table(HEIGHT_TO_BLOCK_HEADER)
+        .unwrap()
+        .iter()
+        .unwrap()
+      {
+        let (height, header) = result.unwrap();
+        old_height_to_block_header
+          .insert(u64::from(height.value()), header.value())
+          .unwrap();
+      }
+    }
+
+    wtx.delete_table(HEIGHT_TO_BLOCK_HEADER).unwrap();
+    wtx.delete_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER).unwrap();
+
+    wtx
+      .open_table(STATISTIC_TO_COUNT)
+      .unwrap()
+      .insert(&Statistic::Schema.key(), &(SCHEMA_VERSION - 2))
+      .unwrap();
+
+    wtx.commit().unwrap();
+
+    OldIndex {
+      core,
+      database,
+      headers,
+      last_sequence_numbers,
+      settings,
+      tempdir,
+    }
+  }
+
+  #[test]
+  fn old_schema_is_migrated() {
+    let old = old_index();
+
+    migrate_with(
+      &old.database,
+      old.settings.index(),
+      &old.settings,
+      &[REWRITE_BLOCK_HEADERS, ADD_HEIGHT_TO_LAST_SEQUENCE_NUMBER],
+      SCHEMA_VERSION,
+    )
+    .unwrap();
+
+    assert_eq!(schema(&old.database), SCHEMA_VERSION);
+    assert!(!tables(&old.database).contains(&"OLD_HEIGHT_TO_BLOCK_HEADER".into()));
+    assert_eq!(table(&old.database, HEIGHT_TO_BLOCK_HEADER), old.headers);
+    assert_eq!(
+      table(&old.database, HEIGHT_TO_LAST_SEQUENCE_NUMBER),
+      old.last_sequence_numbers
+    );
+
+    drop(old.database);
+
+    let index = Index::open(&old.settings).unwrap();
+
+    assert_eq!(index.block_count().unwrap(), 5);
+
+    old.core.mine_blocks(1);
+
+    index.update().unwrap();
+
+    assert_eq!(index.block_count().unwrap(), 6);
+    assert_eq!(index.get_inscriptions_in_block(4).unwrap().len(), 1);
+  }
+
+  #[test]
+  fn height_to_checksum_is_added() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(2);
+
+    let Context {
+      index,
+      core,
+      tempdir: _tempdir,
+    } = context;
+
+    let settings = index.settings.clone();
+
+    drop(index);
+
+    let database = Database::open(settings.index()).unwrap();
+
+    {
+      let wtx = database.begin_write().unwrap();
+
+      wtx.delete_table(HEIGHT_TO_CHECKSUM).unwrap();
+
+      wtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .insert(&Statistic::Schema.key(), &31)
+        .unwrap();
+
+      wtx.commit().unwrap();
+    }
+
+    migrate(&database, settings.index(), &settings).unwrap();
+
+    assert_eq!(schema(&database), SCHEMA_VERSION);
+    assert!(tables(&database).contains(&"HEIGHT_TO_CHECKSUM".into()));
+    assert!(table(&database, HEIGHT_TO_CHECKSUM).is_empty());
+
+    drop(database);
+
+    let index = Index::open(&settings).unwrap();
+
+    assert_eq!(index.block_checksum(2).unwrap(), None);
+
+    core.mine_blocks(2);
+
+    index.update().unwrap();
+
+    assert_eq!(index.block_checksum(2).unwrap(), None);
+    assert!(index.block_checksum(3).unwrap().is_some());
+    assert!(index.block_checksum(4).unwrap().is_some());
+  }
+
+  #[test]
+  fn schema_30_index_is_migrated() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let Context {
+      index,
+      core,
+      tempdir: _tempdir,
+    } = context;
+
+    let settings = index.settings.clone();
+
+    drop(index);
+
+    let database = Database::open(settings.index()).unwrap();
+
+    {
+      let wtx = database.begin_write().unwrap();
+
+      wtx.delete_table(SEQUENCE_NUMBER_TO_PROPERTIES).unwrap();
+      wtx.delete_table(HEIGHT_TO_CHECKSUM).unwrap();
+
+      wtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .insert(&Statistic::Schema.key(), &30)
+        .unwrap();
+
+      wtx.commit().unwrap();
+    }
+
+    migrate(&database, settings.index(), &settings).unwrap();
+
+    assert_eq!(schema(&database), SCHEMA_VERSION);
+    assert!(tables(&database).contains(&"SEQUENCE_NUMBER_TO_PROPERTIES".into()));
+    assert!(tables(&database).contains(&"HEIGHT_TO_CHECKSUM".into()));
+
+    drop(database);
+
+    let index = Index::open(&settings).unwrap();
+
+    assert_eq!(index.block_count().unwrap(), 3);
+
+    let properties = Properties {
+      attributes: Attributes {
+        title: Some("bar".into()),
+        traits: BTreeMap::new(),
+      },
+      gallery: Vec::new(),
+    };
+
+    let txid = core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        Inscription {
+          properties: Some(properties.to_cbor()),
+          ..inscription("text/plain", "baz")
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    core.mine_blocks(1);
+
+    index.update().unwrap();
+
+    assert_eq!(index.block_count().unwrap(), 4);
+    assert_eq!(
+      index
+        .get_inscription_properties(InscriptionId { txid, index: 0 })
+        .unwrap(),
+      Some(properties),
+    );
+  }
+
+  #[test]
+  fn failed_migration_restores_index() {
+    let old = old_index();
+
+    let tables_before = tables(&old.database);
+
+    assert_eq!(
+      migrate_with(
+        &old.database,
+        old.settings.index(),
+        &old.settings,
+        &[
+          ADD_HEIGHT_TO_LAST_SEQUENCE_NUMBER,
+          Migration {
+            from: SCHEMA_VERSION - 1,
+            description: "fail",
+            run: fail,
+          },
+        ],
+        SCHEMA_VERSION,
+      )
+      .unwrap_err()
+      .to_string(),
+      format!(
+        "failed to migrate index from schema {} to {SCHEMA_VERSION}: fail, index restored to schema {}",
+        SCHEMA_VERSION - 1,
+        SCHEMA_VERSION - 2,
+      ),
+    );
+
+    assert_eq!(schema(&old.database), SCHEMA_VERSION - 2);
+    assert_eq!(tables(&old.database), tables_before);
+  }
+
+  #[test]
+  fn incomplete_migrations_are_not_run() {
+    let old = old_index();
+
+    migrate_with(
+      &old.database,
+      old.settings.index(),
+      &old.settings,
+      &[REWRITE_BLOCK_HEADERS],
+      SCHEMA_VERSION,
+    )
+    .unwrap();
+
+    assert_eq!(schema(&old.database), SCHEMA_VERSION - 2);
+
+    assert!(Index::check_schema(&old.database, old.settings.index())
+      .unwrap_err()
+      .to_string()
+      .contains("appears to have been built with an older, incompatible version of ord"));
+  }
+}
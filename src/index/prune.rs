@@ -0,0 +1,249 @@
+use super::*;
+
+impl Index {
+  /// Reorg detection and recovery look back this many blocks from the chain
+  /// tip, so history any closer to it is never pruned
+  pub(crate) const MIN_PRUNE_DEPTH: u32 = reorg::CHAIN_TIP_DISTANCE;
+
+  /// Height below which block headers, stored transactions, and per-block
+  /// inscription lists have been discarded
+  pub(crate) fn pruned_height(&self) -> Result<u32> {
+    Ok(
+      self
+        .database()
+        .begin_read()?
+        .open_table(STATISTIC_TO_COUNT)?
+        .get(&Statistic::PrunedHeight.key())?
+        .map(|height| u32::try_from(height.value()).unwrap())
+        .unwrap_or_default(),
+    )
+  }
+
+  pub(crate) fn is_pruned(&self, height: u32) -> Result<bool> {
+    Ok(height < self.pruned_height()?)
+  }
+
+  /// Discard history more than `--prune` blocks below `block_count`. Current
+  /// UTXO entries, inscription locations, and rune balances are kept.
+  pub(super) fn prune(&self, wtx: &WriteTransaction, block_count: u32) -> Result {
+    let Some(prune_height) = self
+      .settings
+      .prune()
+      .and_then(|depth| block_count.checked_sub(depth))
+    else {
+      return Ok(());
+    };
+
+    let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
+
+    let pruned_height = statistic_to_count
+      .get(&Statistic::PrunedHeight.key())?
+      .map(|height| u32::try_from(height.value()).unwrap())
+      .unwrap_or_default();
+
+    if prune_height <= pruned_height {
+      return Ok(());
+    }
+
+    log::info!("Pruning history below height {prune_height}");
+
+    let mut height_to_last_sequence_number = wtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+
+    if self.index_transactions {
+      if let Some(end) = height_to_last_sequence_number
+        .get(prune_height - 1)?
+        .map(|sequence_number| sequence_number.value())
+      {
+        let sequence_number_to_inscription_entry =
+          wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+        let mut transaction_id_to_transaction = wtx.open_table(TRANSACTION_ID_TO_TRANSACTION)?;
+
+        for result in sequence_number_to_inscription_entry.range(..end)?.rev() {
+          let (_sequence_number, entry) = result?;
+          let entry = InscriptionEntry::load(entry.value());
+
+          if entry.height < pruned_height {
+            break;
+          }
+
+          transaction_id_to_transaction.remove(&entry.id.txid.store())?;
+        }
+      }
+    }
+
+    // the last sequence number of the block below the oldest retained block
+    // is kept, since it is where the retained block's inscriptions start
+    height_to_last_sequence_number.retain_in(..prune_height - 1, |_, _| false)?;
+
+    wtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .retain_in(..prune_height, |_, _| false)?;
+
+    wtx
+      .open_table(HEIGHT_TO_CHECKSUM)?
+      .retain_in(..prune_height, |_, _| false)?;
+
+    wtx
+      .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
+      .retain_in(..prune_height, |_, _| false)?;
+
+    statistic_to_count.insert(&Statistic::PrunedHeight.key(), &u64::from(prune_height))?;
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  fn keys<V: redb::Value + 'static>(
+    index: &Index,
+    definition: TableDefinition<u32, V>,
+  ) -> Vec<u32> {
+    index
+      .database()
+      .begin_read()
+      .unwrap()
+      .open_table(definition)
+      .unwrap()
+      .iter()
+      .unwrap()
+      .map(|result| result.unwrap().0.value())
+      .collect()
+  }
+
+  #[test]
+  fn history_below_prune_depth_is_discarded() {
+    let context = Context::builder()
+      .args(["--index-transactions", "--prune", "21"])
+      .build();
+
+    context.mine_blocks(1);
+
+    let old = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "old").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(30);
+
+    let new = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "new").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let old = InscriptionId {
+      txid: old,
+      index: 0,
+    };
+    let new = InscriptionId {
+      txid: new,
+      index: 0,
+    };
+
+    assert_eq!(context.index.pruned_height().unwrap(), 12);
+    assert!(context.index.is_pruned(11).unwrap());
+    assert!(!context.index.is_pruned(12).unwrap());
+
+    assert_eq!(
+      keys(&context.index, HEIGHT_TO_BLOCK_HEADER),
+      (12..33).collect::<Vec<u32>>()
+    );
+
+    assert_eq!(
+      keys(&context.index, HEIGHT_TO_CHECKSUM),
+      (12..33).collect::<Vec<u32>>()
+    );
+
+    assert_eq!(
+      keys(&context.index, HEIGHT_TO_LAST_SEQUENCE_NUMBER),
+      (11..33).collect::<Vec<u32>>()
+    );
+
+    assert_eq!(
+      keys(
+        &context.index,
+        WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP
+      ),
+      [32]
+    );
+
+    assert_eq!(
+      context
+        .index
+        .database()
+        .begin_read()
+        .unwrap()
+        .open_table(TRANSACTION_ID_TO_TRANSACTION)
+        .unwrap()
+        .iter()
+        .unwrap()
+        .map(|result| Txid::load(*result.unwrap().0.value()))
+        .collect::<Vec<Txid>>(),
+      [new.txid],
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_satpoint_by_id(old)
+        .unwrap()
+        .unwrap(),
+      SatPoint {
+        outpoint: OutPoint {
+          txid: old.txid,
+          vout: 0
+        },
+        offset: 0,
+      },
+    );
+
+    assert_eq!(context.index.get_inscriptions_in_block(32).unwrap(), [new]);
+
+    assert!(context.index.get_inscription_by_id(old).unwrap().is_some());
+
+    let blocktime = context.index.block_time(Height(1)).unwrap();
+
+    assert!(matches!(blocktime, Blocktime::Confirmed(_)));
+
+    let state = context.core.state();
+
+    assert_eq!(
+      blocktime.unix_timestamp(),
+      i64::from(state.blocks[&state.hashes[1]].header.time),
+    );
+  }
+
+  #[test]
+  fn history_is_kept_without_prune() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(30);
+
+    assert_eq!(context.index.pruned_height().unwrap(), 0);
+
+    assert_eq!(
+      keys(&context.index, HEIGHT_TO_BLOCK_HEADER),
+      (0..31).collect::<Vec<u32>>()
+    );
+  }
+
+  #[test]
+  fn pruned_index_cannot_be_backfilled() {
+    let context = Context::builder().args(["--prune", "21"]).build();
+
+    context.mine_blocks(30);
+
+    assert_eq!(
+      context
+        .index
+        .backfill(Feature::Runes)
+        .unwrap_err()
+        .to_string(),
+      "cannot backfill `runes` on a pruned index, reindex with `--index-runes` instead",
+    );
+  }
+}
@@ -21,7 +21,7 @@ impl std::error::Error for Error {}
 
 const MAX_SAVEPOINTS: u32 = 2;
 const SAVEPOINT_INTERVAL: u32 = 10;
-const CHAIN_TIP_DISTANCE: u32 = 21;
+pub(super) const CHAIN_TIP_DISTANCE: u32 = 21;
 
 pub(crate) struct Reorg {}
 
@@ -62,8 +62,12 @@ impl Reorg {
 
     let mut wtx = index.begin_write()?;
 
-    let oldest_savepoint =
-      wtx.get_persistent_savepoint(wtx.list_persistent_savepoints()?.min().unwrap())?;
+    let oldest_savepoint = wtx.get_persistent_savepoint(
+      wtx
+        .list_persistent_savepoints()?
+        .min()
+        .context("no savepoint to roll back to, the index must be rebuilt")?,
+    )?;
 
     wtx.restore_savepoint(&oldest_savepoint)?;
 
@@ -78,6 +82,23 @@ impl Reorg {
     Ok(())
   }
 
+  pub(crate) fn delete_savepoints(index: &Index) -> Result {
+    let wtx = index.begin_write()?;
+
+    for savepoint in wtx.list_persistent_savepoints()?.collect::<Vec<u64>>() {
+      wtx.delete_persistent_savepoint(savepoint)?;
+    }
+
+    wtx
+      .open_table(STATISTIC_TO_COUNT)?
+      .remove(&Statistic::LastSavepointHeight.key())?;
+
+    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
+
+    Ok(())
+  }
+
   pub(crate) fn update_savepoints(index: &Index, height: u32) -> Result {
     if let redb::Durability::None = index.durability {
       return Ok(());
@@ -110,19 +131,32 @@ impl Reorg {
       Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
       wtx.commit()?;
 
-      let wtx = index.begin_write()?;
-
-      log::debug!("creating savepoint at height {}", height);
-      wtx.persistent_savepoint()?;
+      Self::create_savepoint(index, height)?;
+    }
 
-      wtx
-        .open_table(STATISTIC_TO_COUNT)?
-        .insert(&Statistic::LastSavepointHeight.key(), &height)?;
+    Ok(())
+  }
 
-      Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
-      wtx.commit()?;
+  /// Create a savepoint at `height`, regardless of the distance to the chain
+  /// tip or the last savepoint. Returns false if the index's durability does
+  /// not permit savepoints.
+  pub(crate) fn create_savepoint(index: &Index, height: u64) -> Result<bool> {
+    if let redb::Durability::None = index.durability {
+      return Ok(false);
     }
 
-    Ok(())
+    let wtx = index.begin_write()?;
+
+    log::debug!("creating savepoint at height {}", height);
+    wtx.persistent_savepoint()?;
+
+    wtx
+      .open_table(STATISTIC_TO_COUNT)?
+      .insert(&Statistic::LastSavepointHeight.key(), &height)?;
+
+    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
+
+    Ok(true)
   }
 }
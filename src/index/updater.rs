@@ -2,6 +2,7 @@ use {
   self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
   super::{fetcher::Fetcher, *},
   futures::future::try_join_all,
+  rayon::prelude::*,
   tokio::sync::{
     broadcast::{self, error::TryRecvError},
     mpsc::{self},
@@ -22,7 +23,7 @@ impl From<Block> for BlockData {
       header: block.header,
       txdata: block
         .txdata
-        .into_iter()
+        .into_par_iter()
         .map(|transaction| {
           let txid = transaction.compute_txid();
           (transaction, txid)
@@ -32,6 +33,91 @@ impl From<Block> for BlockData {
   }
 }
 
+/// The sats assigned to a transaction's outputs, which only depend on the sat
+/// ranges of its inputs and so can be computed without access to the index
+pub(super) struct TransactionSats {
+  leftover: Vec<u8>,
+  outputs: Vec<Vec<u8>>,
+  rare: Vec<(u64, SatPoint)>,
+  sat_ranges: u64,
+  splits: u64,
+}
+
+impl TransactionSats {
+  fn assign(tx: &Transaction, txid: Txid, input_sat_ranges: &[&[u8]]) -> Self {
+    let mut pending_input_sat_range = None;
+    let mut input_sat_ranges_iter = input_sat_ranges
+      .iter()
+      .flat_map(|slice| slice.chunks_exact(11));
+
+    let mut sats = Self {
+      leftover: Vec::new(),
+      outputs: Vec::with_capacity(tx.output.len()),
+      rare: Vec::new(),
+      sat_ranges: 0,
+      splits: 0,
+    };
+
+    for (vout, output) in tx.output.iter().enumerate() {
+      let outpoint = OutPoint {
+        vout: vout.try_into().unwrap(),
+        txid,
+      };
+
+      let mut ranges = Vec::new();
+
+      let mut remaining = output.value.to_sat();
+      while remaining > 0 {
+        let range = pending_input_sat_range.take().unwrap_or_else(|| {
+          SatRange::load(
+            input_sat_ranges_iter
+              .next()
+              .expect("insufficient inputs for transaction outputs")
+              .try_into()
+              .unwrap(),
+          )
+        });
+
+        if !Sat(range.0).common() {
+          sats.rare.push((
+            range.0,
+            SatPoint {
+              outpoint,
+              offset: output.value.to_sat() - remaining,
+            },
+          ));
+        }
+
+        let count = range.1 - range.0;
+
+        let assigned = if count > remaining {
+          sats.splits += 1;
+          let middle = range.0 + remaining;
+          pending_input_sat_range = Some((middle, range.1));
+          (range.0, middle)
+        } else {
+          range
+        };
+
+        ranges.extend_from_slice(&assigned.store());
+
+        remaining -= assigned.1 - assigned.0;
+
+        sats.sat_ranges += 1;
+      }
+
+      sats.outputs.push(ranges);
+    }
+
+    if let Some(range) = pending_input_sat_range {
+      sats.leftover.extend(&range.store());
+    }
+    sats.leftover.extend(input_sat_ranges_iter.flatten());
+
+    sats
+  }
+}
+
 pub(crate) struct Updater<'index> {
   pub(super) height: u32,
   pub(super) index: &'index Index,
@@ -328,10 +414,21 @@ impl Updater<'_> {
     );
 
     let mut height_to_block_header = wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+    let mut height_to_checksum = wtx.open_table(HEIGHT_TO_CHECKSUM)?;
     let mut inscription_id_to_sequence_number =
       wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
     let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
 
+    let mut checksum = Checksum::new(
+      self
+        .height
+        .checked_sub(1)
+        .map(|height| height_to_checksum.get(height))
+        .transpose()?
+        .flatten()
+        .map(|checksum| sha256::Hash::from_byte_array(*checksum.value())),
+    );
+
     if self.index.index_inscriptions || self.index.index_addresses || self.index.index_sats {
       self.index_utxo_entries(
         &block,
@@ -339,6 +436,7 @@ impl Updater<'_> {
         output_sender,
         utxo_cache,
         wtx,
+        &mut checksum,
         &mut inscription_id_to_sequence_number,
         &mut statistic_to_count,
         &mut sat_ranges_written,
@@ -347,45 +445,19 @@ impl Updater<'_> {
     }
 
     if self.index.index_runes && self.height >= self.index.settings.first_rune_height() {
-      let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
-      let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
-      let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
-      let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
-      let mut transaction_id_to_rune = wtx.open_table(TRANSACTION_ID_TO_RUNE)?;
-
-      let runes = statistic_to_count
-        .get(&Statistic::Runes.into())?
-        .map(|x| x.value())
-        .unwrap_or(0);
-
-      let mut rune_updater = RuneUpdater {
-        event_sender: self.index.event_sender.as_ref(),
-        block_time: block.header.time,
-        burned: HashMap::new(),
-        client: &self.index.client,
-        height: self.height,
-        id_to_entry: &mut rune_id_to_rune_entry,
-        inscription_id_to_sequence_number: &mut inscription_id_to_sequence_number,
-        minimum: Rune::minimum_at_height(
-          self.index.settings.chain().network(),
-          Height(self.height),
-        ),
-        outpoint_to_balances: &mut outpoint_to_rune_balances,
-        rune_to_id: &mut rune_to_rune_id,
-        runes,
-        sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
-        statistic_to_count: &mut statistic_to_count,
-        transaction_id_to_rune: &mut transaction_id_to_rune,
-      };
-
-      for (i, (tx, txid)) in block.txdata.iter().enumerate() {
-        rune_updater.index_runes(u32::try_from(i).unwrap(), tx, *txid)?;
-      }
-
-      rune_updater.update()?;
+      Self::index_runes(
+        self.index,
+        self.height,
+        &block,
+        wtx,
+        &mut checksum,
+        &mut inscription_id_to_sequence_number,
+        &mut statistic_to_count,
+      )?;
     }
 
     height_to_block_header.insert(&self.height, &block.header.store())?;
+    height_to_checksum.insert(&self.height, &checksum.finish().to_byte_array())?;
 
     self.height += 1;
     self.outputs_traversed += outputs_in_block;
@@ -398,6 +470,57 @@ impl Updater<'_> {
     Ok(())
   }
 
+  pub(super) fn index_runes<'wtx>(
+    index: &Index,
+    height: u32,
+    block: &BlockData,
+    wtx: &'wtx WriteTransaction,
+    checksum: &mut Checksum,
+    inscription_id_to_sequence_number: &mut Table<'wtx, (u128, u128, u32), u32>,
+    statistic_to_count: &mut Table<'wtx, u64, u64>,
+  ) -> Result {
+    let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+    let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+    let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
+    let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
+    let mut transaction_id_to_rune = wtx.open_table(TRANSACTION_ID_TO_RUNE)?;
+
+    let runes = statistic_to_count
+      .get(&Statistic::Runes.into())?
+      .map(|x| x.value())
+      .unwrap_or(0);
+
+    let mut rune_updater = RuneUpdater {
+      event_sender: index.event_sender.as_ref(),
+      block_time: block.header.time,
+      burned: HashMap::new(),
+      checksum,
+      client: &index.client,
+      height,
+      id_to_entry: &mut rune_id_to_rune_entry,
+      inscription_id_to_sequence_number,
+      minimum: Rune::minimum_at_height(index.settings.chain().network(), Height(height)),
+      outpoint_to_balances: &mut outpoint_to_rune_balances,
+      rune_to_id: &mut rune_to_rune_id,
+      runes,
+      sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
+      statistic_to_count,
+      transaction_id_to_rune: &mut transaction_id_to_rune,
+    };
+
+    let artifacts = block
+      .txdata
+      .par_iter()
+      .map(|(tx, _txid)| Runestone::decipher(tx))
+      .collect::<Vec<Option<Artifact>>>();
+
+    for (i, ((tx, txid), artifact)) in block.txdata.iter().zip(artifacts).enumerate() {
+      rune_updater.index_runes(u32::try_from(i).unwrap(), tx, *txid, artifact)?;
+    }
+
+    rune_updater.update()
+  }
+
   fn index_utxo_entries<'wtx>(
     &mut self,
     block: &BlockData,
@@ -405,6 +528,7 @@ impl Updater<'_> {
     output_sender: &mut mpsc::Sender<OutPoint>,
     utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
     wtx: &'wtx WriteTransaction,
+    checksum: &mut Checksum,
     inscription_id_to_sequence_number: &mut Table<'wtx, (u128, u128, u32), u32>,
     statistic_to_count: &mut Table<'wtx, u64, u64>,
     sat_ranges_written: &mut u64,
@@ -421,6 +545,7 @@ impl Updater<'_> {
     let mut sequence_number_to_children = wtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
     let mut sequence_number_to_inscription_entry =
       wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let mut sequence_number_to_properties = wtx.open_table(SEQUENCE_NUMBER_TO_PROPERTIES)?;
     let mut transaction_id_to_transaction = wtx.open_table(TRANSACTION_ID_TO_TRANSACTION)?;
 
     let index_inscriptions = self.height >= self.index.settings.first_inscription_height()
@@ -500,6 +625,7 @@ impl Updater<'_> {
 
     let mut inscription_updater = InscriptionUpdater {
       blessed_inscription_count,
+      checksum,
       cursed_inscription_count,
       flotsam: Vec::new(),
       height: self.height,
@@ -513,12 +639,35 @@ impl Updater<'_> {
       sat_to_sequence_number: &mut sat_to_sequence_number,
       sequence_number_to_children: &mut sequence_number_to_children,
       sequence_number_to_entry: &mut sequence_number_to_inscription_entry,
+      sequence_number_to_properties: &mut sequence_number_to_properties,
       timestamp: block.header.time,
       transaction_buffer: Vec::new(),
       transaction_id_to_transaction: &mut transaction_id_to_transaction,
       unbound_inscriptions,
     };
 
+    let mut envelopes = if index_inscriptions {
+      block
+        .txdata
+        .par_iter()
+        .map(|(tx, _txid)| ParsedEnvelope::from_transaction(tx))
+        .collect()
+    } else {
+      Vec::new()
+    };
+
+    let (mut independent_input_utxo_entries, mut independent_sats) = if self.index.index_sats {
+      self.assign_independent_sats(
+        block,
+        utxo_cache,
+        &mut outpoint_to_utxo_entry,
+        &mut script_pubkey_to_outpoint,
+        txout_receiver,
+      )?
+    } else {
+      (Vec::new(), Vec::new())
+    };
+
     let mut coinbase_inputs = Vec::new();
     let mut lost_sat_ranges = Vec::new();
 
@@ -542,43 +691,22 @@ impl Updater<'_> {
 
       let input_utxo_entries = if tx_offset == 0 {
         Vec::new()
+      } else if let Some(entries) = independent_input_utxo_entries
+        .get_mut(tx_offset)
+        .and_then(Option::take)
+      {
+        entries
       } else {
         tx.input
           .iter()
           .map(|input| {
-            let outpoint = input.previous_output.store();
-
-            let entry = if let Some(entry) = utxo_cache.remove(&OutPoint::load(outpoint)) {
-              self.outputs_cached += 1;
-              entry
-            } else if let Some(entry) = outpoint_to_utxo_entry.remove(&outpoint)? {
-              if self.index.index_addresses {
-                let script_pubkey = entry.value().parse(self.index).script_pubkey();
-                if !script_pubkey_to_outpoint.remove(script_pubkey, outpoint)? {
-                  panic!("script pubkey entry ({script_pubkey:?}, {outpoint:?}) not found");
-                }
-              }
-
-              entry.value().to_buf()
-            } else {
-              assert!(!self.index.have_full_utxo_index());
-              let txout = txout_receiver.blocking_recv().map_err(|err| {
-                anyhow!(
-                  "failed to get transaction for {}: {err}",
-                  input.previous_output
-                )
-              })?;
-
-              let mut entry = UtxoEntryBuf::new();
-              entry.push_value(txout.value.to_sat(), self.index);
-              if self.index.index_addresses {
-                entry.push_script_pubkey(txout.script_pubkey.as_bytes(), self.index);
-              }
-
-              entry
-            };
-
-            Ok(entry)
+            self.remove_input_utxo_entry(
+              input.previous_output,
+              utxo_cache,
+              &mut outpoint_to_utxo_entry,
+              &mut script_pubkey_to_outpoint,
+              txout_receiver,
+            )
           })
           .collect::<Result<Vec<UtxoEntryBuf>>>()?
       };
@@ -611,12 +739,17 @@ impl Updater<'_> {
           leftover_sat_ranges = &mut coinbase_inputs;
         }
 
+        let sats = independent_sats
+          .get_mut(tx_offset)
+          .and_then(Option::take)
+          .unwrap_or_else(|| {
+            TransactionSats::assign(tx, *txid, input_sat_ranges.as_ref().unwrap())
+          });
+
         self.index_transaction_sats(
-          tx,
-          *txid,
+          sats,
           &mut sat_to_satpoint,
           &mut output_utxo_entries,
-          input_sat_ranges.as_ref().unwrap(),
           leftover_sat_ranges,
           sat_ranges_written,
           outputs_in_block,
@@ -637,6 +770,7 @@ impl Updater<'_> {
         inscription_updater.index_inscriptions(
           tx,
           *txid,
+          std::mem::take(&mut envelopes[tx_offset]),
           &input_utxo_entries,
           &mut output_utxo_entries,
           utxo_cache,
@@ -726,89 +860,137 @@ impl Updater<'_> {
     }
   }
 
-  fn index_transaction_sats(
+  /// Remove the UTXO entry spent by `outpoint` from the cache or the index,
+  /// or fetch it from Bitcoin Core if the UTXO index is incomplete
+  fn remove_input_utxo_entry(
     &mut self,
-    tx: &Transaction,
-    txid: Txid,
-    sat_to_satpoint: &mut Table<u64, &SatPointValue>,
-    output_utxo_entries: &mut [UtxoEntryBuf],
-    input_sat_ranges: &[&[u8]],
-    leftover_sat_ranges: &mut Vec<u8>,
-    sat_ranges_written: &mut u64,
-    outputs_traversed: &mut u64,
-  ) -> Result {
-    let mut pending_input_sat_range = None;
-    let mut input_sat_ranges_iter = input_sat_ranges
-      .iter()
-      .flat_map(|slice| slice.chunks_exact(11));
-
-    // Preallocate our temporary array, sized to hold the combined
-    // sat ranges from our inputs.  We'll never need more than that
-    // for a single output, even if we end up splitting some ranges.
-    let mut sats = Vec::with_capacity(
-      input_sat_ranges
-        .iter()
-        .map(|slice| slice.len())
-        .sum::<usize>(),
-    );
+    outpoint: OutPoint,
+    utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
+    outpoint_to_utxo_entry: &mut Table<&OutPointValue, &UtxoEntry>,
+    script_pubkey_to_outpoint: &mut MultimapTable<&[u8], OutPointValue>,
+    txout_receiver: &mut broadcast::Receiver<TxOut>,
+  ) -> Result<UtxoEntryBuf> {
+    if let Some(entry) = utxo_cache.remove(&outpoint) {
+      self.outputs_cached += 1;
+      return Ok(entry);
+    }
 
-    for (vout, output) in tx.output.iter().enumerate() {
-      let outpoint = OutPoint {
-        vout: vout.try_into().unwrap(),
-        txid,
-      };
+    if let Some(entry) = outpoint_to_utxo_entry.remove(&outpoint.store())? {
+      if self.index.index_addresses {
+        let script_pubkey = entry.value().parse(self.index).script_pubkey();
+        if !script_pubkey_to_outpoint.remove(script_pubkey, outpoint.store())? {
+          panic!("script pubkey entry ({script_pubkey:?}, {outpoint:?}) not found");
+        }
+      }
 
-      let mut remaining = output.value.to_sat();
-      while remaining > 0 {
-        let range = pending_input_sat_range.take().unwrap_or_else(|| {
-          SatRange::load(
-            input_sat_ranges_iter
-              .next()
-              .expect("insufficient inputs for transaction outputs")
-              .try_into()
-              .unwrap(),
-          )
-        });
+      return Ok(entry.value().to_buf());
+    }
 
-        if !Sat(range.0).common() {
-          sat_to_satpoint.insert(
-            &range.0,
-            &SatPoint {
-              outpoint,
-              offset: output.value.to_sat() - remaining,
-            }
-            .store(),
-          )?;
-        }
+    assert!(!self.index.have_full_utxo_index());
+    let txout = txout_receiver
+      .blocking_recv()
+      .map_err(|err| anyhow!("failed to get transaction for {outpoint}: {err}"))?;
 
-        let count = range.1 - range.0;
+    let mut entry = UtxoEntryBuf::new();
+    entry.push_value(txout.value.to_sat(), self.index);
+    if self.index.index_addresses {
+      entry.push_script_pubkey(txout.script_pubkey.as_bytes(), self.index);
+    }
 
-        let assigned = if count > remaining {
-          self.sat_ranges_since_flush += 1;
-          let middle = range.0 + remaining;
-          pending_input_sat_range = Some((middle, range.1));
-          (range.0, middle)
-        } else {
-          range
-        };
+    Ok(entry)
+  }
 
-        sats.extend_from_slice(&assigned.store());
+  /// Assign sats to the outputs of every transaction that doesn't spend an
+  /// output created earlier in the same block. The inputs of those
+  /// transactions are removed up front, so that sat ranges can then be
+  /// assigned in parallel. Transactions which do depend on an earlier one are
+  /// left as `None`, to be assigned in order.
+  fn assign_independent_sats(
+    &mut self,
+    block: &BlockData,
+    utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
+    outpoint_to_utxo_entry: &mut Table<&OutPointValue, &UtxoEntry>,
+    script_pubkey_to_outpoint: &mut MultimapTable<&[u8], OutPointValue>,
+    txout_receiver: &mut broadcast::Receiver<TxOut>,
+  ) -> Result<(Vec<Option<Vec<UtxoEntryBuf>>>, Vec<Option<TransactionSats>>)> {
+    let txids = block
+      .txdata
+      .iter()
+      .map(|(_, txid)| txid)
+      .collect::<HashSet<_>>();
 
-        remaining -= assigned.1 - assigned.0;
+    let mut input_utxo_entries = Vec::with_capacity(block.txdata.len());
 
-        *sat_ranges_written += 1;
+    for (tx_offset, (tx, _txid)) in block.txdata.iter().enumerate() {
+      if tx_offset == 0
+        || tx
+          .input
+          .iter()
+          .any(|input| txids.contains(&input.previous_output.txid))
+      {
+        input_utxo_entries.push(None);
+        continue;
       }
 
-      *outputs_traversed += 1;
+      input_utxo_entries.push(Some(
+        tx.input
+          .iter()
+          .map(|input| {
+            self.remove_input_utxo_entry(
+              input.previous_output,
+              utxo_cache,
+              outpoint_to_utxo_entry,
+              script_pubkey_to_outpoint,
+              txout_receiver,
+            )
+          })
+          .collect::<Result<Vec<UtxoEntryBuf>>>()?,
+      ));
+    }
+
+    let index = self.index;
+
+    let sats = block
+      .txdata
+      .par_iter()
+      .zip(&input_utxo_entries)
+      .map(|((tx, txid), entries)| {
+        let entries = entries.as_ref()?;
+
+        let input_sat_ranges = entries
+          .iter()
+          .map(|entry| entry.parse(index).sat_ranges())
+          .collect::<Vec<&[u8]>>();
 
-      output_utxo_entries[vout].push_sat_ranges(&sats, self.index);
-      sats.clear();
+        Some(TransactionSats::assign(tx, *txid, &input_sat_ranges))
+      })
+      .collect();
+
+    Ok((input_utxo_entries, sats))
+  }
+
+  fn index_transaction_sats(
+    &mut self,
+    sats: TransactionSats,
+    sat_to_satpoint: &mut Table<u64, &SatPointValue>,
+    output_utxo_entries: &mut [UtxoEntryBuf],
+    leftover_sat_ranges: &mut Vec<u8>,
+    sat_ranges_written: &mut u64,
+    outputs_traversed: &mut u64,
+  ) -> Result {
+    for (sat, satpoint) in sats.rare {
+      sat_to_satpoint.insert(&sat, &satpoint.store())?;
     }
 
-    if let Some(range) = pending_input_sat_range {
-      leftover_sat_ranges.extend(&range.store());
+    for (output_utxo_entry, sat_ranges) in output_utxo_entries.iter_mut().zip(&sats.outputs) {
+      output_utxo_entry.push_sat_ranges(sat_ranges, self.index);
     }
-    leftover_sat_ranges.extend(input_sat_ranges_iter.flatten());
+
+    leftover_sat_ranges.extend(sats.leftover);
+
+    self.sat_ranges_since_flush += sats.splits;
+    *sat_ranges_written += sats.sat_ranges;
+    *outputs_traversed += u64::try_from(sats.outputs.len()).unwrap();
 
     Ok(())
   }
@@ -859,6 +1041,9 @@ impl Updater<'_> {
     self.outputs_traversed = 0;
     Index::increment_statistic(&wtx, Statistic::SatRanges, self.sat_ranges_since_flush)?;
     self.sat_ranges_since_flush = 0;
+
+    self.index.prune(&wtx, self.height)?;
+
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
     wtx.commit()?;
 
@@ -868,6 +1053,8 @@ impl Updater<'_> {
 
     Reorg::update_savepoints(self.index, self.height)?;
 
+    follower::record_committed(&self.index.path, self.height)?;
+
     Ok(())
   }
 }
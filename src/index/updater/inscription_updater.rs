@@ -27,6 +27,7 @@ enum Origin {
     fee: u64,
     hidden: bool,
     parents: Vec<InscriptionId>,
+    properties: Option<Vec<u8>>,
     reinscription: bool,
     unbound: bool,
     vindicated: bool,
@@ -39,6 +40,7 @@ enum Origin {
 
 pub(super) struct InscriptionUpdater<'a, 'tx> {
   pub(super) blessed_inscription_count: u64,
+  pub(super) checksum: &'a mut Checksum,
   pub(super) cursed_inscription_count: u64,
   pub(super) flotsam: Vec<Flotsam>,
   pub(super) height: u32,
@@ -54,6 +56,7 @@ pub(super) struct InscriptionUpdater<'a, 'tx> {
   pub(super) sat_to_sequence_number: &'a mut MultimapTable<'tx, u64, u32>,
   pub(super) sequence_number_to_children: &'a mut MultimapTable<'tx, u32, u32>,
   pub(super) sequence_number_to_entry: &'a mut Table<'tx, u32, InscriptionEntryValue>,
+  pub(super) sequence_number_to_properties: &'a mut Table<'tx, u32, &'static [u8]>,
   pub(super) timestamp: u32,
   pub(super) unbound_inscriptions: u64,
 }
@@ -63,6 +66,7 @@ impl InscriptionUpdater<'_, '_> {
     &mut self,
     tx: &Transaction,
     txid: Txid,
+    envelopes: Vec<ParsedEnvelope>,
     input_utxo_entries: &[ParsedUtxoEntry],
     output_utxo_entries: &mut [UtxoEntryBuf],
     utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
@@ -80,7 +84,6 @@ impl InscriptionUpdater<'_, '_> {
       .map(|txout| txout.value.to_sat())
       .sum::<u64>();
 
-    let envelopes = ParsedEnvelope::from_transaction(tx);
     let has_new_inscriptions = !envelopes.is_empty();
     let mut envelopes = envelopes.into_iter().peekable();
 
@@ -200,6 +203,7 @@ impl InscriptionUpdater<'_, '_> {
             fee: 0,
             hidden: inscription.payload.hidden(),
             parents: inscription.payload.parents(),
+            properties: inscription.payload.properties.clone(),
             reinscription: inscribed_offsets.contains_key(&offset),
             unbound: input_value == 0
               || curse == Some(Curse::UnrecognizedEvenField)
@@ -398,6 +402,10 @@ impl InscriptionUpdater<'_, '_> {
           )?;
         }
 
+        self
+          .checksum
+          .inscription_transferred(sequence_number, old_satpoint, new_satpoint);
+
         if let Some(ref sender) = index.event_sender {
           sender.blocking_send(Event::InscriptionTransferred {
             block_height: self.height,
@@ -415,6 +423,7 @@ impl InscriptionUpdater<'_, '_> {
         fee,
         hidden,
         parents,
+        properties,
         reinscription,
         unbound,
         vindicated,
@@ -504,26 +513,37 @@ impl InscriptionUpdater<'_, '_> {
           })?;
         }
 
-        self.sequence_number_to_entry.insert(
+        let entry = InscriptionEntry {
+          charms,
+          fee,
+          height: self.height,
+          id: inscription_id,
+          inscription_number,
+          parents: parent_sequence_numbers,
+          sat,
           sequence_number,
-          &InscriptionEntry {
-            charms,
-            fee,
-            height: self.height,
-            id: inscription_id,
-            inscription_number,
-            parents: parent_sequence_numbers,
-            sat,
-            sequence_number,
-            timestamp: self.timestamp,
-          }
-          .store(),
-        )?;
+          timestamp: self.timestamp,
+        }
+        .store();
+
+        self
+          .checksum
+          .inscription_created(&entry, (!unbound).then_some(new_satpoint));
+
+        self
+          .sequence_number_to_entry
+          .insert(sequence_number, &entry)?;
 
         self
           .id_to_sequence_number
           .insert(&inscription_id.store(), sequence_number)?;
 
+        if let Some(properties) = properties {
+          self
+            .sequence_number_to_properties
+            .insert(sequence_number, properties.as_slice())?;
+        }
+
         if !hidden {
           self
             .home_inscriptions
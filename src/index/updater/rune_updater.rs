@@ -3,6 +3,7 @@ use super::*;
 pub(super) struct RuneUpdater<'a, 'tx, 'client> {
   pub(super) block_time: u32,
   pub(super) burned: HashMap<RuneId, Lot>,
+  pub(super) checksum: &'a mut Checksum,
   pub(super) client: &'client Client,
   pub(super) event_sender: Option<&'a mpsc::Sender<Event>>,
   pub(super) height: u32,
@@ -18,9 +19,13 @@ pub(super) struct RuneUpdater<'a, 'tx, 'client> {
 }
 
 impl RuneUpdater<'_, '_, '_> {
-  pub(super) fn index_runes(&mut self, tx_index: u32, tx: &Transaction, txid: Txid) -> Result<()> {
-    let artifact = Runestone::decipher(tx);
-
+  pub(super) fn index_runes(
+    &mut self,
+    tx_index: u32,
+    tx: &Transaction,
+    txid: Txid,
+    artifact: Option<Artifact>,
+  ) -> Result<()> {
     let mut unallocated = self.unallocated(tx)?;
 
     let mut allocated: Vec<HashMap<RuneId, Lot>> = vec![HashMap::new(); tx.output.len()];
@@ -209,6 +214,8 @@ impl RuneUpdater<'_, '_, '_> {
         }
       }
 
+      self.checksum.rune_balance(outpoint, &buffer);
+
       self
         .outpoint_to_balances
         .insert(&outpoint.store(), buffer.as_slice())?;
@@ -232,9 +239,15 @@ impl RuneUpdater<'_, '_, '_> {
   }
 
   pub(super) fn update(self) -> Result {
-    for (rune_id, burned) in self.burned {
+    let mut burned = self.burned.into_iter().collect::<Vec<(RuneId, Lot)>>();
+
+    // updated entries are added to the checksum, so must be written in order
+    burned.sort_by_key(|(rune_id, _burned)| *rune_id);
+
+    for (rune_id, burned) in burned {
       let mut entry = RuneEntry::load(self.id_to_entry.get(&rune_id.store())?.unwrap().value());
       entry.burned = entry.burned.checked_add(burned.n()).unwrap();
+      self.checksum.rune_entry(rune_id, entry.store());
       self.id_to_entry.insert(&rune_id.store(), entry.store())?;
     }
 
@@ -306,6 +319,8 @@ impl RuneUpdater<'_, '_, '_> {
       }
     };
 
+    self.checksum.rune_entry(id, entry.store());
+
     self.id_to_entry.insert(id.store(), entry.store())?;
 
     if let Some(sender) = self.event_sender {
@@ -394,6 +409,8 @@ impl RuneUpdater<'_, '_, '_> {
 
     rune_entry.mints += 1;
 
+    self.checksum.rune_entry(id, rune_entry.store());
+
     self.id_to_entry.insert(&id.store(), rune_entry.store())?;
 
     Ok(Some(Lot(amount)))
@@ -81,6 +81,26 @@ impl UtxoEntry {
     }
   }
 
+  /// Insert `script_pubkey` into an entry written without
+  /// `--index-addresses`, for backfilling the address index
+  pub fn with_script_pubkey(&self, script_pubkey: &[u8], index: &Index) -> UtxoEntryBuf {
+    assert!(!index.index_addresses);
+
+    let inscriptions = self.parse(index).inscriptions.map(<[u8]>::len).unwrap_or(0);
+    let (sats, inscriptions) = self.bytes.split_at(self.bytes.len() - inscriptions);
+
+    let mut vec = sats.to_vec();
+    varint::encode_to_vec(script_pubkey.len().try_into().unwrap(), &mut vec);
+    vec.extend(script_pubkey);
+    vec.extend(inscriptions);
+
+    UtxoEntryBuf {
+      vec,
+      #[cfg(debug_assertions)]
+      state: State::Valid,
+    }
+  }
+
   pub fn to_buf(&self) -> UtxoEntryBuf {
     UtxoEntryBuf {
       vec: self.bytes.to_vec(),
@@ -1,14 +1,11 @@
 use super::*;
 
-use tag::Tag;
+pub(crate) use self::media::{Media, MediaType};
 
-pub(crate) use self::{envelope::ParsedEnvelope, media::Media};
+pub use self::{compression::Compression, inscription::InscriptionExt, properties::Properties};
 
-pub use self::{envelope::Envelope, inscription::Inscription, inscription_id::InscriptionId};
-
-mod envelope;
+mod compression;
 mod inscription;
-pub(crate) mod inscription_id;
 pub(crate) mod media;
-mod tag;
+pub mod properties;
 pub(crate) mod teleburn;
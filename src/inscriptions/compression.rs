@@ -0,0 +1,170 @@
+use {
+  super::*,
+  brotli::enc::{
+    backward_references::BrotliEncoderMode, writer::CompressorWriter, BrotliEncoderParams,
+  },
+  flate2::{read::GzDecoder, write::GzEncoder},
+  io::Write,
+};
+
+#[derive(Debug, PartialEq, Copy, Clone, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Compression {
+  #[default]
+  Brotli,
+  Gzip,
+  Zstd,
+}
+
+impl Compression {
+  pub const ALL: [Self; 3] = [Self::Brotli, Self::Gzip, Self::Zstd];
+
+  /// Value of the `Content-Encoding` header for content compressed with this
+  /// algorithm
+  pub fn content_encoding(self) -> &'static str {
+    match self {
+      Self::Brotli => "br",
+      Self::Gzip => "gzip",
+      Self::Zstd => "zstd",
+    }
+  }
+
+  pub fn from_content_encoding(content_encoding: &str) -> Option<Self> {
+    Self::ALL
+      .into_iter()
+      .find(|compression| compression.content_encoding() == content_encoding)
+  }
+
+  pub(crate) fn compress(self, body: &[u8], mode: BrotliEncoderMode) -> Result<Vec<u8>> {
+    let mut compressed = Vec::new();
+
+    match self {
+      Self::Brotli => {
+        CompressorWriter::with_params(
+          &mut compressed,
+          body.len(),
+          &BrotliEncoderParams {
+            lgblock: 24,
+            lgwin: 24,
+            mode,
+            quality: 11,
+            size_hint: body.len(),
+            ..default()
+          },
+        )
+        .write_all(body)?;
+      }
+      Self::Gzip => {
+        let mut encoder = GzEncoder::new(&mut compressed, flate2::Compression::best());
+        encoder.write_all(body)?;
+        encoder.finish()?;
+      }
+      Self::Zstd => {
+        compressed = zstd::encode_all(body, zstd::zstd_safe::max_c_level())?;
+      }
+    }
+
+    ensure!(
+      self.decompress(&compressed, body.len())? == body,
+      "decompression roundtrip failed"
+    );
+
+    Ok(compressed)
+  }
+
+  /// Decompress `body`, returning an error if the decompressed content would
+  /// be larger than `limit` bytes
+  pub(crate) fn decompress(self, body: &[u8], limit: usize) -> Result<Vec<u8>> {
+    let decoder: Box<dyn Read + '_> = match self {
+      Self::Brotli => Box::new(brotli::Decompressor::new(body, 4096)),
+      Self::Gzip => Box::new(GzDecoder::new(body)),
+      Self::Zstd => Box::new(zstd::Decoder::new(body)?),
+    };
+
+    let mut decompressed = Vec::new();
+
+    decoder
+      .take(u64::try_from(limit).unwrap().saturating_add(1))
+      .read_to_end(&mut decompressed)?;
+
+    ensure!(
+      decompressed.len() <= limit,
+      "decompressed content larger than {limit} byte limit",
+    );
+
+    Ok(decompressed)
+  }
+}
+
+impl Display for Compression {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Brotli => "brotli",
+        Self::Gzip => "gzip",
+        Self::Zstd => "zstd",
+      }
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn round_trip() {
+    let body = "foo".repeat(100);
+
+    for compression in Compression::ALL {
+      let compressed = compression
+        .compress(body.as_bytes(), BrotliEncoderMode::BROTLI_MODE_TEXT)
+        .unwrap();
+
+      assert!(compressed.len() < body.len(), "{compression}");
+
+      assert_eq!(
+        compression.decompress(&compressed, body.len()).unwrap(),
+        body.as_bytes(),
+        "{compression}",
+      );
+    }
+  }
+
+  #[test]
+  fn decompression_is_bounded() {
+    let body = vec![0; 1 << 20];
+
+    for compression in Compression::ALL {
+      let compressed = compression
+        .compress(&body, BrotliEncoderMode::BROTLI_MODE_GENERIC)
+        .unwrap();
+
+      assert_eq!(
+        compression
+          .decompress(&compressed, body.len() - 1)
+          .unwrap_err()
+          .to_string(),
+        format!(
+          "decompressed content larger than {} byte limit",
+          body.len() - 1
+        ),
+        "{compression}",
+      );
+    }
+  }
+
+  #[test]
+  fn content_encoding() {
+    for compression in Compression::ALL {
+      assert_eq!(
+        Compression::from_content_encoding(compression.content_encoding()),
+        Some(compression),
+      );
+    }
+
+    assert_eq!(Compression::from_content_encoding("deflate"), None);
+  }
+}
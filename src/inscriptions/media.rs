@@ -4,10 +4,11 @@ use {
   brotli::enc::backward_references::BrotliEncoderMode::{
     self, BROTLI_MODE_FONT as FONT, BROTLI_MODE_GENERIC as GENERIC, BROTLI_MODE_TEXT as TEXT,
   },
-  mp4::{MediaType, Mp4Reader, TrackType},
+  mp4::{MediaType as Mp4MediaType, Mp4Reader, TrackType},
 };
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Media {
   Audio,
   Code(Language),
@@ -22,7 +23,8 @@ pub enum Media {
   Video,
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Language {
   Css,
   JavaScript,
@@ -47,7 +49,8 @@ impl Display for Language {
   }
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ImageRendering {
   Auto,
   Pixelated,
@@ -66,6 +69,38 @@ impl Display for ImageRendering {
   }
 }
 
+#[derive(Debug, Default, PartialEq, Copy, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BrotliMode {
+  #[default]
+  Generic,
+  Text,
+  Font,
+}
+
+impl From<BrotliMode> for BrotliEncoderMode {
+  fn from(mode: BrotliMode) -> Self {
+    match mode {
+      BrotliMode::Generic => GENERIC,
+      BrotliMode::Text => TEXT,
+      BrotliMode::Font => FONT,
+    }
+  }
+}
+
+/// A media type defined in `ord.yaml`, which extends `Media::TABLE`
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MediaType {
+  #[serde(default)]
+  pub brotli_mode: BrotliMode,
+  pub content_security_policy: Option<String>,
+  pub content_type: String,
+  #[serde(default)]
+  pub extensions: Vec<String>,
+  pub media: Media,
+}
+
 impl Media {
   #[rustfmt::skip]
   const TABLE: &'static [(&'static str, BrotliEncoderMode, Media, &'static [&'static str])] = &[
@@ -108,9 +143,10 @@ impl Media {
     ("video/webm",                  GENERIC, Video,            &["webm"]),
   ];
 
-  pub(crate) fn content_type_for_path(
+  pub(crate) fn content_type_for_path<'a>(
     path: &Path,
-  ) -> Result<(&'static str, BrotliEncoderMode), Error> {
+    media_types: &'a [MediaType],
+  ) -> Result<(&'a str, BrotliEncoderMode), Error> {
     let extension = path
       .extension()
       .ok_or_else(|| anyhow!("file must have extension"))?
@@ -123,6 +159,16 @@ impl Media {
       Media::check_mp4_codec(path)?;
     }
 
+    for media_type in media_types {
+      if media_type
+        .extensions
+        .iter()
+        .any(|candidate| candidate.to_lowercase() == extension)
+      {
+        return Ok((&media_type.content_type, media_type.brotli_mode.into()));
+      }
+    }
+
     for (content_type, mode, _, extensions) in Self::TABLE {
       if extensions.contains(&extension.as_str()) {
         return Ok((*content_type, *mode));
@@ -132,6 +178,11 @@ impl Media {
     let mut extensions = Self::TABLE
       .iter()
       .flat_map(|(_, _, _, extensions)| extensions.first().cloned())
+      .chain(
+        media_types
+          .iter()
+          .flat_map(|media_type| media_type.extensions.first().map(String::as_str)),
+      )
       .collect::<Vec<&str>>();
 
     extensions.sort();
@@ -152,7 +203,7 @@ impl Media {
     for track in mp4.tracks().values() {
       if let TrackType::Video = track.track_type()? {
         let media_type = track.media_type()?;
-        if media_type != MediaType::H264 {
+        if media_type != Mp4MediaType::H264 {
           return Err(anyhow!(
             "Unsupported video codec, only H.264 is supported in MP4: {media_type}"
           ));
@@ -185,30 +236,87 @@ mod tests {
   #[test]
   fn for_extension() {
     assert_eq!(
-      Media::content_type_for_path(Path::new("pepe.jpg")).unwrap(),
+      Media::content_type_for_path(Path::new("pepe.jpg"), &[]).unwrap(),
       ("image/jpeg", BrotliEncoderMode::BROTLI_MODE_GENERIC)
     );
     assert_eq!(
-      Media::content_type_for_path(Path::new("pepe.jpeg")).unwrap(),
+      Media::content_type_for_path(Path::new("pepe.jpeg"), &[]).unwrap(),
       ("image/jpeg", BrotliEncoderMode::BROTLI_MODE_GENERIC)
     );
     assert_eq!(
-      Media::content_type_for_path(Path::new("pepe.JPG")).unwrap(),
+      Media::content_type_for_path(Path::new("pepe.JPG"), &[]).unwrap(),
       ("image/jpeg", BrotliEncoderMode::BROTLI_MODE_GENERIC)
     );
     assert_eq!(
-      Media::content_type_for_path(Path::new("pepe.txt")).unwrap(),
+      Media::content_type_for_path(Path::new("pepe.txt"), &[]).unwrap(),
       (
         "text/plain;charset=utf-8",
         BrotliEncoderMode::BROTLI_MODE_TEXT
       )
     );
     assert_regex_match!(
-      Media::content_type_for_path(Path::new("pepe.foo")).unwrap_err(),
+      Media::content_type_for_path(Path::new("pepe.foo"), &[]).unwrap_err(),
       r"unsupported file extension `\.foo`, supported extensions: apng .*"
     );
   }
 
+  #[test]
+  fn configured_media_types_extend_table() {
+    let media_types = [MediaType {
+      brotli_mode: BrotliMode::Text,
+      content_security_policy: None,
+      content_type: "text/csv".into(),
+      extensions: vec!["csv".into()],
+      media: Text,
+    }];
+
+    assert_eq!(
+      Media::content_type_for_path(Path::new("data.CSV"), &media_types).unwrap(),
+      ("text/csv", BrotliEncoderMode::BROTLI_MODE_TEXT)
+    );
+
+    assert_eq!(
+      Media::content_type_for_path(Path::new("pepe.jpg"), &media_types).unwrap(),
+      ("image/jpeg", BrotliEncoderMode::BROTLI_MODE_GENERIC)
+    );
+
+    assert_regex_match!(
+      Media::content_type_for_path(Path::new("pepe.foo"), &media_types).unwrap_err(),
+      r"unsupported file extension `\.foo`, supported extensions: apng .* csv .*"
+    );
+  }
+
+  #[test]
+  fn media_type_deserializes_from_yaml() {
+    assert_eq!(
+      serde_yaml::from_str::<MediaType>(
+        "content_type: image/bmp\nextensions: [bmp]\nmedia: !image pixelated\n"
+      )
+      .unwrap(),
+      MediaType {
+        brotli_mode: BrotliMode::Generic,
+        content_security_policy: None,
+        content_type: "image/bmp".into(),
+        extensions: vec!["bmp".into()],
+        media: Image(Pixelated),
+      }
+    );
+
+    assert_eq!(
+      serde_yaml::from_str::<MediaType>(
+        "brotli_mode: text\ncontent_security_policy: default-src 'self'\ncontent_type: text/x-python3\nmedia: !code python\n"
+      )
+      .unwrap(),
+      MediaType {
+        brotli_mode: BrotliMode::Text,
+        content_security_policy: Some("default-src 'self'".into()),
+        content_type: "text/x-python3".into(),
+        extensions: Vec::new(),
+        media: Code(Python),
+      }
+    );
+  }
+
   #[test]
   fn h264_in_mp4_is_allowed() {
     assert!(Media::check_mp4_codec(Path::new("examples/h264.mp4")).is_ok(),);
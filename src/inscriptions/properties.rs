@@ -0,0 +1,242 @@
+use super::*;
+
+/// Structured inscription properties, serialized as CBOR in the envelope's
+/// properties field. Unknown fields and trait values are ignored, so that
+/// properties written by newer versions may still be decoded.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Properties {
+  #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+  pub attributes: Attributes,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub gallery: Vec<Item>,
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Attributes {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub title: Option<String>,
+  #[serde(
+    default,
+    deserialize_with = "deserialize_traits",
+    skip_serializing_if = "BTreeMap::is_empty"
+  )]
+  pub traits: BTreeMap<String, Trait>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Item {
+  pub id: InscriptionId,
+  #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+  pub attributes: Attributes,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Trait {
+  Bool(bool),
+  Integer(i64),
+  String(String),
+}
+
+fn deserialize_traits<'de, D: Deserializer<'de>>(
+  deserializer: D,
+) -> Result<BTreeMap<String, Trait>, D::Error> {
+  #[derive(Deserialize)]
+  #[serde(untagged)]
+  enum Value {
+    Trait(Trait),
+    Unknown(serde::de::IgnoredAny),
+  }
+
+  Ok(
+    BTreeMap::<String, Value>::deserialize(deserializer)?
+      .into_iter()
+      .filter_map(|(name, value)| match value {
+        Value::Trait(value) => Some((name, value)),
+        Value::Unknown(_) => None,
+      })
+      .collect(),
+  )
+}
+
+impl Display for Trait {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Bool(value) => write!(f, "{value}"),
+      Self::Integer(value) => write!(f, "{value}"),
+      Self::String(value) => write!(f, "{value}"),
+    }
+  }
+}
+
+impl Attributes {
+  pub fn is_empty(&self) -> bool {
+    self.title.is_none() && self.traits.is_empty()
+  }
+}
+
+impl Properties {
+  pub fn is_empty(&self) -> bool {
+    self.attributes.is_empty() && self.gallery.is_empty()
+  }
+
+  pub fn from_cbor(cbor: &[u8]) -> Option<Self> {
+    ciborium::from_reader(Cursor::new(cbor)).ok()
+  }
+
+  pub fn to_cbor(&self) -> Vec<u8> {
+    let mut cbor = Vec::new();
+    ciborium::into_writer(self, &mut cbor).unwrap();
+    cbor
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn cbor_round_trip() {
+    let properties = Properties {
+      attributes: Attributes {
+        title: Some("foo".into()),
+        traits: [
+          ("bar".into(), Trait::Bool(true)),
+          ("baz".into(), Trait::Integer(-1)),
+          ("qux".into(), Trait::String("quux".into())),
+        ]
+        .into(),
+      },
+      gallery: vec![
+        Item {
+          id: inscription_id(1),
+          attributes: Attributes {
+            title: Some("corge".into()),
+            traits: BTreeMap::new(),
+          },
+        },
+        Item {
+          id: inscription_id(2),
+          attributes: Attributes::default(),
+        },
+      ],
+    };
+
+    assert_eq!(
+      Properties::from_cbor(&properties.to_cbor()),
+      Some(properties)
+    );
+  }
+
+  #[test]
+  fn empty_properties_serialize_to_empty_map() {
+    assert_eq!(Properties::default().to_cbor(), [0xa0]);
+  }
+
+  #[test]
+  fn invalid_cbor_is_ignored() {
+    assert_eq!(Properties::from_cbor(&[0xff]), None);
+    assert_eq!(Properties::from_cbor(&[0x01]), None);
+  }
+
+  fn cbor(value: ciborium::Value) -> Vec<u8> {
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&value, &mut cbor).unwrap();
+    cbor
+  }
+
+  fn text(text: &str) -> ciborium::Value {
+    ciborium::Value::Text(text.into())
+  }
+
+  #[test]
+  fn unknown_fields_are_ignored() {
+    assert_eq!(
+      Properties::from_cbor(&cbor(ciborium::Value::Map(vec![
+        (text("foo"), ciborium::Value::Bool(true)),
+        (
+          text("attributes"),
+          ciborium::Value::Map(vec![
+            (text("title"), text("bar")),
+            (text("baz"), ciborium::Value::Integer(1.into())),
+          ]),
+        ),
+        (
+          text("gallery"),
+          ciborium::Value::Array(vec![ciborium::Value::Map(vec![
+            (text("id"), text(&inscription_id(1).to_string())),
+            (text("qux"), ciborium::Value::Null),
+          ])]),
+        ),
+      ]))),
+      Some(Properties {
+        attributes: Attributes {
+          title: Some("bar".into()),
+          traits: BTreeMap::new(),
+        },
+        gallery: vec![Item {
+          id: inscription_id(1),
+          attributes: Attributes::default(),
+        }],
+      }),
+    );
+  }
+
+  #[test]
+  fn unknown_trait_values_are_ignored() {
+    assert_eq!(
+      Properties::from_cbor(&cbor(ciborium::Value::Map(vec![(
+        text("attributes"),
+        ciborium::Value::Map(vec![(
+          text("traits"),
+          ciborium::Value::Map(vec![
+            (text("foo"), ciborium::Value::Float(1.5)),
+            (text("bar"), ciborium::Value::Map(Vec::new())),
+            (text("baz"), ciborium::Value::Integer(u64::MAX.into())),
+            (text("qux"), ciborium::Value::Integer(1.into())),
+          ]),
+        )]),
+      )]))),
+      Some(Properties {
+        attributes: Attributes {
+          title: None,
+          traits: [("qux".into(), Trait::Integer(1))].into(),
+        },
+        gallery: Vec::new(),
+      }),
+    );
+  }
+
+  #[test]
+  fn yaml() {
+    assert_eq!(
+      serde_yaml::from_str::<Properties>(&format!(
+        "
+attributes:
+  title: foo
+  traits:
+    bar: true
+gallery:
+- id: {}
+  attributes:
+    title: baz
+",
+        inscription_id(1)
+      ))
+      .unwrap(),
+      Properties {
+        attributes: Attributes {
+          title: Some("foo".into()),
+          traits: [("bar".into(), Trait::Bool(true))].into(),
+        },
+        gallery: vec![Item {
+          id: inscription_id(1),
+          attributes: Attributes {
+            title: Some("baz".into()),
+            traits: BTreeMap::new(),
+          },
+        }],
+      },
+    );
+  }
+}
@@ -17,11 +17,11 @@ use {
     blocktime::Blocktime,
     decimal::Decimal,
     deserialize_from_str::DeserializeFromStr,
+    fee_rate::FEE_RATE_HELP,
     index::BitcoinCoreRpcResultExt,
     inscriptions::{
-      inscription_id,
-      media::{self, ImageRendering, Media},
-      teleburn, ParsedEnvelope,
+      media::{self, ImageRendering, Media, MediaType},
+      teleburn,
     },
     into_usize::IntoUsize,
     outgoing::Outgoing,
@@ -36,12 +36,12 @@ use {
   bitcoin::{
     address::{Address, NetworkUnchecked},
     blockdata::{
-      constants::{DIFFCHANGE_INTERVAL, MAX_SCRIPT_ELEMENT_SIZE, SUBSIDY_HALVING_INTERVAL},
+      constants::{DIFFCHANGE_INTERVAL, SUBSIDY_HALVING_INTERVAL},
       locktime::absolute::LockTime,
     },
     consensus::{self, Decodable, Encodable},
     hash_types::{BlockHash, TxMerkleNode},
-    hashes::Hash,
+    hashes::{sha256, Hash},
     policy::MAX_STANDARD_TX_WEIGHT,
     script,
     transaction::Version,
@@ -57,8 +57,9 @@ use {
   http::{HeaderMap, StatusCode},
   lazy_static::lazy_static,
   ordinals::{
-    varint, Artifact, Charm, Edict, Epoch, Etching, Height, Pile, Rarity, Rune, RuneId, Runestone,
-    Sat, SatPoint, SpacedRune, Terms,
+    inscription_id, runestone_builder, varint, Artifact, Cenotaph, Charm, Edict, Epoch, Etching,
+    Explanation, Height, InscriptionBuilder, ParsedEnvelope, Pile, Rarity, Rune, RuneId, Runestone,
+    RunestoneBuilder, Sat, SatPoint, SpacedRune, Terms, MAX_STANDARD_OP_RETURN_SIZE,
   },
   regex::Regex,
   reqwest::Url,
@@ -74,7 +75,6 @@ use {
     fmt::{self, Display, Formatter},
     fs::{self, File},
     io::{self, BufReader, Cursor, Read},
-    mem,
     net::ToSocketAddrs,
     path::{Path, PathBuf},
     process::{self, Command, Stdio},
@@ -92,14 +92,16 @@ use {
 
 pub use self::{
   chain::Chain,
-  fee_rate::FeeRate,
+  fee_rate::{FeeRate, FeeRateTarget},
   index::{Index, RuneEntry},
-  inscriptions::{Envelope, Inscription, InscriptionId},
+  inscriptions::{properties, Compression, InscriptionExt, Properties},
   object::Object,
   options::Options,
   wallet::transaction_builder::{Target, TransactionBuilder},
 };
 
+pub use ordinals::{Envelope, Inscription, InscriptionId};
+
 #[cfg(test)]
 #[macro_use]
 mod test;
@@ -135,12 +137,11 @@ pub mod wallet;
 type Result<T = (), E = Error> = std::result::Result<T, E>;
 type SnafuResult<T = (), E = SnafuError> = std::result::Result<T, E>;
 
-const MAX_STANDARD_OP_RETURN_SIZE: usize = 83;
 const TARGET_POSTAGE: Amount = Amount::from_sat(10_000);
 
 static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
 static LISTENERS: Mutex<Vec<axum_server::Handle>> = Mutex::new(Vec::new());
-static INDEXER: Mutex<Option<thread::JoinHandle<()>>> = Mutex::new(None);
+static INDEXER: Mutex<Vec<thread::JoinHandle<()>>> = Mutex::new(Vec::new());
 
 #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
 fn fund_raw_transaction(
@@ -242,9 +243,15 @@ pub fn shut_down() {
 }
 
 fn gracefully_shut_down_indexer() {
-  if let Some(indexer) = INDEXER.lock().unwrap().take() {
-    shut_down();
-    log::info!("Waiting for index thread to finish...");
+  let indexers = std::mem::take(&mut *INDEXER.lock().unwrap());
+
+  if indexers.is_empty() {
+    return;
+  }
+
+  shut_down();
+  log::info!("Waiting for index threads to finish...");
+  for indexer in indexers {
     if indexer.join().is_err() {
       log::warn!("Index thread panicked; join failed");
     }
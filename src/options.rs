@@ -57,6 +57,11 @@ pub struct Options {
   pub(crate) index_transactions: bool,
   #[arg(long, help = "Run in integration test mode.")]
   pub(crate) integration_test: bool,
+  #[arg(
+    long,
+    help = "Abort if estimated fee rate exceeds <MAX_FEE_RATE> sats/vB."
+  )]
+  pub(crate) max_fee_rate: Option<FeeRate>,
   #[clap(long, short, long, help = "Specify output format. [default: json]")]
   pub(crate) format: Option<OutputFormat>,
   #[arg(
@@ -66,6 +71,11 @@ pub struct Options {
     help = "Do not index inscriptions."
   )]
   pub(crate) no_index_inscriptions: bool,
+  #[arg(
+    long,
+    help = "Discard block headers, stored transactions, and other history older than <PRUNE> blocks."
+  )]
+  pub(crate) prune: Option<u32>,
   #[arg(
     long,
     help = "Require basic HTTP authentication with <SERVER_PASSWORD>. Credentials are sent in cleartext. Consider using authentication in conjunction with HTTPS."
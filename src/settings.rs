@@ -9,6 +9,7 @@ pub struct Settings {
   bitcoin_rpc_url: Option<String>,
   bitcoin_rpc_username: Option<String>,
   chain: Option<Chain>,
+  chains: Option<BTreeMap<Chain, Settings>>,
   commit_interval: Option<usize>,
   config: Option<PathBuf>,
   config_dir: Option<PathBuf>,
@@ -16,6 +17,7 @@ pub struct Settings {
   data_dir: Option<PathBuf>,
   height_limit: Option<u32>,
   hidden: Option<HashSet<InscriptionId>>,
+  hostname: Option<String>,
   http_port: Option<u16>,
   index: Option<PathBuf>,
   index_addresses: bool,
@@ -24,7 +26,10 @@ pub struct Settings {
   index_sats: bool,
   index_transactions: bool,
   integration_test: bool,
+  max_fee_rate: Option<FeeRate>,
+  media_types: Option<Vec<MediaType>>,
   no_index_inscriptions: bool,
+  prune: Option<u32>,
   server_password: Option<String>,
   server_url: Option<String>,
   server_username: Option<String>,
@@ -103,6 +108,24 @@ impl Settings {
       _ => {}
     };
 
+    if let Some(prune) = settings.prune {
+      ensure!(
+        prune >= Index::MIN_PRUNE_DEPTH,
+        "--prune must be at least {} blocks",
+        Index::MIN_PRUNE_DEPTH,
+      );
+    }
+
+    let mut extensions = HashSet::new();
+    for media_type in settings.media_types() {
+      for extension in &media_type.extensions {
+        ensure!(
+          extensions.insert(extension.to_lowercase()),
+          "duplicate media type extension `{extension}`"
+        );
+      }
+    }
+
     Ok(settings)
   }
 
@@ -114,6 +137,7 @@ impl Settings {
       bitcoin_rpc_url: self.bitcoin_rpc_url.or(source.bitcoin_rpc_url),
       bitcoin_rpc_username: self.bitcoin_rpc_username.or(source.bitcoin_rpc_username),
       chain: self.chain.or(source.chain),
+      chains: self.chains.or(source.chains),
       commit_interval: self.commit_interval.or(source.commit_interval),
       config: self.config.or(source.config),
       config_dir: self.config_dir.or(source.config_dir),
@@ -129,6 +153,7 @@ impl Settings {
           .cloned()
           .collect(),
       ),
+      hostname: self.hostname.or(source.hostname),
       http_port: self.http_port.or(source.http_port),
       index: self.index.or(source.index),
       index_addresses: self.index_addresses || source.index_addresses,
@@ -137,7 +162,10 @@ impl Settings {
       index_sats: self.index_sats || source.index_sats,
       index_transactions: self.index_transactions || source.index_transactions,
       integration_test: self.integration_test || source.integration_test,
+      max_fee_rate: self.max_fee_rate.or(source.max_fee_rate),
+      media_types: self.media_types.or(source.media_types),
       no_index_inscriptions: self.no_index_inscriptions || source.no_index_inscriptions,
+      prune: self.prune.or(source.prune),
       server_password: self.server_password.or(source.server_password),
       server_url: self.server_url.or(source.server_url),
       server_username: self.server_username.or(source.server_username),
@@ -158,6 +186,7 @@ impl Settings {
         .or(options.testnet.then_some(Chain::Testnet))
         .or(options.testnet4.then_some(Chain::Testnet4))
         .or(options.chain_argument),
+      chains: None,
       commit_interval: options.commit_interval,
       config: options.config,
       config_dir: options.config_dir,
@@ -165,6 +194,7 @@ impl Settings {
       data_dir: options.data_dir,
       height_limit: options.height_limit,
       hidden: None,
+      hostname: None,
       http_port: None,
       index: options.index,
       index_addresses: options.index_addresses,
@@ -173,7 +203,10 @@ impl Settings {
       index_sats: options.index_sats,
       index_transactions: options.index_transactions,
       integration_test: options.integration_test,
+      max_fee_rate: options.max_fee_rate,
+      media_types: None,
       no_index_inscriptions: options.no_index_inscriptions,
+      prune: options.prune,
       server_password: options.server_password,
       server_url: None,
       server_username: options.server_username,
@@ -215,6 +248,14 @@ impl Settings {
         })
     };
 
+    let get_fee_rate = |key| {
+      env
+        .get(key)
+        .map(|fee_rate| fee_rate.parse::<FeeRate>())
+        .transpose()
+        .with_context(|| format!("failed to parse environment variable ORD_{key} as fee rate"))
+    };
+
     let get_u16 = |key| {
       env
         .get(key)
@@ -246,6 +287,7 @@ impl Settings {
       bitcoin_rpc_url: get_string("BITCOIN_RPC_URL"),
       bitcoin_rpc_username: get_string("BITCOIN_RPC_USERNAME"),
       chain: get_chain("CHAIN")?,
+      chains: None,
       commit_interval: get_usize("COMMIT_INTERVAL")?,
       config: get_path("CONFIG"),
       config_dir: get_path("CONFIG_DIR"),
@@ -253,6 +295,7 @@ impl Settings {
       data_dir: get_path("DATA_DIR"),
       height_limit: get_u32("HEIGHT_LIMIT")?,
       hidden: inscriptions("HIDDEN")?,
+      hostname: None,
       http_port: get_u16("HTTP_PORT")?,
       index: get_path("INDEX"),
       index_addresses: get_bool("INDEX_ADDRESSES"),
@@ -261,7 +304,10 @@ impl Settings {
       index_sats: get_bool("INDEX_SATS"),
       index_transactions: get_bool("INDEX_TRANSACTIONS"),
       integration_test: get_bool("INTEGRATION_TEST"),
+      max_fee_rate: get_fee_rate("MAX_FEE_RATE")?,
+      media_types: None,
       no_index_inscriptions: get_bool("NO_INDEX_INSCRIPTIONS"),
+      prune: get_u32("PRUNE")?,
       server_password: get_string("SERVER_PASSWORD"),
       server_url: get_string("SERVER_URL"),
       server_username: get_string("SERVER_USERNAME"),
@@ -276,6 +322,7 @@ impl Settings {
       bitcoin_rpc_username: None,
       bitcoin_rpc_limit: None,
       chain: Some(Chain::Regtest),
+      chains: None,
       commit_interval: None,
       config: None,
       config_dir: None,
@@ -283,6 +330,7 @@ impl Settings {
       data_dir: Some(dir.into()),
       height_limit: None,
       hidden: None,
+      hostname: None,
       http_port: None,
       index: None,
       index_addresses: true,
@@ -291,7 +339,10 @@ impl Settings {
       index_sats: true,
       index_transactions: false,
       integration_test: false,
+      max_fee_rate: None,
+      media_types: None,
       no_index_inscriptions: false,
+      prune: None,
       server_password: None,
       server_url: Some(server_url.into()),
       server_username: None,
@@ -331,6 +382,69 @@ impl Settings {
       None => data_dir.join("index.redb"),
     };
 
+    ensure!(
+      self.hostname.is_none(),
+      "hostname may only be set for additional chains"
+    );
+
+    let chains = self
+      .chains
+      .map(|chains| {
+        let mut hostnames = BTreeMap::<String, Chain>::new();
+
+        chains
+          .into_iter()
+          .map(|(key, mut settings)| {
+            ensure!(
+              key != chain,
+              "chain `{key}` cannot be both the primary chain and an additional chain"
+            );
+
+            ensure!(
+              settings.chain.map_or(true, |chain| chain == key),
+              "settings for chain `{key}` have mismatched chain `{}`",
+              settings.chain.unwrap(),
+            );
+
+            ensure!(
+              settings.chains.is_none(),
+              "settings for chain `{key}` cannot contain additional chains"
+            );
+
+            let hostname = settings
+              .hostname
+              .take()
+              .ok_or_else(|| anyhow!("settings for chain `{key}` must specify a hostname"))?;
+
+            if let Some(other) = hostnames.insert(hostname.clone(), key) {
+              bail!("chains `{other}` and `{key}` cannot both be served at hostname `{hostname}`");
+            }
+
+            let settings = Settings {
+              chain: Some(key),
+              ..settings
+            }
+            .or(Settings {
+              bitcoin_data_dir: self.bitcoin_data_dir.clone(),
+              data_dir: self.data_dir.clone(),
+              integration_test: self.integration_test,
+              media_types: self.media_types.clone(),
+              ..default()
+            })
+            .or_defaults()?;
+
+            Ok((
+              key,
+              Settings {
+                hostname: Some(hostname),
+                ..settings
+              },
+            ))
+          })
+          .collect::<Result<BTreeMap<Chain, Settings>>>()
+      })
+      .transpose()?;
+
     Ok(Self {
       bitcoin_data_dir: Some(bitcoin_data_dir),
       bitcoin_rpc_limit: Some(self.bitcoin_rpc_limit.unwrap_or(12)),
@@ -343,6 +457,7 @@ impl Settings {
       ),
       bitcoin_rpc_username: self.bitcoin_rpc_username,
       chain: Some(chain),
+      chains,
       commit_interval: Some(self.commit_interval.unwrap_or(5000)),
       config: None,
       config_dir: None,
@@ -350,6 +465,7 @@ impl Settings {
       data_dir: Some(data_dir),
       height_limit: self.height_limit,
       hidden: self.hidden,
+      hostname: self.hostname,
       http_port: self.http_port,
       index: Some(index),
       index_addresses: self.index_addresses,
@@ -365,7 +481,10 @@ impl Settings {
       index_sats: self.index_sats,
       index_transactions: self.index_transactions,
       integration_test: self.integration_test,
+      max_fee_rate: self.max_fee_rate,
+      media_types: self.media_types,
       no_index_inscriptions: self.no_index_inscriptions,
+      prune: self.prune,
       server_password: self.server_password,
       server_url: self.server_url,
       server_username: self.server_username,
@@ -555,6 +674,14 @@ impl Settings {
     self.integration_test
   }
 
+  pub fn max_fee_rate(&self) -> Option<FeeRate> {
+    self.max_fee_rate
+  }
+
+  pub fn prune(&self) -> Option<u32> {
+    self.prune
+  }
+
   pub fn is_hidden(&self, inscription_id: InscriptionId) -> bool {
     self
       .hidden
@@ -563,6 +690,20 @@ impl Settings {
       .unwrap_or_default()
   }
 
+  pub(crate) fn media_types(&self) -> &[MediaType] {
+    self.media_types.as_deref().unwrap_or_default()
+  }
+
+  /// Settings for chains served alongside the primary chain
+  pub(crate) fn chains(&self) -> impl Iterator<Item = &Settings> {
+    self.chains.iter().flat_map(|chains| chains.values())
+  }
+
+  /// Hostname at which an additional chain is served
+  pub(crate) fn hostname(&self) -> Option<&str> {
+    self.hostname.as_deref()
+  }
+
   pub fn bitcoin_rpc_url(&self, wallet_name: Option<String>) -> String {
     let base_url = self.bitcoin_rpc_url.as_ref().unwrap();
     match wallet_name {
@@ -624,6 +765,220 @@ mod tests {
     );
   }
 
+  #[test]
+  fn duplicate_media_type_extensions_are_an_error() {
+    let tempdir = TempDir::new().unwrap();
+
+    let config_path = tempdir.path().join("ord.yaml");
+
+    fs::write(
+      &config_path,
+      "media_types:
+- content_type: text/csv
+  extensions: [csv]
+  media: text
+- content_type: text/x-csv
+  extensions: [CSV]
+  media: text",
+    )
+    .unwrap();
+
+    assert_eq!(
+      Settings::merge(
+        Options {
+          config: Some(config_path),
+          ..default()
+        },
+        Default::default(),
+      )
+      .unwrap_err()
+      .to_string(),
+      "duplicate media type extension `CSV`"
+    );
+  }
+
+  #[test]
+  fn additional_chains_are_resolved() {
+    let tempdir = TempDir::new().unwrap();
+
+    let config_path = tempdir.path().join("ord.yaml");
+
+    fs::write(
+      &config_path,
+      "chains:
+  signet:
+    bitcoin_rpc_url: foo:38332
+    hostname: signet.example.com
+    index_runes: true
+  testnet:
+    hostname: testnet.example.com",
+    )
+    .unwrap();
+
+    let settings = Settings::merge(
+      Options {
+        config: Some(config_path),
+        data_dir: Some("/data".into()),
+        bitcoin_data_dir: Some("/bitcoin".into()),
+        ..default()
+      },
+      Default::default(),
+    )
+    .unwrap();
+
+    let chains = settings.chains().collect::<Vec<&Settings>>();
+
+    assert_eq!(chains.len(), 2);
+
+    assert_eq!(chains[0].chain(), Chain::Signet);
+    assert_eq!(chains[0].bitcoin_rpc_url(None), "foo:38332/");
+    assert_eq!(chains[0].index(), Path::new("/data/signet/index.redb"));
+    assert_eq!(
+      chains[0].cookie_file().unwrap(),
+      Path::new("/bitcoin/signet/.cookie")
+    );
+    assert!(chains[0].index_runes_raw());
+    assert_eq!(chains[0].hostname(), Some("signet.example.com"));
+    assert_eq!(chains[0].chains().count(), 0);
+
+    assert_eq!(chains[1].chain(), Chain::Testnet);
+    assert_eq!(chains[1].bitcoin_rpc_url(None), "127.0.0.1:18332/");
+    assert_eq!(chains[1].index(), Path::new("/data/testnet3/index.redb"));
+    assert!(!chains[1].index_runes_raw());
+    assert_eq!(chains[1].hostname(), Some("testnet.example.com"));
+
+    assert!(!settings.index_runes_raw());
+    assert_eq!(settings.hostname(), None);
+  }
+
+  #[test]
+  fn additional_chains_must_specify_hostname() {
+    assert_eq!(
+      Settings {
+        chains: Some([(Chain::Signet, Settings::default())].into()),
+        ..default()
+      }
+      .or_defaults()
+      .unwrap_err()
+      .to_string(),
+      "settings for chain `signet` must specify a hostname"
+    );
+  }
+
+  #[test]
+  fn additional_chains_cannot_share_hostname() {
+    let settings = Settings {
+      hostname: Some("example.com".into()),
+      ..default()
+    };
+
+    assert_eq!(
+      Settings {
+        chains: Some(
+          [
+            (Chain::Signet, settings.clone()),
+            (Chain::Testnet, settings)
+          ]
+          .into()
+        ),
+        ..default()
+      }
+      .or_defaults()
+      .unwrap_err()
+      .to_string(),
+      "chains `signet` and `testnet` cannot both be served at hostname `example.com`"
+    );
+  }
+
+  #[test]
+  fn additional_chains_cannot_specify_zmq_endpoints() {
+    for key in ["bitcoin_zmq_hashblock", "bitcoin_zmq_rawtx"] {
+      assert!(serde_yaml::from_str::<Settings>(&format!(
+        "chains:
+  signet:
+    hostname: signet.example.com
+    {key}: tcp://127.0.0.1:28332"
+      ))
+      .unwrap_err()
+      .to_string()
+      .contains(&format!("unknown field `{key}`")));
+    }
+  }
+
+  #[test]
+  fn primary_chain_cannot_specify_hostname() {
+    assert_eq!(
+      Settings {
+        hostname: Some("example.com".into()),
+        ..default()
+      }
+      .or_defaults()
+      .unwrap_err()
+      .to_string(),
+      "hostname may only be set for additional chains"
+    );
+  }
+
+  #[test]
+  fn additional_chain_cannot_be_primary_chain() {
+    assert_eq!(
+      Settings {
+        chains: Some([(Chain::Mainnet, Settings::default())].into()),
+        ..default()
+      }
+      .or_defaults()
+      .unwrap_err()
+      .to_string(),
+      "chain `mainnet` cannot be both the primary chain and an additional chain"
+    );
+  }
+
+  #[test]
+  fn additional_chain_settings_must_match_chain() {
+    assert_eq!(
+      Settings {
+        chains: Some(
+          [(
+            Chain::Signet,
+            Settings {
+              chain: Some(Chain::Regtest),
+              ..default()
+            }
+          )]
+          .into()
+        ),
+        ..default()
+      }
+      .or_defaults()
+      .unwrap_err()
+      .to_string(),
+      "settings for chain `signet` have mismatched chain `regtest`"
+    );
+  }
+
+  #[test]
+  fn additional_chains_cannot_be_nested() {
+    assert_eq!(
+      Settings {
+        chains: Some(
+          [(
+            Chain::Signet,
+            Settings {
+              chains: Some([(Chain::Regtest, Settings::default())].into()),
+              ..default()
+            }
+          )]
+          .into()
+        ),
+        ..default()
+      }
+      .or_defaults()
+      .unwrap_err()
+      .to_string(),
+      "settings for chain `signet` cannot contain additional chains"
+    );
+  }
+
   #[test]
   fn auth_missing_rpc_user_is_an_error() {
     assert_eq!(
@@ -1046,7 +1401,9 @@ mod tests {
       ("INDEX_SATS", "1"),
       ("INDEX_TRANSACTIONS", "1"),
       ("INTEGRATION_TEST", "1"),
+      ("MAX_FEE_RATE", "50"),
       ("NO_INDEX_INSCRIPTIONS", "1"),
+      ("PRUNE", "100"),
       ("SERVER_PASSWORD", "server password"),
       ("SERVER_URL", "server url"),
       ("SERVER_USERNAME", "server username"),
@@ -1064,6 +1421,7 @@ mod tests {
         bitcoin_rpc_url: Some("url".into()),
         bitcoin_rpc_username: Some("bitcoin username".into()),
         chain: Some(Chain::Signet),
+        chains: None,
         commit_interval: Some(1),
         config: Some("config".into()),
         config_dir: Some("config dir".into()),
@@ -1082,6 +1440,7 @@ mod tests {
           .into_iter()
           .collect()
         ),
+        hostname: None,
         http_port: Some(8080),
         index: Some("index".into()),
         index_addresses: true,
@@ -1090,7 +1449,10 @@ mod tests {
         index_sats: true,
         index_transactions: true,
         integration_test: true,
+        max_fee_rate: Some(FeeRate::try_from(50.0).unwrap()),
+        media_types: None,
         no_index_inscriptions: true,
+        prune: Some(100),
         server_password: Some("server password".into()),
         server_url: Some("server url".into()),
         server_username: Some("server username".into()),
@@ -1123,7 +1485,9 @@ mod tests {
           "--index-transactions",
           "--index=index",
           "--integration-test",
+          "--max-fee-rate=50",
           "--no-index-inscriptions",
+          "--prune=100",
           "--server-password=server password",
           "--server-username=server username",
         ])
@@ -1136,6 +1500,7 @@ mod tests {
         bitcoin_rpc_url: Some("url".into()),
         bitcoin_rpc_username: Some("bitcoin username".into()),
         chain: Some(Chain::Signet),
+        chains: None,
         commit_interval: Some(1),
         config: Some("config".into()),
         config_dir: Some("config dir".into()),
@@ -1143,6 +1508,7 @@ mod tests {
         data_dir: Some("/data/dir".into()),
         height_limit: Some(3),
         hidden: None,
+        hostname: None,
         http_port: None,
         index: Some("index".into()),
         index_addresses: true,
@@ -1151,7 +1517,10 @@ mod tests {
         index_sats: true,
         index_transactions: true,
         integration_test: true,
+        max_fee_rate: Some(FeeRate::try_from(50.0).unwrap()),
+        media_types: None,
         no_index_inscriptions: true,
+        prune: Some(100),
         server_password: Some("server password".into()),
         server_url: None,
         server_username: Some("server username".into()),
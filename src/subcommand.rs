@@ -72,7 +72,11 @@ impl Subcommand {
       Self::Parse(parse) => parse.run(),
       Self::Runes => runes::run(settings),
       Self::Server(server) => {
-        let index = Arc::new(Index::open(&settings)?);
+        let index = Arc::new(if server.read_only {
+          Index::open_read_only(&settings)?
+        } else {
+          Index::open(&settings)?
+        });
         let handle = axum_server::Handle::new();
         LISTENERS.lock().unwrap().push(handle.clone());
         server.run(settings, index, handle)
@@ -1,4 +1,9 @@
-use super::*;
+use {
+  super::*,
+  base64::{engine::general_purpose, Engine},
+  bitcoin::psbt::Psbt,
+  ordinals::RawEnvelope,
+};
 
 #[derive(Serialize, Eq, PartialEq, Deserialize, Debug)]
 pub struct CompactOutput {
@@ -6,6 +11,18 @@ pub struct CompactOutput {
   pub runestone: Option<Artifact>,
 }
 
+#[derive(Serialize, PartialEq, Deserialize, Debug)]
+pub struct ExplainOutput {
+  pub burned: Option<BTreeMap<SpacedRune, Pile>>,
+  pub explanation: Option<Explanation>,
+  pub runestone: Option<Artifact>,
+}
+
+#[derive(Serialize, Eq, PartialEq, Deserialize, Debug)]
+pub struct MempoolOutput {
+  pub transactions: BTreeMap<Txid, CompactOutput>,
+}
+
 #[derive(Serialize, Eq, PartialEq, Deserialize, Debug)]
 pub struct RawOutput {
   pub inscriptions: Vec<ParsedEnvelope>,
@@ -54,52 +71,220 @@ impl TryFrom<Inscription> for CompactInscription {
 }
 
 #[derive(Debug, Parser)]
+#[clap(group(
+  ArgGroup::new("input")
+    .args(&["txid", "file", "hex", "psbt", "mempool"])
+))]
 pub(crate) struct Decode {
+  #[arg(long, help = "Fetch transaction with <TXID> from Bitcoin Core.")]
+  txid: Option<Txid>,
   #[arg(
     long,
-    conflicts_with = "file",
-    help = "Fetch transaction with <TXID> from Bitcoin Core."
+    help = "Load transaction from <FILE>, which may be binary or hex-encoded."
   )]
-  txid: Option<Txid>,
-  #[arg(long, conflicts_with = "txid", help = "Load transaction from <FILE>.")]
   file: Option<PathBuf>,
+  #[arg(long, help = "Decode hex-encoded <TRANSACTION>.")]
+  hex: Option<String>,
+  #[arg(
+    long,
+    help = "Decode unsigned transaction of base64-encoded <PSBT>, including inscriptions in \
+    tapscript leaves of unsigned inputs."
+  )]
+  psbt: Option<String>,
+  #[arg(
+    long,
+    conflicts_with_all = &["compact", "explain"],
+    help = "Decode all mempool transactions containing inscriptions or runestones."
+  )]
+  mempool: bool,
   #[arg(
     long,
     help = "Serialize inscriptions in a compact, human-readable format."
   )]
   compact: bool,
+  #[arg(
+    long,
+    conflicts_with = "compact",
+    help = "Explain how the runestone is deciphered, including the byte offset of any flaw. \
+    If the runestone is a cenotaph and an index exists, look up runes in unspent inputs which \
+    would be burned. The index is not updated first."
+  )]
+  explain: bool,
 }
 
 impl Decode {
   pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    if self.mempool {
+      let client = settings.bitcoin_rpc_client(None)?;
+
+      let mut transactions = BTreeMap::new();
+
+      for txid in client.get_raw_mempool()? {
+        let transaction = client.get_raw_transaction(&txid, None)?;
+
+        let output = Self::compact(&transaction)?;
+
+        if !output.inscriptions.is_empty() || output.runestone.is_some() {
+          transactions.insert(txid, output);
+        }
+      }
+
+      return Ok(Some(Box::new(MempoolOutput { transactions })));
+    }
+
     let transaction = if let Some(txid) = self.txid {
       settings
         .bitcoin_rpc_client(None)?
         .get_raw_transaction(&txid, None)?
+    } else if let Some(hex) = self.hex {
+      Self::deserialize(hex.as_bytes())?
+    } else if let Some(psbt) = self.psbt {
+      Self::psbt_transaction(&psbt)?
     } else if let Some(file) = self.file {
-      Transaction::consensus_decode(&mut io::BufReader::new(File::open(file)?))?
+      Self::deserialize(&fs::read(file)?)?
     } else {
-      Transaction::consensus_decode(&mut io::BufReader::new(io::stdin()))?
+      let mut buffer = Vec::new();
+      io::stdin().read_to_end(&mut buffer)?;
+      Self::deserialize(&buffer)?
     };
 
-    let inscriptions = ParsedEnvelope::from_transaction(&transaction);
-
     let runestone = Runestone::decipher(&transaction);
 
-    if self.compact {
-      Ok(Some(Box::new(CompactOutput {
-        inscriptions: inscriptions
-          .clone()
-          .into_iter()
-          .map(|inscription| inscription.payload.try_into())
-          .collect::<Result<Vec<CompactInscription>>>()?,
+    if self.explain {
+      let burned = match &runestone {
+        Some(Artifact::Cenotaph(cenotaph)) => Self::burned(&settings, &transaction, cenotaph),
+        _ => None,
+      };
+
+      return Ok(Some(Box::new(ExplainOutput {
+        burned,
+        explanation: Runestone::explain(&transaction),
         runestone,
-      })))
+      })));
+    }
+
+    if self.compact {
+      Ok(Some(Box::new(Self::compact(&transaction)?)))
     } else {
       Ok(Some(Box::new(RawOutput {
-        inscriptions,
+        inscriptions: ParsedEnvelope::from_transaction(&transaction),
         runestone,
       })))
     }
   }
+
+  fn compact(transaction: &Transaction) -> Result<CompactOutput> {
+    Ok(CompactOutput {
+      inscriptions: ParsedEnvelope::from_transaction(transaction)
+        .into_iter()
+        .map(|inscription| inscription.payload.try_into())
+        .collect::<Result<Vec<CompactInscription>>>()?,
+      runestone: Runestone::decipher(transaction),
+    })
+  }
+
+  /// Deserialize a transaction which may be either binary or hex-encoded
+  fn deserialize(buffer: &[u8]) -> Result<Transaction> {
+    if let Ok(hex) = std::str::from_utf8(buffer) {
+      if let Ok(bytes) = hex::decode(hex.trim()) {
+        return Ok(consensus::encode::deserialize(&bytes)?);
+      }
+    }
+
+    Ok(consensus::encode::deserialize(buffer)?)
+  }
+
+  /// The unsigned transaction of a PSBT, with the witnesses of inputs that have
+  /// not been finalized replaced by the first tapscript leaf containing an
+  /// inscription envelope, so that pending reveals may be decoded
+  fn psbt_transaction(psbt: &str) -> Result<Transaction> {
+    let psbt = Psbt::deserialize(&general_purpose::STANDARD.decode(psbt.trim())?)?;
+
+    let mut transaction = psbt.unsigned_tx;
+
+    for (i, (txin, input)) in transaction.input.iter_mut().zip(psbt.inputs).enumerate() {
+      if let Some(witness) = input.final_script_witness {
+        txin.witness = witness;
+        continue;
+      }
+
+      if let Some((control_block, (script, _))) =
+        input.tap_scripts.iter().find(|(_, (script, _))| {
+          RawEnvelope::from_tapscript(script, i)
+            .map(|envelopes| !envelopes.is_empty())
+            .unwrap_or_default()
+        })
+      {
+        txin.witness = Witness::from_slice(&[script.to_bytes(), control_block.serialize()]);
+      }
+    }
+
+    Ok(transaction)
+  }
+
+  /// Runes burned by `cenotaph`, according to an existing index. The lookup
+  /// is best-effort: the index is never created or updated, and any error
+  /// opening or reading it is ignored.
+  fn burned(
+    settings: &Settings,
+    transaction: &Transaction,
+    cenotaph: &Cenotaph,
+  ) -> Option<BTreeMap<SpacedRune, Pile>> {
+    if !settings.index().is_file() {
+      return None;
+    }
+
+    let index = Index::open(settings).ok()?;
+
+    Self::burned_from_index(&index, transaction, cenotaph)
+      .ok()
+      .flatten()
+  }
+
+  fn burned_from_index(
+    index: &Index,
+    transaction: &Transaction,
+    cenotaph: &Cenotaph,
+  ) -> Result<Option<BTreeMap<SpacedRune, Pile>>> {
+    if !index.has_rune_index() {
+      return Ok(None);
+    }
+
+    let mut burned = BTreeMap::<SpacedRune, Pile>::new();
+
+    for input in &transaction.input {
+      if input.previous_output.is_null() {
+        continue;
+      }
+
+      for (spaced_rune, pile) in index
+        .get_rune_balances_for_output(input.previous_output)?
+        .unwrap_or_default()
+      {
+        burned
+          .entry(spaced_rune)
+          .and_modify(|burned| burned.amount += pile.amount)
+          .or_insert(pile);
+      }
+    }
+
+    if let Some(id) = cenotaph.mint {
+      if let Some(rune) = index.get_rune_by_id(id)? {
+        let (_, entry, _) = index.rune(rune)?.unwrap();
+
+        if let Ok(amount) = entry.mintable(index.block_count()?.into()) {
+          burned
+            .entry(entry.spaced_rune)
+            .or_insert(Pile {
+              amount: 0,
+              divisibility: entry.divisibility,
+              symbol: entry.symbol,
+            })
+            .amount += amount;
+        }
+      }
+    }
+
+    Ok(Some(burned))
+  }
 }
@@ -18,7 +18,7 @@ pub(crate) struct Env {
   directory: PathBuf,
   #[arg(
     long,
-    help = "Decompress encoded content. Currently only supports brotli. Be careful using this on production instances. A decompressed inscription may be arbitrarily large, making decompression a DoS vector."
+    help = "Decompress brotli, gzip, and zstd encoded content for clients which do not accept it. Decompressed content is limited to 64 MiB."
   )]
   pub(crate) decompress: bool,
   #[arg(
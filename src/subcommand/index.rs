@@ -1,25 +1,37 @@
 use super::*;
 
+pub mod checksum;
+pub mod compact;
+mod enable;
 mod export;
 pub mod info;
 mod update;
 
 #[derive(Debug, Parser)]
 pub(crate) enum IndexSubcommand {
+  #[command(about = "Print the index checksum of a block")]
+  Checksum(checksum::Checksum),
+  #[command(about = "Compact the index file")]
+  Compact,
+  #[command(about = "Backfill and enable an optional index")]
+  Enable(enable::Enable),
   #[command(about = "Write inscription numbers and ids to a tab-separated file")]
   Export(export::Export),
   #[command(about = "Print index statistics")]
   Info(info::Info),
   #[command(about = "Update the index", alias = "run")]
-  Update,
+  Update(update::Update),
 }
 
 impl IndexSubcommand {
   pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
     match self {
+      Self::Checksum(checksum) => checksum.run(settings),
+      Self::Compact => compact::run(settings),
+      Self::Enable(enable) => enable.run(settings),
       Self::Export(export) => export.run(settings),
       Self::Info(info) => info.run(settings),
-      Self::Update => update::run(settings),
+      Self::Update(update) => update.run(settings),
     }
   }
 }
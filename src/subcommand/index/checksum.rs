@@ -0,0 +1,42 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Checksum {
+  #[arg(
+    long,
+    help = "Print checksum of block at <HEIGHT>. [default: last indexed block]"
+  )]
+  height: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Output {
+  pub checksum: sha256::Hash,
+  pub height: u32,
+}
+
+impl Checksum {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    let height = match self.height {
+      Some(height) => height,
+      None => index
+        .block_height()?
+        .context("index has no indexed blocks")?
+        .n(),
+    };
+
+    let checksum = index.block_checksum(height)?.with_context(|| {
+      if index.is_pruned(height).unwrap_or_default() {
+        format!("block {height} has been pruned")
+      } else if index.block_hash(Some(height)).ok().flatten().is_some() {
+        format!("block {height} was indexed before the index was migrated to include checksums")
+      } else {
+        format!("block {height} has not been indexed")
+      }
+    })?;
+
+    Ok(Some(Box::new(Output { checksum, height })))
+  }
+}
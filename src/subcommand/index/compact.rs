@@ -0,0 +1,27 @@
+use super::*;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Output {
+  pub before: u64,
+  pub after: u64,
+}
+
+pub(crate) fn run(settings: Settings) -> SubcommandResult {
+  let index = Index::open(&settings)?;
+
+  let before = fs::metadata(settings.index())?.len();
+
+  if let Some(height) = index.compact()? {
+    eprintln!(
+      "warning: reorg savepoints were deleted to compact the index, and a new savepoint was \
+      created at height {height}. Reorgs of blocks below height {height} cannot be rolled back \
+      and require rebuilding the index."
+    );
+  }
+
+  drop(index);
+
+  let after = fs::metadata(settings.index())?.len();
+
+  Ok(Some(Box::new(Output { before, after })))
+}
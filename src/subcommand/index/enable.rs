@@ -0,0 +1,17 @@
+use {super::*, crate::index::Feature};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Enable {
+  #[arg(help = "Backfill and enable the <FEATURE> index")]
+  feature: Feature,
+}
+
+impl Enable {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    index.backfill(self.feature)?;
+
+    Ok(None)
+  }
+}
@@ -2,10 +2,24 @@ use super::*;
 
 #[derive(Debug, Parser)]
 pub(crate) struct Info {
+  #[arg(
+    long,
+    conflicts_with = "transactions",
+    help = "Print size of each table."
+  )]
+  tables: bool,
   #[arg(long)]
   transactions: bool,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TableOutput {
+  pub entries: u64,
+  pub fragmented_bytes: u64,
+  pub stored_bytes: u64,
+  pub total_bytes: u64,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TransactionsOutput {
   pub start: u32,
@@ -20,7 +34,25 @@ impl Info {
 
     let info = index.info()?;
 
-    if self.transactions {
+    if self.tables {
+      Ok(Some(Box::new(
+        info
+          .tables
+          .into_iter()
+          .map(|(name, table)| {
+            (
+              name,
+              TableOutput {
+                entries: table.entries,
+                fragmented_bytes: table.fragmented_bytes,
+                stored_bytes: table.stored_bytes,
+                total_bytes: table.total_bytes,
+              },
+            )
+          })
+          .collect::<BTreeMap<String, TableOutput>>(),
+      )))
+    } else if self.transactions {
       let mut output = Vec::new();
       for window in info.transactions.windows(2) {
         let start = &window[0];
@@ -1,9 +1,24 @@
 use super::*;
 
-pub(crate) fn run(settings: Settings) -> SubcommandResult {
-  let index = Index::open(&settings)?;
+#[derive(Debug, Parser)]
+pub(crate) struct Update {
+  #[arg(
+    long,
+    help = "Publish a snapshot of the index for `ord server --read-only` after updating."
+  )]
+  snapshot: bool,
+}
+
+impl Update {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    index.update()?;
 
-  index.update()?;
+    if self.snapshot {
+      index.publish_snapshot()?;
+    }
 
-  Ok(None)
+    Ok(None)
+  }
 }
@@ -3,8 +3,11 @@ use {
     accept_encoding::AcceptEncoding,
     accept_json::AcceptJson,
     error::{OptionExt, ServerError, ServerResult},
+    thumbnail::{Thumbnail, ThumbnailFormat},
+    zmq::Notification,
   },
   super::*,
+  crate::index::mempool::Mempool,
   crate::templates::{
     AddressHtml, BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionsHtml, HomeHtml,
     InputHtml, InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml, OutputHtml, PageContent,
@@ -16,12 +19,13 @@ use {
     body,
     extract::{DefaultBodyLimit, Extension, Json, Path, Query},
     http::{header, HeaderValue, StatusCode, Uri},
+    middleware::{self, Next},
     response::{IntoResponse, Redirect, Response},
     routing::{get, post},
     Router,
   },
   axum_server::Handle,
-  brotli::Decompressor,
+  hyper::service::Service,
   rust_embed::RustEmbed,
   rustls_acme::{
     acme::{LETS_ENCRYPT_PRODUCTION_DIRECTORY, LETS_ENCRYPT_STAGING_DIRECTORY},
@@ -29,7 +33,10 @@ use {
     caches::DirCache,
     AcmeConfig,
   },
-  std::{str, sync::Arc},
+  std::{
+    iter, str,
+    sync::{mpsc, Arc},
+  },
   tokio_stream::StreamExt,
   tower_http::{
     compression::CompressionLayer,
@@ -46,6 +53,8 @@ mod accept_json;
 mod error;
 pub mod query;
 mod server_config;
+mod thumbnail;
+mod zmq;
 
 enum SpawnConfig {
   Https(AxumAcceptor),
@@ -97,9 +106,15 @@ pub struct Server {
   pub(crate) csp_origin: Option<String>,
   #[arg(
     long,
-    help = "Decompress encoded content. Currently only supports brotli. Be careful using this on production instances. A decompressed inscription may be arbitrarily large, making decompression a DoS vector."
+    help = "Decompress brotli, gzip, and zstd encoded content for clients which do not accept it. Decompressed content is limited by --decompress-limit, and content which exceeds the limit is refused with 406 Not Acceptable."
   )]
   pub(crate) decompress: bool,
+  #[arg(
+    long,
+    value_name = "BYTES",
+    help = "Refuse to decompress content which would be larger than <BYTES> when decompressed. [default: 67108864]"
+  )]
+  pub(crate) decompress_limit: Option<usize>,
   #[arg(long, help = "Disable JSON API.")]
   pub(crate) disable_json_api: bool,
   #[arg(
@@ -123,6 +138,11 @@ pub struct Server {
   pub(crate) https: bool,
   #[arg(long, help = "Redirect HTTP traffic to HTTPS.")]
   pub(crate) redirect_http_to_https: bool,
+  #[arg(
+    long,
+    help = "Track inscriptions and rune transfers in unconfirmed mempool transactions."
+  )]
+  pub(crate) mempool: bool,
   #[arg(long, alias = "nosync", help = "Do not update the index.")]
   pub(crate) no_sync: bool,
   #[arg(
@@ -132,37 +152,65 @@ pub struct Server {
   pub(crate) proxy: Option<Url>,
   #[arg(
     long,
-    default_value = "5s",
-    help = "Poll Bitcoin Core every <POLLING_INTERVAL>."
+    conflicts_with = "no_sync",
+    help = "Serve snapshots of the index published by a separate `ord index update --snapshot` process instead of updating the index, so that several servers can share one indexer."
+  )]
+  pub(crate) read_only: bool,
+  #[arg(
+    long,
+    help = "Poll Bitcoin Core every <POLLING_INTERVAL>. [default: 5s]"
+  )]
+  pub(crate) polling_interval: Option<humantime::Duration>,
+  #[arg(
+    long,
+    value_name = "ENDPOINT",
+    help = "Update the index as soon as Bitcoin Core publishes a block to its `zmqpubhashblock` <ENDPOINT>, e.g. `tcp://127.0.0.1:28332`. Polling continues while disconnected."
+  )]
+  pub(crate) zmq_hashblock: Option<String>,
+  #[arg(
+    long,
+    requires = "mempool",
+    value_name = "ENDPOINT",
+    help = "Update the mempool as soon as Bitcoin Core publishes a transaction to its `zmqpubrawtx` <ENDPOINT>. Requires `--mempool`."
   )]
-  pub(crate) polling_interval: humantime::Duration,
+  pub(crate) zmq_rawtx: Option<String>,
 }
 
 impl Server {
+  const DEFAULT_DECOMPRESS_LIMIT: usize = 64 * 1024 * 1024;
+
   pub fn run(self, settings: Settings, index: Arc<Index>, handle: Handle) -> SubcommandResult {
     Runtime::new()?.block_on(async {
-      let index_clone = index.clone();
       let integration_test = settings.integration_test();
 
-      let index_thread = thread::spawn(move || loop {
-        if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
-          break;
-        }
-
-        if !self.no_sync {
-          if let Err(error) = index_clone.update() {
-            log::warn!("Updating index: {error}");
-          }
-        }
+      let mempool = self.mempool.then(|| Arc::new(Mempool::default()));
 
-        thread::sleep(if integration_test {
+      let polling_interval = self
+        .polling_interval
+        .map(Into::into)
+        .unwrap_or(if integration_test {
           Duration::from_millis(100)
         } else {
-          self.polling_interval.into()
+          Duration::from_secs(5)
         });
-      });
 
-      INDEXER.lock().unwrap().replace(index_thread);
+      let (sender, receiver) = mpsc::channel();
+
+      for (endpoint, notification) in [
+        (&self.zmq_hashblock, Notification::Block),
+        (&self.zmq_rawtx, Notification::Transaction),
+      ] {
+        if let Some(endpoint) = endpoint {
+          zmq::subscribe(endpoint, notification, sender.clone())?;
+        }
+      }
+
+      INDEXER.lock().unwrap().push(self.spawn_indexer(
+        index.clone(),
+        mempool.clone(),
+        (sender, receiver),
+        polling_interval,
+      ));
 
       let settings = Arc::new(settings);
       let acme_domains = self.acme_domains()?;
@@ -171,125 +219,88 @@ impl Server {
         chain: settings.chain(),
         csp_origin: self.csp_origin.clone(),
         decompress: self.decompress,
+        decompress_limit: Some(
+          self
+            .decompress_limit
+            .unwrap_or(Self::DEFAULT_DECOMPRESS_LIMIT),
+        ),
         domain: acme_domains.first().cloned(),
         index_sats: index.has_sat_index(),
         json_api_enabled: !self.disable_json_api,
+        media_types: settings.media_types().to_vec(),
         proxy: self.proxy.clone(),
+        thumbnail_cache: Some(Self::thumbnail_cache(&settings)?),
       });
 
-      let router = Router::new()
-        .route("/", get(Self::home))
-        .route("/address/:address", get(Self::address))
-        .route("/block/:query", get(Self::block))
-        .route("/blockcount", get(Self::block_count))
-        .route("/blockhash", get(Self::block_hash))
-        .route("/blockhash/:height", get(Self::block_hash_from_height))
-        .route("/blockheight", get(Self::block_height))
-        .route("/blocks", get(Self::blocks))
-        .route("/blocktime", get(Self::block_time))
-        .route("/bounties", get(Self::bounties))
-        .route("/children/:inscription_id", get(Self::children))
-        .route(
-          "/children/:inscription_id/:page",
-          get(Self::children_paginated),
-        )
-        .route("/clock", get(Self::clock))
-        .route("/collections", get(Self::collections))
-        .route("/collections/:page", get(Self::collections_paginated))
-        .route("/content/:inscription_id", get(Self::content))
-        .route("/faq", get(Self::faq))
-        .route("/favicon.ico", get(Self::favicon))
-        .route("/feed.xml", get(Self::feed))
-        .route("/input/:block/:transaction/:input", get(Self::input))
-        .route("/inscription/:inscription_query", get(Self::inscription))
-        .route(
-          "/inscription/:inscription_query/:child",
-          get(Self::inscription_child),
-        )
-        .route("/inscriptions", get(Self::inscriptions))
-        .route("/inscriptions", post(Self::inscriptions_json))
-        .route("/inscriptions/:page", get(Self::inscriptions_paginated))
-        .route(
-          "/inscriptions/block/:height",
-          get(Self::inscriptions_in_block),
-        )
-        .route(
-          "/inscriptions/block/:height/:page",
-          get(Self::inscriptions_in_block_paginated),
-        )
-        .route("/install.sh", get(Self::install_script))
-        .route("/ordinal/:sat", get(Self::ordinal))
-        .route("/output/:output", get(Self::output))
-        .route("/outputs", post(Self::outputs))
-        .route("/outputs/:address", get(Self::outputs_address))
-        .route("/parents/:inscription_id", get(Self::parents))
-        .route(
-          "/parents/:inscription_id/:page",
-          get(Self::parents_paginated),
-        )
-        .route("/preview/:inscription_id", get(Self::preview))
-        .route("/r/blockhash", get(Self::block_hash_json))
-        .route(
-          "/r/blockhash/:height",
-          get(Self::block_hash_from_height_json),
-        )
-        .route("/r/blockheight", get(Self::block_height))
-        .route("/r/blocktime", get(Self::block_time))
-        .route("/r/blockinfo/:query", get(Self::block_info))
-        .route(
-          "/r/inscription/:inscription_id",
-          get(Self::inscription_recursive),
-        )
-        .route("/r/children/:inscription_id", get(Self::children_recursive))
-        .route(
-          "/r/children/:inscription_id/:page",
-          get(Self::children_recursive_paginated),
-        )
-        .route(
-          "/r/children/:inscription_id/inscriptions",
-          get(Self::child_inscriptions_recursive),
-        )
-        .route(
-          "/r/children/:inscription_id/inscriptions/:page",
-          get(Self::child_inscriptions_recursive_paginated),
-        )
-        .route(
-          "/r/undelegated-content/:inscription_id",
-          get(Self::undelegated_content),
-        )
-        .route("/r/metadata/:inscription_id", get(Self::metadata))
-        .route("/r/parents/:inscription_id", get(Self::parents_recursive))
-        .route(
-          "/r/parents/:inscription_id/:page",
-          get(Self::parents_recursive_paginated),
-        )
-        .route("/r/sat/:sat_number", get(Self::sat_inscriptions))
-        .route(
-          "/r/sat/:sat_number/:page",
-          get(Self::sat_inscriptions_paginated),
-        )
-        .route(
-          "/r/sat/:sat_number/at/:index",
-          get(Self::sat_inscription_at_index),
-        )
-        .route("/r/utxo/:outpoint", get(Self::utxo_recursive))
-        .route("/rare.txt", get(Self::rare_txt))
-        .route("/rune/:rune", get(Self::rune))
-        .route("/runes", get(Self::runes))
-        .route("/runes/:page", get(Self::runes_paginated))
-        .route("/sat/:sat", get(Self::sat))
-        .route("/satpoint/:satpoint", get(Self::satpoint))
-        .route("/search", get(Self::search_by_query))
-        .route("/search/*query", get(Self::search_by_path))
-        .route("/static/*path", get(Self::static_asset))
-        .route("/status", get(Self::status))
-        .route("/tx/:txid", get(Self::transaction))
-        .route("/decode/:txid", get(Self::decode))
-        .route("/update", get(Self::update))
-        .fallback(Self::fallback)
-        .layer(Extension(index))
-        .layer(Extension(server_config.clone()))
-        .layer(Extension(settings.clone()))
+      let mut hosts = BTreeMap::new();
+
+      for chain_settings in settings.chains() {
+        let index = Arc::new(if self.read_only {
+          Index::open_read_only(chain_settings)?
+        } else {
+          Index::open(chain_settings)?
+        });
+
+        let mempool = self.mempool.then(|| Arc::new(Mempool::default()));
+
+        // `--zmq-hashblock` and `--zmq-rawtx` are the primary chain's node's
+        // endpoints, and additional chains cannot be configured with their own,
+        // so additional chains are polled
+        INDEXER.lock().unwrap().push(self.spawn_indexer(
+          index.clone(),
+          mempool.clone(),
+          mpsc::channel(),
+          polling_interval,
+        ));
+
+        let hostname = chain_settings.hostname().unwrap();
+
+        let chain_config = Arc::new(ServerConfig {
+          chain: chain_settings.chain(),
+          domain: Some(hostname.into()),
+          index_sats: index.has_sat_index(),
+          media_types: chain_settings.media_types().to_vec(),
+          thumbnail_cache: Some(Self::thumbnail_cache(chain_settings)?),
+          ..(*server_config).clone()
+        });
+
+        hosts.insert(
+          hostname.to_owned(),
+          Self::router(
+            chain_config,
+            index,
+            mempool,
+            Arc::new(chain_settings.clone()),
+          ),
+        );
+      }
+
+      let router = Self::router(server_config.clone(), index, mempool, settings.clone());
+
+      let router = if hosts.is_empty() {
+        router
+      } else {
+        // the primary chain's router is a fallback, so that requests are
+        // dispatched by hostname before any routes are matched
+        Router::new()
+          .fallback_service(router)
+          .layer(middleware::from_fn(
+            move |request: http::Request<body::Body>, next: Next<body::Body>| {
+              let router = Self::host_router(&hosts, &request);
+              async move {
+                match router {
+                  Some(mut router) => match router.call(request).await {
+                    Ok(response) => response,
+                    Err(never) => match never {},
+                  },
+                  None => next.run(request).await,
+                }
+              }
+            },
+          ))
+      };
+
+      let router = router
         .layer(SetResponseHeaderLayer::if_not_present(
           header::CONTENT_SECURITY_POLICY,
           HeaderValue::from_static("default-src 'self'"),
@@ -301,10 +312,15 @@ impl Server {
         .layer(
           CorsLayer::new()
             .allow_methods([http::Method::GET])
-            .allow_origin(Any),
+            .allow_origin(Any)
+            .vary([
+              header::ORIGIN,
+              header::ACCESS_CONTROL_REQUEST_METHOD,
+              header::ACCESS_CONTROL_REQUEST_HEADERS,
+              header::ACCEPT,
+            ]),
         )
-        .layer(CompressionLayer::new())
-        .with_state(server_config.clone());
+        .layer(CompressionLayer::new());
 
       let router = if server_config.json_api_enabled {
         router.layer(DefaultBodyLimit::disable())
@@ -371,6 +387,196 @@ impl Server {
     })
   }
 
+  fn spawn_indexer(
+    &self,
+    index: Arc<Index>,
+    mempool: Option<Arc<Mempool>>,
+    (sender, receiver): (mpsc::Sender<Notification>, mpsc::Receiver<Notification>),
+    polling_interval: Duration,
+  ) -> thread::JoinHandle<()> {
+    let no_sync = self.no_sync;
+
+    thread::spawn(move || {
+      // keep the channel open so `recv_timeout` waits even without subscribers
+      let _sender = sender;
+
+      let mut update_index = true;
+
+      loop {
+        if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+          break;
+        }
+
+        if update_index && !no_sync {
+          if let Err(error) = index.update() {
+            log::warn!("Updating index: {error}");
+          }
+        }
+
+        if let Some(mempool) = &mempool {
+          if let Err(error) = mempool.update(&index) {
+            log::warn!("Updating mempool: {error}");
+          }
+        }
+
+        // transaction notifications only require a mempool update
+        update_index = match receiver.recv_timeout(polling_interval) {
+          Ok(notification) => iter::once(notification)
+            .chain(receiver.try_iter())
+            .any(|notification| notification == Notification::Block),
+          Err(_) => true,
+        };
+      }
+    })
+  }
+
+  fn thumbnail_cache(settings: &Settings) -> Result<PathBuf> {
+    let thumbnail_cache = settings.data_dir().join("thumbnails");
+
+    fs::create_dir_all(&thumbnail_cache).with_context(|| {
+      format!(
+        "failed to create thumbnail cache directory {}",
+        thumbnail_cache.display()
+      )
+    })?;
+
+    Ok(thumbnail_cache)
+  }
+
+  fn router(
+    server_config: Arc<ServerConfig>,
+    index: Arc<Index>,
+    mempool: Option<Arc<Mempool>>,
+    settings: Arc<Settings>,
+  ) -> Router {
+    Router::new()
+      .route("/", get(Self::home))
+      .route("/address/:address", get(Self::address))
+      .route("/block/:query", get(Self::block))
+      .route("/blockcount", get(Self::block_count))
+      .route("/blockhash", get(Self::block_hash))
+      .route("/blockhash/:height", get(Self::block_hash_from_height))
+      .route("/blockheight", get(Self::block_height))
+      .route("/blocks", get(Self::blocks))
+      .route("/blocktime", get(Self::block_time))
+      .route("/bounties", get(Self::bounties))
+      .route("/children/:inscription_id", get(Self::children))
+      .route(
+        "/children/:inscription_id/:page",
+        get(Self::children_paginated),
+      )
+      .route("/clock", get(Self::clock))
+      .route("/collections", get(Self::collections))
+      .route("/collections/:page", get(Self::collections_paginated))
+      .route("/content/:inscription_id", get(Self::content))
+      .route("/thumbnail/:inscription_id", get(Self::thumbnail))
+      .route("/faq", get(Self::faq))
+      .route("/favicon.ico", get(Self::favicon))
+      .route("/feed.xml", get(Self::feed))
+      .route("/input/:block/:transaction/:input", get(Self::input))
+      .route("/inscription/:inscription_query", get(Self::inscription))
+      .route(
+        "/inscription/:inscription_query/:child",
+        get(Self::inscription_child),
+      )
+      .route("/inscriptions", get(Self::inscriptions))
+      .route("/inscriptions", post(Self::inscriptions_json))
+      .route("/inscriptions/:page", get(Self::inscriptions_paginated))
+      .route(
+        "/inscriptions/block/:height",
+        get(Self::inscriptions_in_block),
+      )
+      .route(
+        "/inscriptions/block/:height/:page",
+        get(Self::inscriptions_in_block_paginated),
+      )
+      .route("/install.sh", get(Self::install_script))
+      .route("/ordinal/:sat", get(Self::ordinal))
+      .route("/mempool/inscriptions", get(Self::mempool_inscriptions))
+      .route("/mempool/runes", get(Self::mempool_runes))
+      .route("/output/:output", get(Self::output))
+      .route("/outputs", post(Self::outputs))
+      .route("/outputs/:address", get(Self::outputs_address))
+      .route("/parents/:inscription_id", get(Self::parents))
+      .route(
+        "/parents/:inscription_id/:page",
+        get(Self::parents_paginated),
+      )
+      .route("/preview/:inscription_id", get(Self::preview))
+      .route(
+        "/preview/:inscription_id/thumbnail",
+        get(Self::preview_thumbnail),
+      )
+      .route("/r/blockhash", get(Self::block_hash_json))
+      .route(
+        "/r/blockhash/:height",
+        get(Self::block_hash_from_height_json),
+      )
+      .route("/r/blockheight", get(Self::block_height))
+      .route("/r/blocktime", get(Self::block_time))
+      .route("/r/blockinfo/:query", get(Self::block_info))
+      .route(
+        "/r/inscription/:inscription_id",
+        get(Self::inscription_recursive),
+      )
+      .route(
+        "/r/inscription/:inscription_id/properties",
+        get(Self::inscription_properties_recursive),
+      )
+      .route("/r/children/:inscription_id", get(Self::children_recursive))
+      .route(
+        "/r/children/:inscription_id/:page",
+        get(Self::children_recursive_paginated),
+      )
+      .route(
+        "/r/children/:inscription_id/inscriptions",
+        get(Self::child_inscriptions_recursive),
+      )
+      .route(
+        "/r/children/:inscription_id/inscriptions/:page",
+        get(Self::child_inscriptions_recursive_paginated),
+      )
+      .route(
+        "/r/undelegated-content/:inscription_id",
+        get(Self::undelegated_content),
+      )
+      .route("/r/metadata/:inscription_id", get(Self::metadata))
+      .route("/r/parents/:inscription_id", get(Self::parents_recursive))
+      .route(
+        "/r/parents/:inscription_id/:page",
+        get(Self::parents_recursive_paginated),
+      )
+      .route("/r/sat/:sat_number", get(Self::sat_inscriptions))
+      .route(
+        "/r/sat/:sat_number/:page",
+        get(Self::sat_inscriptions_paginated),
+      )
+      .route(
+        "/r/sat/:sat_number/at/:index",
+        get(Self::sat_inscription_at_index),
+      )
+      .route("/r/utxo/:outpoint", get(Self::utxo_recursive))
+      .route("/rare.txt", get(Self::rare_txt))
+      .route("/rune/:rune", get(Self::rune))
+      .route("/runes", get(Self::runes))
+      .route("/runes/:page", get(Self::runes_paginated))
+      .route("/sat/:sat", get(Self::sat))
+      .route("/satpoint/:satpoint", get(Self::satpoint))
+      .route("/search", get(Self::search_by_query))
+      .route("/search/*query", get(Self::search_by_path))
+      .route("/static/*path", get(Self::static_asset))
+      .route("/status", get(Self::status))
+      .route("/tx/:txid", get(Self::transaction))
+      .route("/decode/:txid", get(Self::decode))
+      .route("/update", get(Self::update))
+      .fallback(Self::fallback)
+      .layer(Extension(index))
+      .layer(Extension(mempool))
+      .layer(Extension(server_config.clone()))
+      .layer(Extension(settings))
+      .with_state(server_config)
+  }
+
   fn spawn(
     &self,
     settings: &Settings,
@@ -442,6 +648,25 @@ impl Server {
     }
   }
 
+  /// Router for an additional chain served at the request's hostname. All
+  /// other requests are served by the primary chain's router.
+  fn host_router(
+    hosts: &BTreeMap<String, Router>,
+    request: &http::Request<body::Body>,
+  ) -> Option<Router> {
+    let host = request.uri().host().or_else(|| {
+      request
+        .headers()
+        .get(header::HOST)?
+        .to_str()
+        .ok()?
+        .split(':')
+        .next()
+    })?;
+
+    hosts.get(host).cloned()
+  }
+
   fn acme_domains(&self) -> Result<Vec<String>> {
     if !self.acme_domain.is_empty() {
       Ok(self.acme_domain.clone())
@@ -507,6 +732,14 @@ impl Server {
     index.block_height()?.ok_or_not_found(|| "genesis block")
   }
 
+  fn check_pruned(index: &Index, height: u32) -> ServerResult<()> {
+    if index.is_pruned(height)? {
+      Err(ServerError::Gone(format!("block {height} has been pruned")))
+    } else {
+      Ok(())
+    }
+  }
+
   async fn clock(Extension(index): Extension<Arc<Index>>) -> ServerResult {
     task::block_in_place(|| {
       Ok(
@@ -631,16 +864,45 @@ impl Server {
     Redirect::to(&format!("/sat/{sat}"))
   }
 
+  async fn mempool_inscriptions(
+    Extension(mempool): Extension<Option<Arc<Mempool>>>,
+  ) -> ServerResult {
+    let mempool = mempool
+      .ok_or_else(|| ServerError::NotFound("this server does not track the mempool".to_string()))?;
+
+    Ok(Json(mempool.inscriptions()).into_response())
+  }
+
+  async fn mempool_runes(Extension(mempool): Extension<Option<Arc<Mempool>>>) -> ServerResult {
+    let mempool = mempool
+      .ok_or_else(|| ServerError::NotFound("this server does not track the mempool".to_string()))?;
+
+    Ok(Json(mempool.runes()).into_response())
+  }
+
+  fn output_info(
+    index: &Index,
+    mempool: Option<&Mempool>,
+    outpoint: OutPoint,
+  ) -> ServerResult<(api::Output, TxOut)> {
+    let (mut output_info, txout) = index
+      .get_output_info(outpoint)?
+      .ok_or_not_found(|| format!("output {outpoint}"))?;
+
+    output_info.pending_spend = mempool.and_then(|mempool| mempool.pending_spend(outpoint));
+
+    Ok((output_info, txout))
+  }
+
   async fn output(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(mempool): Extension<Option<Arc<Mempool>>>,
     Path(outpoint): Path<OutPoint>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (output_info, txout) = index
-        .get_output_info(outpoint)?
-        .ok_or_not_found(|| format!("output {outpoint}"))?;
+      let (output_info, txout) = Self::output_info(&index, mempool.as_deref(), outpoint)?;
 
       Ok(if accept_json {
         Json(output_info).into_response()
@@ -708,6 +970,7 @@ impl Server {
 
   async fn outputs(
     Extension(index): Extension<Arc<Index>>,
+    Extension(mempool): Extension<Option<Arc<Mempool>>>,
     AcceptJson(accept_json): AcceptJson,
     Json(outputs): Json<Vec<OutPoint>>,
   ) -> ServerResult {
@@ -715,9 +978,7 @@ impl Server {
       Ok(if accept_json {
         let mut response = Vec::new();
         for outpoint in outputs {
-          let (output_info, _) = index
-            .get_output_info(outpoint)?
-            .ok_or_not_found(|| format!("output {outpoint}"))?;
+          let (output_info, _) = Self::output_info(&index, mempool.as_deref(), outpoint)?;
 
           response.push(output_info);
         }
@@ -731,6 +992,7 @@ impl Server {
   async fn outputs_address(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(mempool): Extension<Option<Arc<Mempool>>>,
     AcceptJson(accept_json): AcceptJson,
     Path(address): Path<Address<NetworkUnchecked>>,
     Query(query): Query<OutputsQuery>,
@@ -793,9 +1055,7 @@ impl Server {
         };
 
         if include {
-          let (output_info, _) = index
-            .get_output_info(output)?
-            .ok_or_not_found(|| format!("output {output}"))?;
+          let (output_info, _) = Self::output_info(&index, mempool.as_deref(), output)?;
 
           response.push(output_info);
         }
@@ -1041,6 +1301,8 @@ impl Server {
         }
       };
 
+      Self::check_pruned(&index, height)?;
+
       let runes = index.get_runes_in_block(u64::from(height))?;
       Ok(if accept_json {
         let inscriptions = index.get_inscriptions_in_block(height)?;
@@ -1243,6 +1505,34 @@ impl Server {
     })
   }
 
+  async fn inscription_properties_recursive(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.inscription_exists(inscription_id)? {
+        return if let Some(proxy) = server_config.proxy.as_ref() {
+          Self::proxy(
+            proxy,
+            &format!("r/inscription/{}/properties", inscription_id),
+          )
+        } else {
+          Err(ServerError::NotFound(format!(
+            "inscription {} not found",
+            inscription_id
+          )))
+        };
+      }
+
+      let properties = index
+        .get_inscription_properties(inscription_id)?
+        .ok_or_not_found(|| format!("inscription {inscription_id} properties"))?;
+
+      Ok(Json(properties).into_response())
+    })
+  }
+
   async fn status(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1423,6 +1713,8 @@ impl Server {
     Path(height): Path<u32>,
   ) -> ServerResult<String> {
     task::block_in_place(|| {
+      Self::check_pruned(&index, height)?;
+
       Ok(
         index
           .block_hash(Some(height))?
@@ -1437,6 +1729,8 @@ impl Server {
     Path(height): Path<u32>,
   ) -> ServerResult<Json<String>> {
     task::block_in_place(|| {
+      Self::check_pruned(&index, height)?;
+
       Ok(Json(
         index
           .block_hash(Some(height))?
@@ -1453,9 +1747,13 @@ impl Server {
     task::block_in_place(|| {
       let hash = match query {
         query::Block::Hash(hash) => hash,
-        query::Block::Height(height) => index
-          .block_hash(Some(height))?
-          .ok_or_not_found(|| format!("block {height}"))?,
+        query::Block::Height(height) => {
+          Self::check_pruned(&index, height)?;
+
+          index
+            .block_hash(Some(height))?
+            .ok_or_not_found(|| format!("block {height}"))?
+        }
       };
 
       let header = index
@@ -1475,6 +1773,7 @@ impl Server {
         average_fee_rate: stats.avg_fee_rate.to_sat(),
         bits: header.bits.to_consensus(),
         chainwork: info.chainwork.try_into().unwrap(),
+        checksum: index.block_checksum(info.height.try_into().unwrap())?,
         confirmations: info.confirmations,
         difficulty: info.difficulty,
         hash,
@@ -1646,6 +1945,113 @@ impl Server {
     })
   }
 
+  async fn thumbnail(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(settings): Extension<Arc<Settings>>,
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Path(inscription_id): Path<InscriptionId>,
+    headers: HeaderMap,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if settings.is_hidden(inscription_id) {
+        return Ok(PreviewUnknownHtml.into_response());
+      }
+
+      let format = ThumbnailFormat::negotiate(&headers);
+
+      let path = server_config
+        .thumbnail_cache
+        .as_ref()
+        .map(|cache| cache.join(format!("{inscription_id}.{}", format.extension())));
+
+      // an empty thumbnail means that the content is its own thumbnail
+      let thumbnail = if let Some(thumbnail) = path.as_ref().and_then(|path| fs::read(path).ok()) {
+        thumbnail
+      } else {
+        let Some(mut inscription) = index.get_inscription_by_id(inscription_id)? else {
+          return if let Some(proxy) = server_config.proxy.as_ref() {
+            Self::proxy(proxy, &format!("thumbnail/{}", inscription_id))
+          } else {
+            Err(ServerError::NotFound(format!(
+              "inscription {} not found",
+              inscription_id
+            )))
+          };
+        };
+
+        if let Some(delegate) = inscription.delegate() {
+          inscription = index
+            .get_inscription_by_id(delegate)?
+            .ok_or_not_found(|| format!("delegate {inscription_id}"))?
+        }
+
+        let Media::Image(image_rendering) = server_config.media(&inscription) else {
+          return Err(ServerError::NotFound(format!(
+            "inscription {inscription_id} thumbnail not found: content is not an image"
+          )));
+        };
+
+        let content_encoding = inscription.content_encoding();
+
+        let body = inscription
+          .into_body()
+          .ok_or_not_found(|| format!("inscription {inscription_id} content"))?;
+
+        let body = match content_encoding {
+          None => Some(body),
+          Some(content_encoding) => content_encoding
+            .to_str()
+            .ok()
+            .and_then(Compression::from_content_encoding)
+            .and_then(|compression| {
+              compression
+                .decompress(
+                  &body,
+                  server_config
+                    .decompress_limit
+                    .unwrap_or(Self::DEFAULT_DECOMPRESS_LIMIT),
+                )
+                .ok()
+            }),
+        };
+
+        let thumbnail = match body {
+          Some(body) => Thumbnail::render(&body, image_rendering, format)?.unwrap_or_default(),
+          None => Vec::new(),
+        };
+
+        if let Some(path) = &path {
+          if let Err(err) = Thumbnail::store(path, &thumbnail) {
+            log::warn!("failed to cache thumbnail {}: {err}", path.display());
+          }
+        }
+
+        thumbnail
+      };
+
+      if thumbnail.is_empty() {
+        return Ok(Redirect::to(&format!("/content/{inscription_id}")).into_response());
+      }
+
+      Ok(
+        (
+          [
+            (
+              header::CONTENT_TYPE,
+              HeaderValue::from_static(format.content_type()),
+            ),
+            (
+              header::CACHE_CONTROL,
+              HeaderValue::from_static("public, max-age=1209600, immutable"),
+            ),
+          ],
+          thumbnail,
+        )
+          .into_response(),
+      )
+    })
+  }
+
   fn content_response(
     inscription: Inscription,
     accept_encoding: AcceptEncoding,
@@ -1689,16 +2095,23 @@ impl Server {
     if let Some(content_encoding) = inscription.content_encoding() {
       if accept_encoding.is_acceptable(&content_encoding) {
         headers.insert(header::CONTENT_ENCODING, content_encoding);
-      } else if server_config.decompress && content_encoding == "br" {
+      } else if let Some(compression) = server_config
+        .decompress
+        .then(|| Compression::from_content_encoding(content_encoding.to_str().ok()?))
+        .flatten()
+      {
         let Some(body) = inscription.into_body() else {
           return Ok(None);
         };
 
-        let mut decompressed = Vec::new();
-
-        Decompressor::new(body.as_slice(), 4096)
-          .read_to_end(&mut decompressed)
-          .map_err(|err| ServerError::Internal(err.into()))?;
+        let Ok(decompressed) =
+          compression.decompress(&body, server_config.decompress_limit.unwrap_or(usize::MAX))
+        else {
+          return Err(ServerError::NotAcceptable {
+            accept_encoding,
+            content_encoding,
+          });
+        };
 
         return Ok(Some((headers, decompressed)));
       } else {
@@ -1722,6 +2135,41 @@ impl Server {
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Path(inscription_id): Path<InscriptionId>,
     accept_encoding: AcceptEncoding,
+  ) -> ServerResult {
+    Self::preview_inner(
+      &index,
+      &settings,
+      &server_config,
+      inscription_id,
+      accept_encoding,
+      false,
+    )
+  }
+
+  async fn preview_thumbnail(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(settings): Extension<Arc<Settings>>,
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Path(inscription_id): Path<InscriptionId>,
+    accept_encoding: AcceptEncoding,
+  ) -> ServerResult {
+    Self::preview_inner(
+      &index,
+      &settings,
+      &server_config,
+      inscription_id,
+      accept_encoding,
+      true,
+    )
+  }
+
+  fn preview_inner(
+    index: &Index,
+    settings: &Settings,
+    server_config: &ServerConfig,
+    inscription_id: InscriptionId,
+    accept_encoding: AcceptEncoding,
+    thumbnail: bool,
   ) -> ServerResult {
     task::block_in_place(|| {
       if settings.is_hidden(inscription_id) {
@@ -1738,17 +2186,17 @@ impl Server {
           .ok_or_not_found(|| format!("delegate {inscription_id}"))?
       }
 
-      let media = inscription.media();
+      let media = server_config.media(&inscription);
 
       if let Media::Iframe = media {
         return Ok(
-          Self::content_response(inscription, accept_encoding, &server_config)?
+          Self::content_response(inscription, accept_encoding, server_config)?
             .ok_or_not_found(|| format!("inscription {inscription_id} content"))?
             .into_response(),
         );
       }
 
-      let content_security_policy = server_config.preview_content_security_policy(media)?;
+      let content_security_policy = server_config.preview_content_security_policy(&inscription)?;
 
       match media {
         Media::Audio => {
@@ -1774,6 +2222,7 @@ impl Server {
             PreviewImageHtml {
               image_rendering,
               inscription_id,
+              thumbnail,
             },
           )
             .into_response(),
@@ -1805,24 +2254,58 @@ impl Server {
   async fn inscription(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(mempool): Extension<Option<Arc<Mempool>>>,
     AcceptJson(accept_json): AcceptJson,
     Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Inscription>>,
   ) -> ServerResult {
-    Self::inscription_inner(server_config, &index, accept_json, query, None).await
+    Self::inscription_inner(
+      server_config,
+      &index,
+      mempool.as_deref(),
+      accept_json,
+      query,
+      None,
+    )
+    .await
   }
 
   async fn inscription_child(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(mempool): Extension<Option<Arc<Mempool>>>,
     AcceptJson(accept_json): AcceptJson,
     Path((DeserializeFromStr(query), child)): Path<(DeserializeFromStr<query::Inscription>, usize)>,
   ) -> ServerResult {
-    Self::inscription_inner(server_config, &index, accept_json, query, Some(child)).await
+    Self::inscription_inner(
+      server_config,
+      &index,
+      mempool.as_deref(),
+      accept_json,
+      query,
+      Some(child),
+    )
+    .await
+  }
+
+  fn inscription_info(
+    index: &Index,
+    mempool: Option<&Mempool>,
+    query: query::Inscription,
+    child: Option<usize>,
+  ) -> ServerResult<(api::Inscription, Option<TxOut>, Inscription)> {
+    let (mut info, txout, inscription) = index
+      .inscription_info(query, child)?
+      .ok_or_not_found(|| format!("inscription {query}"))?;
+
+    info.pending_spend = mempool.and_then(|mempool| mempool.pending_spend(info.satpoint.outpoint));
+
+    Ok((info, txout, inscription))
   }
 
   async fn inscription_inner(
     server_config: Arc<ServerConfig>,
     index: &Index,
+    mempool: Option<&Mempool>,
     accept_json: bool,
     query: query::Inscription,
     child: Option<usize>,
@@ -1834,9 +2317,7 @@ impl Server {
         }
       }
 
-      let (info, txout, inscription) = index
-        .inscription_info(query, child)?
-        .ok_or_not_found(|| format!("inscription {query}"))?;
+      let (info, txout, inscription) = Self::inscription_info(index, mempool, query, child)?;
 
       Ok(if accept_json {
         Json(info).into_response()
@@ -1871,6 +2352,7 @@ impl Server {
 
   async fn inscriptions_json(
     Extension(index): Extension<Arc<Index>>,
+    Extension(mempool): Extension<Option<Arc<Mempool>>>,
     AcceptJson(accept_json): AcceptJson,
     Json(inscriptions): Json<Vec<InscriptionId>>,
   ) -> ServerResult {
@@ -1879,9 +2361,7 @@ impl Server {
         let mut response = Vec::new();
         for inscription in inscriptions {
           let query = query::Inscription::Id(inscription);
-          let (info, _, _) = index
-            .inscription_info(query, None)?
-            .ok_or_not_found(|| format!("inscription {query}"))?;
+          let (info, _, _) = Self::inscription_info(&index, mempool.as_deref(), query, None)?;
 
           response.push(info);
         }
@@ -2136,6 +2616,8 @@ impl Server {
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
+      Self::check_pruned(&index, block_height)?;
+
       let page_size = 100;
 
       let page_index_usize = usize::try_from(page_index).unwrap_or(usize::MAX);
@@ -2416,8 +2898,10 @@ mod tests {
       args.push("--http-port".into());
       args.push(port.to_string());
 
-      args.push("--polling-interval".into());
-      args.push("100ms".into());
+      if !self.server_args.contains_key("--polling-interval") {
+        args.push("--polling-interval".into());
+        args.push("100ms".into());
+      }
 
       for (arg, value) in self.server_args {
         args.push(arg);
@@ -2601,6 +3085,27 @@ mod tests {
       self.url.join(url).unwrap()
     }
 
+    /// Poll `path` until `condition` holds, for state which is updated in the
+    /// background, like the mempool
+    #[track_caller]
+    fn poll_json<T: DeserializeOwned>(
+      &self,
+      path: impl AsRef<str>,
+      condition: impl Fn(&T) -> bool,
+    ) -> T {
+      for _ in 0..100 {
+        let value = self.get_json(path.as_ref());
+
+        if condition(&value) {
+          return value;
+        }
+
+        thread::sleep(Duration::from_millis(50));
+      }
+
+      panic!("timed out polling {}", path.as_ref());
+    }
+
     #[track_caller]
     fn assert_response(&self, path: impl AsRef<str>, status: StatusCode, expected_response: &str) {
       let response = self.get(path);
@@ -3665,6 +4170,198 @@ mod tests {
     );
   }
 
+  #[test]
+  fn mempool_inscriptions() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--mempool")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let moving = InscriptionId { txid, index: 0 };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, inscription("text/plain", "bar").to_witness())],
+      ..default()
+    });
+
+    let created = InscriptionId { txid, index: 0 };
+
+    pretty_assert_eq!(
+      server.poll_json::<api::MempoolInscriptions>("/mempool/inscriptions", |mempool| {
+        !mempool.created.is_empty()
+      }),
+      api::MempoolInscriptions {
+        created: vec![api::PendingInscription {
+          content_length: Some(3),
+          content_type: Some("text/plain".into()),
+          id: created,
+          metaprotocol: None,
+          parents: Vec::new(),
+        }],
+        moving: [(moving, txid)].into(),
+      }
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::Inscription>(format!("/inscription/{moving}"))
+        .pending_spend,
+      Some(txid),
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::Output>(format!("/output/{}:0", moving.txid))
+        .pending_spend,
+      Some(txid),
+    );
+
+    server.mine_blocks(1);
+
+    server.poll_json::<api::MempoolInscriptions>("/mempool/inscriptions", |mempool| {
+      mempool.created.is_empty() && mempool.moving.is_empty()
+    });
+
+    assert_eq!(
+      server
+        .get_json::<api::Inscription>(format!("/inscription/{moving}"))
+        .pending_spend,
+      None,
+    );
+  }
+
+  #[test]
+  fn mempool_runes() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .server_flag("--mempool")
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    let (_, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(rune),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let runestone = Runestone {
+      edicts: vec![Edict {
+        id,
+        amount: 1000,
+        output: 0,
+      }],
+      ..default()
+    };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(id.block.try_into().unwrap(), 1, 0, Witness::new())],
+      op_return: Some(runestone.encipher()),
+      ..default()
+    });
+
+    pretty_assert_eq!(
+      server.poll_json::<api::MempoolRunes>("/mempool/runes", |mempool| {
+        !mempool.transactions.is_empty()
+      }),
+      api::MempoolRunes {
+        transactions: [(
+          txid,
+          api::MempoolRuneTransaction {
+            artifact: Some(Artifact::Runestone(runestone)),
+            spent: [(
+              SpacedRune { rune, spacers: 0 },
+              Pile {
+                amount: 1000,
+                divisibility: 0,
+                symbol: None,
+              },
+            )]
+            .into(),
+          },
+        )]
+        .into(),
+      }
+    );
+  }
+
+  #[test]
+  fn zmq_block_notifications_update_index() {
+    let mut publisher = zmq::Publisher::new();
+
+    let server = TestServer::builder()
+      .server_option("--polling-interval", "1h")
+      .server_option("--zmq-hashblock", &publisher.endpoint())
+      .build();
+
+    assert_eq!(publisher.accept(), "hashblock");
+
+    assert_eq!(server.index.block_count().unwrap(), 1);
+
+    let block = server.core.mine_blocks(1).remove(0);
+
+    publisher.publish("hashblock", block.block_hash().as_byte_array());
+
+    for _ in 0..100 {
+      if server.index.block_count().unwrap() == 2 {
+        return;
+      }
+
+      thread::sleep(Duration::from_millis(50));
+    }
+
+    panic!("index was not updated after block notification");
+  }
+
+  #[test]
+  fn zmq_rawtx_requires_mempool() {
+    assert!(
+      Arguments::try_parse_from(["ord", "server", "--zmq-rawtx", "tcp://127.0.0.1:28333",])
+        .is_err()
+    );
+  }
+
+  #[test]
+  fn mempool_endpoints_require_mempool_flag() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.assert_response(
+      "/mempool/inscriptions",
+      StatusCode::NOT_FOUND,
+      "this server does not track the mempool",
+    );
+
+    server.assert_response(
+      "/mempool/runes",
+      StatusCode::NOT_FOUND,
+      "this server does not track the mempool",
+    );
+  }
+
   #[test]
   fn runes_are_displayed_on_output_page() {
     let server = TestServer::builder()
@@ -3755,6 +4452,7 @@ mod tests {
         indexed: true,
         inscriptions: Some(Vec::new()),
         outpoint: output,
+        pending_spend: None,
         runes: Some(
           vec![(
             SpacedRune {
@@ -4142,7 +4840,7 @@ mod tests {
 <dl>
   <dt>inscriptions</dt>
   <dd class=thumbnails>
-    <a href=/inscription/.*><iframe sandbox=allow-scripts scrolling=no loading=lazy src=/preview/.*></iframe></a>
+    <a href=/inscription/.*><iframe sandbox=allow-scripts scrolling=no loading=lazy src=/preview/.*/thumbnail></iframe></a>
   </dd>.*",
     );
   }
@@ -4334,6 +5032,26 @@ mod tests {
     test_server.assert_response_regex("/block/0", StatusCode::OK, ".*<h1>Block 0</h1>.*");
   }
 
+  #[test]
+  fn pruned_block_lookups_are_gone() {
+    let test_server = TestServer::builder().ord_option("--prune", "21").build();
+
+    test_server.mine_blocks(30);
+
+    for path in [
+      "/block/9",
+      "/blockhash/9",
+      "/r/blockhash/9",
+      "/r/blockinfo/9",
+      "/inscriptions/block/9",
+    ] {
+      test_server.assert_response(path, StatusCode::GONE, "block 9 has been pruned");
+    }
+
+    test_server.assert_response_regex("/block/10", StatusCode::OK, ".*<h1>Block 10</h1>.*");
+    test_server.assert_response_regex("/blockhash/10", StatusCode::OK, "[[:xdigit:]]{64}");
+  }
+
   #[test]
   fn transaction() {
     let test_server = TestServer::new();
@@ -4733,6 +5451,61 @@ mod tests {
     }
   }
 
+  #[test]
+  fn configured_media_types_are_previewed() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .config(
+        "media_types:
+- content_type: text/csv
+  content_security_policy: default-src 'none'
+  extensions: [csv]
+  media: text
+- content_type: image/bmp
+  extensions: [bmp]
+  media: !image pixelated",
+      )
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/csv", "a,b").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.assert_response_csp(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      "default-src 'none'",
+      format!(
+        r".*<html lang=en data-inscription={inscription_id}>.*<script src=/static/preview-text.js.*"
+      ),
+    );
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("image/bmp", "BM").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.assert_response_csp(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      "default-src 'self' 'unsafe-inline'",
+      format!(r".*image-rendering: pixelated;.*<img src=/content/{inscription_id} alt='inscription {inscription_id}'>.*"),
+    );
+  }
+
   #[test]
   fn code_preview() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
@@ -4927,6 +5700,139 @@ mod tests {
     );
   }
 
+  #[test]
+  fn image_preview_thumbnail() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("image/png", "hello").to_witness())],
+      ..default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_csp(
+      format!("/preview/{inscription_id}/thumbnail"),
+      StatusCode::OK,
+      "default-src 'self' 'unsafe-inline'",
+      format!(
+        r".*background-image: url\(/thumbnail/{inscription_id}\);.*<img src=/thumbnail/{inscription_id} .*"
+      ),
+    );
+  }
+
+  #[test]
+  fn thumbnail() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let mut png = Vec::new();
+
+    image::DynamicImage::ImageRgb8(image::RgbImage::new(1024, 512))
+      .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+      .unwrap();
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("image/png", png).to_witness())],
+      ..default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    let response = server.get(format!("/thumbnail/{inscription_id}"));
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "image/png"
+    );
+    assert!(response
+      .headers()
+      .get_all(header::VARY)
+      .iter()
+      .any(|value| value == "accept"));
+
+    let thumbnail = image::load_from_memory(&response.bytes().unwrap()).unwrap();
+
+    assert_eq!((thumbnail.width(), thumbnail.height()), (256, 128));
+
+    assert!(server
+      .tempdir
+      .path()
+      .join(format!("regtest/thumbnails/{inscription_id}.png"))
+      .is_file());
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url(&format!("/thumbnail/{inscription_id}")))
+      .header(header::ACCEPT, "image/webp,*/*")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "image/webp"
+    );
+
+    image::load_from_memory_with_format(&response.bytes().unwrap(), image::ImageFormat::WebP)
+      .unwrap();
+  }
+
+  #[test]
+  fn thumbnail_redirects_to_content_if_image_is_small_or_unsupported() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(2);
+
+    let mut png = Vec::new();
+
+    image::DynamicImage::ImageRgb8(image::RgbImage::new(16, 16))
+      .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+      .unwrap();
+
+    for (i, content) in [png, b"hello".to_vec()].into_iter().enumerate() {
+      let txid = server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("image/png", content).to_witness())],
+        ..default()
+      });
+      let inscription_id = InscriptionId { txid, index: 0 };
+
+      server.mine_blocks(1);
+
+      server.assert_redirect(
+        &format!("/thumbnail/{inscription_id}"),
+        &format!("/content/{inscription_id}"),
+      );
+
+      server.assert_redirect(
+        &format!("/thumbnail/{inscription_id}"),
+        &format!("/content/{inscription_id}"),
+      );
+    }
+  }
+
+  #[test]
+  fn thumbnail_of_non_image_is_not_found() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response(
+      format!("/thumbnail/{inscription_id}"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {inscription_id} thumbnail not found: content is not an image"),
+    );
+  }
+
   #[test]
   fn iframe_preview() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
@@ -5333,7 +6239,7 @@ mod tests {
       r".*
 <h1>Collections</h1>
 <div class=thumbnails>
-  <a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>
+  <a href=/inscription/.*><iframe .* src=/preview/.*/thumbnail></iframe></a>
   (<a href=/inscription/[[:xdigit:]]{64}i0>.*</a>\s*){99}
 </div>
 <div class=center>
@@ -5350,7 +6256,7 @@ prev
       ".*
 <h1>Collections</h1>
 <div class=thumbnails>
-  <a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>
+  <a href=/inscription/.*><iframe .* src=/preview/.*/thumbnail></iframe></a>
 </div>
 <div class=center>
 <a class=prev href=/collections/0>prev</a>
@@ -5448,7 +6354,7 @@ next
     server.assert_response_regex(
       format!("/inscription/{inscription_id}"),
       StatusCode::OK,
-      format!(".*<title>Inscription 1</title>.*<dt>parents</dt>.*<div class=thumbnails>.**<a href=/inscription/{parent_inscription_id}><iframe .* src=/preview/{parent_inscription_id}></iframe></a>.*"),
+      format!(".*<title>Inscription 1</title>.*<dt>parents</dt>.*<div class=thumbnails>.**<a href=/inscription/{parent_inscription_id}><iframe .* src=/preview/{parent_inscription_id}/thumbnail></iframe></a>.*"),
     );
     server.assert_response_regex(
       format!("/inscription/{parent_inscription_id}"),
@@ -5520,7 +6426,7 @@ next
     server.assert_response_regex(
       format!("/children/{parent_inscription_id}"),
       StatusCode::OK,
-      format!(".*<title>Inscription 0 Children</title>.*<h1><a href=/inscription/{parent_inscription_id}>Inscription 0</a> Children</h1>.*<div class=thumbnails>.*<a href=/inscription/{inscription_id}><iframe .* src=/preview/{inscription_id}></iframe></a>.*"),
+      format!(".*<title>Inscription 0 Children</title>.*<h1><a href=/inscription/{parent_inscription_id}>Inscription 0</a> Children</h1>.*<div class=thumbnails>.*<a href=/inscription/{inscription_id}><iframe .* src=/preview/{inscription_id}/thumbnail></iframe></a>.*"),
     );
   }
 
@@ -5615,10 +6521,10 @@ next
       StatusCode::OK,
       format!(
         ".*<title>Inscription 0</title>.*
-.*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*
-.*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*
-.*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*
-.*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*
+.*<a href=/inscription/.*><iframe .* src=/preview/.*/thumbnail></iframe></a>.*
+.*<a href=/inscription/.*><iframe .* src=/preview/.*/thumbnail></iframe></a>.*
+.*<a href=/inscription/.*><iframe .* src=/preview/.*/thumbnail></iframe></a>.*
+.*<a href=/inscription/.*><iframe .* src=/preview/.*/thumbnail></iframe></a>.*
     <div class=center>
       <a href=/children/{parent_inscription_id}>all \\(5\\)</a>
     </div>.*"
@@ -5764,7 +6670,7 @@ next
     server.assert_response_regex(
       format!("/parents/{inscription_id}"),
       StatusCode::OK,
-      format!(".*<title>Inscription -1 Parents</title>.*<h1><a href=/inscription/{inscription_id}>Inscription -1</a> Parents</h1>.*<div class=thumbnails>.*<a href=/inscription/{parent_a_inscription_id}><iframe .* src=/preview/{parent_b_inscription_id}></iframe></a>.*"),
+      format!(".*<title>Inscription -1 Parents</title>.*<h1><a href=/inscription/{inscription_id}>Inscription -1</a> Parents</h1>.*<div class=thumbnails>.*<a href=/inscription/{parent_a_inscription_id}><iframe .* src=/preview/{parent_b_inscription_id}/thumbnail></iframe></a>.*"),
     );
   }
 
@@ -5821,19 +6727,19 @@ next
     server.assert_response_regex(
       format!("/parents/{inscription_id}"),
       StatusCode::OK,
-      format!(".*<title>Inscription -1 Parents</title>.*<h1><a href=/inscription/{inscription_id}>Inscription -1</a> Parents</h1>.*<div class=thumbnails>(.*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*){{100}}.*"),
+      format!(".*<title>Inscription -1 Parents</title>.*<h1><a href=/inscription/{inscription_id}>Inscription -1</a> Parents</h1>.*<div class=thumbnails>(.*<a href=/inscription/.*><iframe .* src=/preview/.*/thumbnail></iframe></a>.*){{100}}.*"),
     );
 
     server.assert_response_regex(
       format!("/parents/{inscription_id}/1"),
       StatusCode::OK,
-      format!(".*<title>Inscription -1 Parents</title>.*<h1><a href=/inscription/{inscription_id}>Inscription -1</a> Parents</h1>.*<div class=thumbnails>(.*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*){{1}}.*"),
+      format!(".*<title>Inscription -1 Parents</title>.*<h1><a href=/inscription/{inscription_id}>Inscription -1</a> Parents</h1>.*<div class=thumbnails>(.*<a href=/inscription/.*><iframe .* src=/preview/.*/thumbnail></iframe></a>.*){{1}}.*"),
     );
 
     server.assert_response_regex(
       format!("/inscription/{inscription_id}"),
       StatusCode::OK,
-      ".*<title>Inscription -1</title>.*<h1>Inscription -1</h1>.*<div class=thumbnails>(.*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*){4}.*",
+      ".*<title>Inscription -1</title>.*<h1>Inscription -1</h1>.*<div class=thumbnails>(.*<a href=/inscription/.*><iframe .* src=/preview/.*/thumbnail></iframe></a>.*){4}.*",
     );
   }
 
@@ -7100,6 +8006,136 @@ next
     );
   }
 
+  #[test]
+  fn inscription_properties() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(2);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let plain = InscriptionId { txid, index: 0 };
+
+    server.assert_response(
+      format!("/r/inscription/{plain}/properties"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {plain} properties not found"),
+    );
+
+    let properties = Properties {
+      attributes: properties::Attributes {
+        title: Some("foo".into()),
+        traits: [("bar".into(), properties::Trait::Integer(1))].into(),
+      },
+      gallery: vec![properties::Item {
+        id: plain,
+        attributes: properties::Attributes {
+          title: Some("baz".into()),
+          traits: BTreeMap::new(),
+        },
+      }],
+    };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/html".into()),
+          body: Some("foo".into()),
+          properties: Some(properties.to_cbor()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    pretty_assert_eq!(
+      server.get_json::<Properties>(format!("/r/inscription/{id}/properties")),
+      properties,
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        ".*<dt>title</dt>
+  <dd>foo</dd>
+  <dt>traits</dt>
+  <dd>
+    <dl>
+      <dt>bar</dt>
+      <dd>1</dd>
+    </dl>
+  </dd>
+  <dt>gallery</dt>
+  <dd>
+    <div class=thumbnails>
+      <a href=/inscription/{plain}><iframe .* src=/preview/{plain}/thumbnail></iframe></a>
+    </div>
+  </dd>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn inscription_properties_proxy() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let properties = Properties {
+      attributes: properties::Attributes {
+        title: Some("foo".into()),
+        traits: BTreeMap::new(),
+      },
+      gallery: Vec::new(),
+    };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/html".into()),
+          body: Some("foo".into()),
+          properties: Some(properties.to_cbor()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    let server_with_proxy = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_option("--proxy", server.url.as_ref())
+      .build();
+
+    server_with_proxy.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server_with_proxy.get_json::<Properties>(format!("/r/inscription/{id}/properties")),
+      properties,
+    );
+  }
+
   #[test]
   fn children_proxy() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
@@ -7283,6 +8319,7 @@ next
         average_fee_rate: 0,
         bits: 486604799,
         chainwork: [0; 32],
+        checksum: Some(sha256::Hash::hash(&[0; 32])),
         confirmations: 0,
         difficulty: 0.0,
         hash: "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f"
@@ -7323,6 +8360,9 @@ next
         average_fee_rate: 0,
         bits: 0,
         chainwork: [0; 32],
+        checksum: Some(sha256::Hash::hash(
+          sha256::Hash::hash(&[0; 32]).as_byte_array()
+        )),
         confirmations: 0,
         difficulty: 0.0,
         hash: "56d05060a0280d0712d113f25321158747310ece87ea9e299bde06cf385b8d85"
@@ -30,12 +30,18 @@ impl AcceptEncoding {
       return false;
     };
 
-    self
-      .0
-      .clone()
-      .unwrap_or_default()
-      .split(',')
-      .any(|value| value.split(';').next().unwrap_or_default().trim() == encoding)
+    self.0.clone().unwrap_or_default().split(',').any(|value| {
+      let mut parameters = value.split(';');
+
+      parameters.next().unwrap_or_default().trim() == encoding
+        && !parameters.any(|parameter| {
+          parameter
+            .trim()
+            .strip_prefix("q=")
+            .and_then(|q| q.parse::<f32>().ok())
+            == Some(0.0)
+        })
+    })
   }
 }
 
@@ -122,4 +128,23 @@ mod tests {
     assert!(encodings.is_acceptable(&HeaderValue::from_static("br")));
     assert!(!encodings.is_acceptable(&HeaderValue::from_static("bzip2")));
   }
+
+  #[tokio::test]
+  async fn encodings_with_zero_qvalue_are_not_acceptable() {
+    let req = Request::builder()
+      .header(ACCEPT_ENCODING, "zstd;q=0, gzip;q=0.000, br;q=0.1")
+      .body(())
+      .unwrap();
+
+    let encodings = AcceptEncoding::from_request_parts(
+      &mut req.into_parts().0,
+      &Arc::new(ServerConfig::default()),
+    )
+    .await
+    .unwrap();
+
+    assert!(!encodings.is_acceptable(&HeaderValue::from_static("zstd")));
+    assert!(!encodings.is_acceptable(&HeaderValue::from_static("gzip")));
+    assert!(encodings.is_acceptable(&HeaderValue::from_static("br")));
+  }
 }
@@ -3,6 +3,7 @@ use {super::*, std::fmt::Write};
 #[derive(Debug)]
 pub(super) enum ServerError {
   BadRequest(String),
+  Gone(String),
   Internal(Error),
   NotAcceptable {
     accept_encoding: AcceptEncoding,
@@ -17,6 +18,7 @@ impl IntoResponse for ServerError {
   fn into_response(self) -> Response {
     match self {
       Self::BadRequest(message) => (StatusCode::BAD_REQUEST, message).into_response(),
+      Self::Gone(message) => (StatusCode::GONE, message).into_response(),
       Self::Internal(error) => {
         eprintln!("error serving request: {error}");
         (
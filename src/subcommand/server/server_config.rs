@@ -1,21 +1,42 @@
 use {super::*, axum::http::HeaderName};
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct ServerConfig {
   pub chain: Chain,
   pub csp_origin: Option<String>,
   pub decompress: bool,
+  pub decompress_limit: Option<usize>,
   pub domain: Option<String>,
   pub index_sats: bool,
   pub json_api_enabled: bool,
+  pub media_types: Vec<MediaType>,
   pub proxy: Option<Url>,
+  pub thumbnail_cache: Option<PathBuf>,
 }
 
 impl ServerConfig {
+  fn media_type(&self, inscription: &Inscription) -> Option<&MediaType> {
+    let content_type = inscription.content_type()?;
+
+    self
+      .media_types
+      .iter()
+      .find(|media_type| media_type.content_type == content_type)
+  }
+
+  pub(super) fn media(&self, inscription: &Inscription) -> Media {
+    match self.media_type(inscription) {
+      Some(media_type) if inscription.body().is_some() => media_type.media,
+      _ => inscription.media(),
+    }
+  }
+
   pub(super) fn preview_content_security_policy(
     &self,
-    media: Media,
+    inscription: &Inscription,
   ) -> ServerResult<[(HeaderName, HeaderValue); 1]> {
+    let media = self.media(inscription);
+
     let default = match media {
       Media::Audio => "default-src 'self'",
       Media::Code(_) => "script-src-elem 'self' https://cdn.jsdelivr.net",
@@ -34,13 +55,20 @@ impl ServerConfig {
       Media::Video => "default-src 'self'",
     };
 
+    let policy = self
+      .media_type(inscription)
+      .and_then(|media_type| media_type.content_security_policy.as_deref())
+      .unwrap_or(default);
+
     let value = if let Some(csp_origin) = &self.csp_origin {
-      default
+      policy
         .replace("'self'", csp_origin)
         .parse()
         .map_err(|err| anyhow!("invalid content-security-policy origin `{csp_origin}`: {err}"))?
     } else {
-      HeaderValue::from_static(default)
+      policy
+        .parse()
+        .map_err(|err| anyhow!("invalid content-security-policy `{policy}`: {err}"))?
     };
 
     Ok([(header::CONTENT_SECURITY_POLICY, value)])
@@ -0,0 +1,212 @@
+use {
+  super::*,
+  image::{imageops::FilterType, DynamicImage, ImageFormat, ImageReader, Limits},
+  io::Write,
+};
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub(super) enum ThumbnailFormat {
+  Png,
+  Webp,
+}
+
+impl ThumbnailFormat {
+  /// WebP if the client accepts it, PNG otherwise
+  pub(super) fn negotiate(headers: &HeaderMap) -> Self {
+    if headers
+      .get_all(header::ACCEPT)
+      .iter()
+      .filter_map(|value| value.to_str().ok())
+      .flat_map(|value| value.split(','))
+      .any(|value| value.split(';').next().unwrap_or_default().trim() == "image/webp")
+    {
+      Self::Webp
+    } else {
+      Self::Png
+    }
+  }
+
+  pub(super) fn content_type(self) -> &'static str {
+    match self {
+      Self::Png => "image/png",
+      Self::Webp => "image/webp",
+    }
+  }
+
+  pub(super) fn extension(self) -> &'static str {
+    match self {
+      Self::Png => "png",
+      Self::Webp => "webp",
+    }
+  }
+
+  fn image_format(self) -> ImageFormat {
+    match self {
+      Self::Png => ImageFormat::Png,
+      Self::Webp => ImageFormat::WebP,
+    }
+  }
+}
+
+pub(super) struct Thumbnail;
+
+impl Thumbnail {
+  /// Maximum width and height of thumbnails in pixels
+  pub(super) const SIZE: u32 = 256;
+
+  /// Maximum width and height of images which will be decoded
+  const MAX_DIMENSION: u32 = 16384;
+
+  /// Render a thumbnail of `content`, or `None` if `content` is in a format
+  /// which cannot be decoded, or is already small enough to be used as its own
+  /// thumbnail.
+  pub(super) fn render(
+    content: &[u8],
+    image_rendering: ImageRendering,
+    format: ThumbnailFormat,
+  ) -> Result<Option<Vec<u8>>> {
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(Self::MAX_DIMENSION);
+    limits.max_image_height = Some(Self::MAX_DIMENSION);
+
+    let mut reader = ImageReader::new(Cursor::new(content)).with_guessed_format()?;
+
+    reader.limits(limits);
+
+    let Ok(image) = reader.decode() else {
+      return Ok(None);
+    };
+
+    if image.width() <= Self::SIZE && image.height() <= Self::SIZE {
+      return Ok(None);
+    }
+
+    let filter = match image_rendering {
+      ImageRendering::Auto => FilterType::Lanczos3,
+      ImageRendering::Pixelated => FilterType::Nearest,
+    };
+
+    let thumbnail =
+      DynamicImage::ImageRgba8(image.resize(Self::SIZE, Self::SIZE, filter).to_rgba8());
+
+    let mut encoded = Vec::new();
+
+    thumbnail.write_to(&mut Cursor::new(&mut encoded), format.image_format())?;
+
+    Ok(Some(encoded))
+  }
+
+  /// Atomically write `thumbnail` to `path`, so that concurrent requests never
+  /// read a partially written thumbnail
+  pub(super) fn store(path: &std::path::Path, thumbnail: &[u8]) -> Result {
+    let mut file = tempfile::NamedTempFile::new_in(path.parent().unwrap())?;
+    file.write_all(thumbnail)?;
+    file.persist(path)?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, image::RgbaImage};
+
+  fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut encoded = Vec::new();
+
+    DynamicImage::ImageRgba8(RgbaImage::from_fn(width, height, |x, y| {
+      image::Rgba([x.to_le_bytes()[0], y.to_le_bytes()[0], 0, 255])
+    }))
+    .write_to(&mut Cursor::new(&mut encoded), ImageFormat::Png)
+    .unwrap();
+
+    encoded
+  }
+
+  #[test]
+  fn negotiate() {
+    #[track_caller]
+    fn case(accept: &str, expected: ThumbnailFormat) {
+      let mut headers = HeaderMap::new();
+      headers.insert(header::ACCEPT, accept.parse().unwrap());
+      assert_eq!(ThumbnailFormat::negotiate(&headers), expected);
+    }
+
+    assert_eq!(
+      ThumbnailFormat::negotiate(&HeaderMap::new()),
+      ThumbnailFormat::Png
+    );
+
+    case("image/png", ThumbnailFormat::Png);
+    case("image/avif,image/webp,*/*", ThumbnailFormat::Webp);
+    case("image/webp;q=0.9", ThumbnailFormat::Webp);
+  }
+
+  #[test]
+  fn thumbnails_are_bounded_and_preserve_aspect_ratio() {
+    for format in [ThumbnailFormat::Png, ThumbnailFormat::Webp] {
+      for image_rendering in [ImageRendering::Auto, ImageRendering::Pixelated] {
+        let thumbnail = Thumbnail::render(&png(1024, 512), image_rendering, format)
+          .unwrap()
+          .unwrap();
+
+        let image = image::load_from_memory_with_format(&thumbnail, format.image_format()).unwrap();
+
+        assert_eq!((image.width(), image.height()), (256, 128));
+      }
+    }
+  }
+
+  #[test]
+  fn pixelated_images_use_nearest_neighbor_scaling() {
+    let mut encoded = Vec::new();
+
+    DynamicImage::ImageRgba8(RgbaImage::from_fn(512, 512, |x, _| {
+      if x % 2 == 0 {
+        image::Rgba([255, 255, 255, 255])
+      } else {
+        image::Rgba([0, 0, 0, 255])
+      }
+    }))
+    .write_to(&mut Cursor::new(&mut encoded), ImageFormat::Png)
+    .unwrap();
+
+    let thumbnail = Thumbnail::render(&encoded, ImageRendering::Pixelated, ThumbnailFormat::Png)
+      .unwrap()
+      .unwrap();
+
+    let image = image::load_from_memory(&thumbnail).unwrap().to_rgba8();
+
+    assert!(image
+      .pixels()
+      .all(|pixel| pixel.0 == [255, 255, 255, 255] || pixel.0 == [0, 0, 0, 255]));
+  }
+
+  #[test]
+  fn small_images_are_not_thumbnailed() {
+    assert_eq!(
+      Thumbnail::render(&png(256, 100), ImageRendering::Auto, ThumbnailFormat::Png).unwrap(),
+      None,
+    );
+  }
+
+  #[test]
+  fn undecodable_images_are_not_thumbnailed() {
+    assert_eq!(
+      Thumbnail::render(b"foo", ImageRendering::Auto, ThumbnailFormat::Png).unwrap(),
+      None,
+    );
+  }
+
+  #[test]
+  fn oversize_images_are_not_thumbnailed() {
+    assert_eq!(
+      Thumbnail::render(
+        &png(Thumbnail::MAX_DIMENSION + 1, 1),
+        ImageRendering::Auto,
+        ThumbnailFormat::Png
+      )
+      .unwrap(),
+      None,
+    );
+  }
+}
@@ -0,0 +1,370 @@
+use {
+  super::*,
+  io::Write,
+  std::{net::TcpStream, sync::mpsc::Sender},
+};
+
+const COMMAND: u8 = 0x04;
+const LONG: u8 = 0x02;
+const MORE: u8 = 0x01;
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Bitcoin Core ZMQ notifications which wake the index thread before the
+/// polling interval elapses
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub(super) enum Notification {
+  Block,
+  Transaction,
+}
+
+impl Notification {
+  fn topic(self) -> &'static str {
+    match self {
+      Self::Block => "hashblock",
+      Self::Transaction => "rawtx",
+    }
+  }
+}
+
+/// Subscribe to `notification` at `endpoint` on a background thread, sending
+/// it on `sender` whenever Bitcoin Core publishes. This is a minimal ZMTP 3.0
+/// subscriber, since bitcoind only speaks the NULL mechanism over TCP. When
+/// the connection drops it is re-established, and until then the index thread
+/// keeps polling. The thread exits when `sender`'s receiver is dropped.
+pub(super) fn subscribe(
+  endpoint: &str,
+  notification: Notification,
+  sender: Sender<Notification>,
+) -> Result<thread::JoinHandle<()>> {
+  let address = endpoint
+    .strip_prefix("tcp://")
+    .with_context(|| format!("unsupported ZMQ endpoint `{endpoint}`: expected `tcp://HOST:PORT`"))?
+    .to_string();
+
+  let endpoint = endpoint.to_string();
+
+  Ok(thread::spawn(move || loop {
+    if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+      break;
+    }
+
+    let stream = match TcpStream::connect(&address) {
+      Ok(stream) => stream,
+      Err(err) => {
+        log::debug!("Connecting to ZMQ endpoint {endpoint}: {err}");
+        thread::sleep(RECONNECT_DELAY);
+        continue;
+      }
+    };
+
+    log::info!(
+      "Subscribed to ZMQ `{}` notifications at {endpoint}",
+      notification.topic()
+    );
+
+    match receive(stream, notification, &sender) {
+      Ok(()) => break,
+      Err(err) => {
+        log::warn!(
+          "ZMQ `{}` subscription to {endpoint} interrupted, falling back to polling: {err}",
+          notification.topic()
+        );
+        thread::sleep(RECONNECT_DELAY);
+      }
+    }
+  }))
+}
+
+/// Receive messages until the connection fails, or successfully return once
+/// notifications can no longer be delivered
+fn receive(
+  mut stream: TcpStream,
+  notification: Notification,
+  sender: &Sender<Notification>,
+) -> Result {
+  let topic = notification.topic().as_bytes();
+
+  stream.write_all(&greeting())?;
+  write_frame(&mut stream, COMMAND, &ready("SUB"))?;
+  write_frame(&mut stream, 0, &[&[1], topic].concat())?;
+
+  let mut reader = BufReader::new(stream);
+
+  read_greeting(&mut reader)?;
+
+  let mut first = true;
+
+  loop {
+    let (flags, body) = read_frame(&mut reader, topic.len())?;
+
+    if flags & COMMAND != 0 {
+      continue;
+    }
+
+    if first && body.as_deref() == Some(topic) && sender.send(notification).is_err() {
+      return Ok(());
+    }
+
+    first = flags & MORE == 0;
+  }
+}
+
+/// Signature, version 3.0, NULL mechanism, and `as-server` unset
+fn greeting() -> [u8; 64] {
+  let mut greeting = [0; 64];
+  greeting[0] = 0xFF;
+  greeting[9] = 0x7F;
+  greeting[10] = 3;
+  greeting[12..16].copy_from_slice(b"NULL");
+  greeting
+}
+
+fn read_greeting(reader: &mut impl Read) -> Result {
+  let mut greeting = [0; 64];
+
+  reader.read_exact(&mut greeting)?;
+
+  ensure!(
+    greeting[0] == 0xFF && greeting[9] == 0x7F,
+    "invalid ZMTP greeting signature"
+  );
+
+  ensure!(
+    greeting[10] >= 3,
+    "unsupported ZMTP version {}.{}",
+    greeting[10],
+    greeting[11],
+  );
+
+  ensure!(
+    greeting[12..32].starts_with(b"NULL\0"),
+    "unsupported ZMTP security mechanism"
+  );
+
+  Ok(())
+}
+
+fn ready(socket_type: &str) -> Vec<u8> {
+  let mut body = Vec::new();
+  body.push(5);
+  body.extend_from_slice(b"READY");
+  body.push(11);
+  body.extend_from_slice(b"Socket-Type");
+  body.extend_from_slice(&u32::try_from(socket_type.len()).unwrap().to_be_bytes());
+  body.extend_from_slice(socket_type.as_bytes());
+  body
+}
+
+/// Write a frame, with an eight byte size and the `LONG` flag set if its body
+/// is longer than 255 bytes
+fn write_frame(writer: &mut impl Write, flags: u8, body: &[u8]) -> Result {
+  match u8::try_from(body.len()) {
+    Ok(size) => writer.write_all(&[flags, size])?,
+    Err(_) => {
+      writer.write_all(&[flags | LONG])?;
+      writer.write_all(&u64::try_from(body.len())?.to_be_bytes())?;
+    }
+  }
+  writer.write_all(body)?;
+  Ok(())
+}
+
+/// Read a frame, returning its flags and its body if it is `len` bytes long.
+/// Other bodies are discarded without buffering, since `rawtx` frames may be
+/// large and only topic frames are of interest.
+fn read_frame(reader: &mut impl Read, len: usize) -> Result<(u8, Option<Vec<u8>>)> {
+  let mut flags = [0];
+  reader.read_exact(&mut flags)?;
+  let flags = flags[0];
+
+  let size = if flags & LONG != 0 {
+    let mut size = [0; 8];
+    reader.read_exact(&mut size)?;
+    u64::from_be_bytes(size)
+  } else {
+    let mut size = [0];
+    reader.read_exact(&mut size)?;
+    size[0].into()
+  };
+
+  if size == u64::try_from(len)? {
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body)?;
+    return Ok((flags, Some(body)));
+  }
+
+  let discarded = io::copy(&mut reader.take(size), &mut io::sink())?;
+
+  ensure!(discarded == size, "connection closed mid-frame");
+
+  Ok((flags, None))
+}
+
+/// A stand-in for bitcoind's ZMQ publisher which accepts a single subscriber
+/// at a time
+#[cfg(test)]
+pub(super) struct Publisher {
+  listener: std::net::TcpListener,
+  stream: Option<TcpStream>,
+}
+
+#[cfg(test)]
+impl Publisher {
+  pub(super) fn new() -> Self {
+    Self {
+      listener: std::net::TcpListener::bind("127.0.0.1:0").unwrap(),
+      stream: None,
+    }
+  }
+
+  pub(super) fn endpoint(&self) -> String {
+    format!("tcp://{}", self.listener.local_addr().unwrap())
+  }
+
+  /// Accept a subscriber and return the topic it subscribed to. Connections
+  /// which don't start with a ZMTP greeting, for example from other tests
+  /// probing ports, are ignored.
+  pub(super) fn accept(&mut self) -> String {
+    let mut stream = loop {
+      let (mut stream, _) = self.listener.accept().unwrap();
+
+      if read_greeting(&mut stream).is_ok() {
+        break stream;
+      }
+    };
+
+    let (flags, _) = read_frame(&mut stream, usize::MAX).unwrap();
+    assert_eq!(flags, COMMAND);
+
+    stream.write_all(&greeting()).unwrap();
+    write_frame(&mut stream, COMMAND, &ready("PUB")).unwrap();
+
+    let mut flags = [0; 2];
+    stream.read_exact(&mut flags).unwrap();
+    let mut subscription = vec![0; flags[1].into()];
+    stream.read_exact(&mut subscription).unwrap();
+    assert_eq!(subscription[0], 1);
+
+    self.stream = Some(stream);
+
+    String::from_utf8(subscription[1..].to_vec()).unwrap()
+  }
+
+  pub(super) fn publish(&mut self, topic: &str, body: &[u8]) {
+    let stream = self.stream.as_mut().unwrap();
+    write_frame(stream, MORE, topic.as_bytes()).unwrap();
+    write_frame(stream, MORE, body).unwrap();
+    write_frame(stream, 0, &0u32.to_le_bytes()).unwrap();
+  }
+
+  pub(super) fn disconnect(&mut self) {
+    self.stream = None;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, std::sync::mpsc};
+
+  #[test]
+  fn unsupported_endpoint() {
+    assert_eq!(
+      subscribe(
+        "ipc:///tmp/bitcoind",
+        Notification::Block,
+        mpsc::channel().0
+      )
+      .unwrap_err()
+      .to_string(),
+      "unsupported ZMQ endpoint `ipc:///tmp/bitcoind`: expected `tcp://HOST:PORT`",
+    );
+  }
+
+  #[test]
+  fn notifications_are_received() {
+    let mut publisher = Publisher::new();
+
+    let (sender, receiver) = mpsc::channel();
+
+    subscribe(&publisher.endpoint(), Notification::Block, sender).unwrap();
+
+    assert_eq!(publisher.accept(), "hashblock");
+
+    publisher.publish("hashblock", &[0; 32]);
+
+    assert_eq!(
+      receiver.recv_timeout(Duration::from_secs(5)),
+      Ok(Notification::Block),
+    );
+  }
+
+  #[test]
+  fn other_topics_and_large_frames_are_ignored() {
+    let mut publisher = Publisher::new();
+
+    let (sender, receiver) = mpsc::channel();
+
+    subscribe(&publisher.endpoint(), Notification::Transaction, sender).unwrap();
+
+    assert_eq!(publisher.accept(), "rawtx");
+
+    let stream = publisher.stream.as_mut().unwrap();
+    write_frame(stream, MORE, b"hashtx").unwrap();
+    write_frame(stream, MORE, &[0; 1000]).unwrap();
+    write_frame(stream, 0, &[0; 4]).unwrap();
+
+    publisher.publish("rawtx", b"rawtx");
+
+    assert_eq!(
+      receiver.recv_timeout(Duration::from_secs(5)),
+      Ok(Notification::Transaction),
+    );
+
+    assert!(receiver.try_recv().is_err());
+  }
+
+  #[test]
+  fn long_frames() {
+    let mut buffer = Vec::new();
+
+    write_frame(&mut buffer, MORE, &[1; 255]).unwrap();
+    write_frame(&mut buffer, MORE, &[2; 256]).unwrap();
+
+    assert_eq!(buffer[..2], [MORE, 255]);
+    assert_eq!(buffer[257..266], [MORE | LONG, 0, 0, 0, 0, 0, 0, 1, 0]);
+
+    let mut reader = buffer.as_slice();
+
+    assert_eq!(
+      read_frame(&mut reader, 255).unwrap(),
+      (MORE, Some(vec![1; 255]))
+    );
+    assert_eq!(
+      read_frame(&mut reader, 256).unwrap(),
+      (MORE | LONG, Some(vec![2; 256]))
+    );
+    assert!(reader.is_empty());
+  }
+
+  #[test]
+  fn subscriber_reconnects() {
+    let mut publisher = Publisher::new();
+
+    let (sender, receiver) = mpsc::channel();
+
+    subscribe(&publisher.endpoint(), Notification::Block, sender).unwrap();
+
+    publisher.accept();
+    publisher.disconnect();
+
+    assert_eq!(publisher.accept(), "hashblock");
+
+    publisher.publish("hashblock", &[0; 32]);
+
+    assert_eq!(
+      receiver.recv_timeout(Duration::from_secs(5)),
+      Ok(Notification::Block),
+    );
+  }
+}
@@ -13,7 +13,7 @@ pub mod create;
 pub mod dump;
 pub mod inscribe;
 pub mod inscriptions;
-mod label;
+pub mod label;
 pub mod mint;
 pub mod outputs;
 pub mod pending;
@@ -23,6 +23,7 @@ pub mod resume;
 pub mod runics;
 pub mod sats;
 pub mod send;
+pub mod send_many;
 mod shared_args;
 pub mod sign;
 pub mod split;
@@ -64,8 +65,8 @@ pub(crate) enum Subcommand {
   Inscribe(inscribe::Inscribe),
   #[command(about = "List wallet inscriptions")]
   Inscriptions,
-  #[command(about = "Export output labels")]
-  Label,
+  #[command(about = "Export or import BIP-329 wallet labels")]
+  Label(label::Label),
   #[command(about = "Mint a rune")]
   Mint(mint::Mint),
   #[command(about = "List all unspent outputs in wallet")]
@@ -84,6 +85,8 @@ pub(crate) enum Subcommand {
   Sats(sats::Sats),
   #[command(about = "Send sat or inscription")]
   Send(send::Send),
+  #[command(about = "Send many assets to many recipients")]
+  SendMany(send_many::SendMany),
   #[command(about = "Sign message")]
   Sign(sign::Sign),
   #[command(about = "Split outputs")]
@@ -124,7 +127,7 @@ impl WalletCommand {
       Subcommand::Dump => dump::run(wallet),
       Subcommand::Inscribe(inscribe) => inscribe.run(wallet),
       Subcommand::Inscriptions => inscriptions::run(wallet),
-      Subcommand::Label => label::run(wallet),
+      Subcommand::Label(label) => label.run(wallet),
       Subcommand::Mint(mint) => mint.run(wallet),
       Subcommand::Outputs(outputs) => outputs.run(wallet),
       Subcommand::Pending(pending) => pending.run(wallet),
@@ -133,6 +136,7 @@ impl WalletCommand {
       Subcommand::Runics => runics::run(wallet),
       Subcommand::Sats(sats) => sats.run(wallet),
       Subcommand::Send(send) => send.run(wallet),
+      Subcommand::SendMany(send_many) => send_many.run(wallet),
       Subcommand::Sign(sign) => sign.run(wallet),
       Subcommand::Split(split) => split.run(wallet),
       Subcommand::Transactions(transactions) => transactions.run(wallet),
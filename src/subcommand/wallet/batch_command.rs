@@ -42,8 +42,10 @@ impl Batch {
       Self::check_etching(&wallet, &etching)?;
     }
 
+    let (commit_fee_rate, reveal_fee_rate) = self.shared.fee_rates(&wallet)?;
+
     batch::Plan {
-      commit_fee_rate: self.shared.commit_fee_rate.unwrap_or(self.shared.fee_rate),
+      commit_fee_rate,
       destinations,
       dry_run: self.shared.dry_run,
       etching: batchfile.etching,
@@ -54,7 +56,7 @@ impl Batch {
       parent_info,
       postages,
       reinscribe: batchfile.reinscribe,
-      reveal_fee_rate: self.shared.fee_rate,
+      reveal_fee_rate,
       reveal_satpoints,
       satpoint: if let Some(sat) = batchfile.sat {
         Some(wallet.find_sat_in_outputs(sat)?)
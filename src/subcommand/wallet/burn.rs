@@ -11,8 +11,8 @@ pub struct Burn {
   cbor_metadata: Option<PathBuf>,
   #[arg(long, help = "Don't sign or broadcast transaction.")]
   dry_run: bool,
-  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
-  fee_rate: FeeRate,
+  #[arg(long, help = FEE_RATE_HELP)]
+  fee_rate: FeeRateTarget,
   #[arg(
     long,
     help = "Include JSON from <PATH> converted to CBOR in OP_RETURN.",
@@ -33,6 +33,8 @@ pub struct Burn {
 
 impl Burn {
   pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let fee_rate = wallet.fee_rate(self.fee_rate)?;
+
     let (unsigned_transaction, burn_amount) = match self.asset {
       Outgoing::InscriptionId(id) => {
         let inscription_info = wallet
@@ -87,7 +89,7 @@ impl Burn {
           Self::create_unsigned_burn_satpoint_transaction(
             &wallet,
             inscription_info.satpoint,
-            self.fee_rate,
+            fee_rate,
             script_pubkey,
             burn_amount,
           )?,
@@ -101,13 +103,8 @@ impl Burn {
         );
 
         (
-          wallet.create_unsigned_send_or_burn_runes_transaction(
-            None,
-            rune,
-            decimal,
-            None,
-            self.fee_rate,
-          )?,
+          wallet
+            .create_unsigned_send_or_burn_runes_transaction(None, rune, decimal, None, fee_rate)?,
           Amount::ZERO,
         )
       }
@@ -60,8 +60,10 @@ impl Inscribe {
       }
     }
 
+    let (commit_fee_rate, reveal_fee_rate) = self.shared.fee_rates(&wallet)?;
+
     batch::Plan {
-      commit_fee_rate: self.shared.commit_fee_rate.unwrap_or(self.shared.fee_rate),
+      commit_fee_rate,
       destinations: vec![match self.destination.clone() {
         Some(destination) => destination.require_network(chain.network())?,
         None => wallet.get_change_address()?,
@@ -72,12 +74,14 @@ impl Inscribe {
         chain,
         self.shared.compress,
         self.delegate,
+        wallet.media_types(),
         WalletCommand::parse_metadata(self.cbor_metadata, self.json_metadata)?,
         self.metaprotocol,
         self.parent.into_iter().collect(),
         self.file,
         None,
         None,
+        None,
       )?],
       mode: batch::Mode::SeparateOutputs,
       no_backup: self.shared.no_backup,
@@ -85,7 +89,7 @@ impl Inscribe {
       parent_info: wallet.get_parent_info(self.parent.as_slice())?,
       postages: vec![self.postage.unwrap_or(TARGET_POSTAGE)],
       reinscribe: self.reinscribe,
-      reveal_fee_rate: self.shared.fee_rate,
+      reveal_fee_rate,
       reveal_satpoints: Vec::new(),
       satpoint: if let Some(sat) = self.sat {
         Some(wallet.find_sat_in_outputs(sat)?)
@@ -1,71 +1,174 @@
-use super::*;
+use {
+  super::*,
+  crate::wallet::label::{Label as Record, LabelType},
+};
 
-#[derive(Serialize)]
-struct Label {
-  first_sat: SatLabel,
-  inscriptions: BTreeMap<u64, BTreeSet<InscriptionId>>,
+#[derive(Debug, Parser)]
+pub(crate) struct Label {
+  #[arg(
+    long,
+    help = "Import BIP-329 labels from JSON lines <FILE> instead of exporting labels.",
+    value_name = "FILE"
+  )]
+  import: Option<PathBuf>,
 }
 
-#[derive(Serialize)]
-struct SatLabel {
-  name: String,
-  number: u64,
-  rarity: Rarity,
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub imported: usize,
 }
 
-#[derive(Serialize)]
-struct Line {
-  label: String,
-  r#ref: String,
-  r#type: String,
-}
+impl Label {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    if let Some(path) = self.import {
+      let records = Self::load(&fs::read_to_string(&path)?)
+        .with_context(|| format!("failed to import labels from `{}`", path.display()))?;
+
+      wallet.import_labels(&records)?;
+
+      return Ok(Some(Box::new(Output {
+        imported: records.len(),
+      })));
+    }
+
+    for record in Self::export(&wallet)? {
+      serde_json::to_writer(io::stdout(), &record)?;
+      println!();
+    }
 
-pub(crate) fn run(wallet: Wallet) -> SubcommandResult {
-  let mut lines: Vec<Line> = Vec::new();
+    Ok(None)
+  }
+
+  fn load(jsonl: &str) -> Result<Vec<Record>> {
+    let mut records = Vec::new();
+
+    for (i, line) in jsonl.lines().enumerate() {
+      if line.trim().is_empty() {
+        continue;
+      }
+
+      let record = serde_json::from_str::<Record>(line)
+        .with_context(|| format!("line {}: invalid BIP-329 label", i + 1))?;
 
-  let sat_ranges = wallet.get_wallet_sat_ranges()?;
+      record
+        .check()
+        .with_context(|| format!("line {}: invalid BIP-329 label", i + 1))?;
 
-  let mut inscriptions_by_output: BTreeMap<OutPoint, BTreeMap<u64, Vec<InscriptionId>>> =
-    BTreeMap::new();
+      records.push(record);
+    }
 
-  for (satpoint, inscriptions) in wallet.inscriptions() {
-    inscriptions_by_output
-      .entry(satpoint.outpoint)
-      .or_default()
-      .insert(satpoint.offset, inscriptions.clone());
+    Ok(records)
   }
 
-  for (output, ranges) in sat_ranges {
-    let sat = Sat(ranges[0].0);
-    let mut inscriptions = BTreeMap::<u64, BTreeSet<InscriptionId>>::new();
+  fn export(wallet: &Wallet) -> Result<Vec<Record>> {
+    let mut records = wallet.labels()?;
 
-    if let Some(output_inscriptions) = inscriptions_by_output.get(&output) {
-      for (&offset, offset_inscriptions) in output_inscriptions {
-        inscriptions
-          .entry(offset)
-          .or_default()
-          .extend(offset_inscriptions);
+    for output in wallet.utxos().keys() {
+      let mut descriptions = Vec::new();
+
+      if let Some(inscriptions) = wallet.get_inscriptions_in_output(output) {
+        if !inscriptions.is_empty() {
+          descriptions.push(format!(
+            "inscriptions: {}",
+            inscriptions
+              .iter()
+              .map(|id| id.to_string())
+              .collect::<Vec<String>>()
+              .join(", ")
+          ));
+        }
+      }
+
+      if let Some(balances) = wallet.get_runes_balances_in_output(output)? {
+        if !balances.is_empty() {
+          descriptions.push(format!(
+            "runes: {}",
+            balances
+              .iter()
+              .map(|(rune, pile)| {
+                format!(
+                  "{}:{rune}",
+                  Decimal {
+                    value: pile.amount,
+                    scale: pile.divisibility,
+                  }
+                )
+              })
+              .collect::<Vec<String>>()
+              .join(", ")
+          ));
+        }
+      }
+
+      if wallet.has_sat_index() {
+        let rare_sats = wallet
+          .get_output_sat_ranges(output)?
+          .into_iter()
+          .map(|(start, _end)| Sat(start))
+          .filter(|sat| sat.rarity() > Rarity::Common)
+          .map(|sat| format!("{} ({})", sat.name(), sat.rarity()))
+          .collect::<Vec<String>>();
+
+        if !rare_sats.is_empty() {
+          descriptions.push(format!("rare sats: {}", rare_sats.join(", ")));
+        }
       }
+
+      if descriptions.is_empty() {
+        continue;
+      }
+
+      // keep ord from spending inscriptions, runes, and rare sats when labels
+      // are imported into another wallet
+      records
+        .entry((LabelType::Output, output.to_string()))
+        .and_modify(|record| {
+          record.spendable.get_or_insert(false);
+        })
+        .or_insert_with(|| Record {
+          r#type: LabelType::Output,
+          r#ref: output.to_string(),
+          label: Some(descriptions.join("; ")),
+          origin: None,
+          spendable: Some(false),
+          extra: BTreeMap::new(),
+        });
     }
 
-    lines.push(Line {
-      label: serde_json::to_string(&Label {
-        first_sat: SatLabel {
-          name: sat.name(),
-          number: sat.n(),
-          rarity: sat.rarity(),
-        },
-        inscriptions,
-      })?,
-      r#ref: output.to_string(),
-      r#type: "output".into(),
-    });
+    Ok(records.into_values().collect())
   }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
 
-  for line in lines {
-    serde_json::to_writer(io::stdout(), &line)?;
-    println!();
+  #[test]
+  fn load_skips_blank_lines() {
+    assert_eq!(
+      Label::load(&format!(
+        "{{\"type\":\"tx\",\"ref\":\"{}\",\"label\":\"foo\"}}\n\n",
+        txid(1)
+      ))
+      .unwrap(),
+      [Record {
+        r#type: LabelType::Tx,
+        r#ref: txid(1).to_string(),
+        label: Some("foo".into()),
+        origin: None,
+        spendable: None,
+        extra: BTreeMap::new(),
+      }],
+    );
   }
 
-  Ok(None)
+  #[test]
+  fn load_rejects_invalid_refs() {
+    assert_eq!(
+      Label::load("{\"type\":\"tx\",\"ref\":\"foo\"}")
+        .unwrap_err()
+        .to_string(),
+      "line 1: invalid BIP-329 label",
+    );
+  }
 }
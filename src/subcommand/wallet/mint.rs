@@ -2,8 +2,8 @@ use super::*;
 
 #[derive(Debug, Parser)]
 pub(crate) struct Mint {
-  #[clap(long, help = "Use <FEE_RATE> sats/vbyte for mint transaction.")]
-  fee_rate: FeeRate,
+  #[clap(long, help = FEE_RATE_HELP)]
+  fee_rate: FeeRateTarget,
   #[clap(long, help = "Mint <RUNE>. May contain `.` or `•`as spacers.")]
   rune: SpacedRune,
   #[clap(
@@ -13,6 +13,26 @@ pub(crate) struct Mint {
   postage: Option<Amount>,
   #[clap(long, help = "Send minted runes to <DESTINATION>.")]
   destination: Option<Address<NetworkUnchecked>>,
+  #[clap(
+    long,
+    conflicts_with = "until_cap",
+    help = "Mint <COUNT> times in chained transactions, stopping early if the mint closes or \
+    the mempool chain limit is reached. [default: 1]"
+  )]
+  count: Option<u32>,
+  #[clap(
+    long,
+    help = "Mint in chained transactions until the mint closes or the mempool chain limit is \
+    reached."
+  )]
+  until_cap: bool,
+  #[clap(
+    long,
+    help = "Consolidate minted runes into a single output after minting. If \
+    --destination is given, minted runes are sent there by the consolidation transaction, \
+    or by the mint transaction if only one mint is made."
+  )]
+  consolidate: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -20,10 +40,18 @@ pub struct Output {
   pub rune: SpacedRune,
   pub pile: Pile,
   pub mint: Txid,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub chained: Vec<Txid>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub consolidation: Option<Txid>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub stopped: Option<String>,
 }
 
 impl Mint {
   pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let fee_rate = wallet.fee_rate(self.fee_rate)?;
+
     ensure!(
       wallet.has_rune_index(),
       "`ord wallet mint` requires index created with `--index-runes` flag",
@@ -35,20 +63,29 @@ impl Mint {
 
     let block_height = bitcoin_client.get_block_count()?;
 
-    let Some((id, rune_entry, _)) = wallet.get_rune(rune)? else {
+    let Some((id, mut rune_entry, _)) = wallet.get_rune(rune)? else {
       bail!("rune {rune} has not been etched");
     };
 
     let postage = self.postage.unwrap_or(TARGET_POSTAGE);
 
-    let amount = rune_entry
-      .mintable(block_height + 1)
-      .map_err(|err| anyhow!("rune {rune} {err}"))?;
+    let count = match self.count {
+      Some(0) => bail!("--count must be greater than zero"),
+      Some(count) => count,
+      None if self.until_cap => u32::MAX,
+      None => 1,
+    };
 
     let chain = wallet.chain();
 
-    let destination = match self.destination {
-      Some(destination) => destination.require_network(chain.network())?,
+    // a single mint needs no consolidation, and is sent straight to the
+    // destination
+    let consolidate = self.consolidate && count > 1;
+
+    let mint_to_wallet = consolidate || self.destination.is_none();
+
+    let destination = match &self.destination {
+      Some(destination) => destination.clone().require_network(chain.network())?,
       None => wallet.get_change_address()?,
     };
 
@@ -58,12 +95,18 @@ impl Mint {
       destination.script_pubkey().minimal_non_dust().to_sat()
     );
 
-    let runestone = Runestone {
-      mint: Some(id),
-      ..default()
+    // when consolidating, mint to the wallet and send to the destination afterwards
+    let mint_destination = if consolidate {
+      wallet.get_change_address()?
+    } else {
+      destination.clone()
     };
 
-    let script_pubkey = runestone.encipher();
+    let script_pubkey = Runestone {
+      mint: Some(id),
+      ..default()
+    }
+    .encipher();
 
     ensure!(
       script_pubkey.len() <= MAX_STANDARD_OP_RETURN_SIZE,
@@ -72,39 +115,108 @@ impl Mint {
       MAX_STANDARD_OP_RETURN_SIZE,
     );
 
-    let unfunded_transaction = Transaction {
-      version: Version(2),
-      lock_time: LockTime::ZERO,
-      input: Vec::new(),
-      output: vec![
-        TxOut {
-          script_pubkey,
-          value: Amount::from_sat(0),
-        },
-        TxOut {
-          script_pubkey: destination.script_pubkey(),
-          value: postage,
-        },
-      ],
-    };
-
     wallet.lock_non_cardinal_outputs()?;
 
-    let unsigned_transaction =
-      fund_raw_transaction(bitcoin_client, self.fee_rate, &unfunded_transaction)?;
+    let mut amount = 0;
+    let mut mints = Vec::new();
+    let mut stopped = None;
+
+    for _ in 0..count {
+      // mints which have not yet confirmed are not reflected in the rune entry,
+      // so all mints are checked against the next block
+      let mintable = match rune_entry.mintable(block_height + 1) {
+        Ok(mintable) => mintable,
+        Err(err) if mints.is_empty() => bail!("rune {rune} {err}"),
+        Err(err) => {
+          stopped = Some(format!("rune {rune} {err}"));
+          break;
+        }
+      };
+
+      let unfunded_transaction = Transaction {
+        version: Version(2),
+        lock_time: LockTime::ZERO,
+        input: Vec::new(),
+        output: vec![
+          TxOut {
+            script_pubkey: script_pubkey.clone(),
+            value: Amount::from_sat(0),
+          },
+          TxOut {
+            script_pubkey: mint_destination.script_pubkey(),
+            value: postage,
+          },
+        ],
+      };
+
+      let signed_transaction = Self::sign(bitcoin_client, fee_rate, &unfunded_transaction)?;
+
+      assert_eq!(
+        Runestone::decipher(&signed_transaction),
+        Some(Artifact::Runestone(Runestone {
+          mint: Some(id),
+          ..default()
+        })),
+      );
+
+      let txid = match bitcoin_client.send_raw_transaction(&signed_transaction) {
+        Ok(txid) => txid,
+        // bitcoind limits how many unconfirmed transactions may be chained
+        Err(bitcoincore_rpc::Error::JsonRpc(bitcoincore_rpc::jsonrpc::Error::Rpc(
+          bitcoincore_rpc::jsonrpc::error::RpcError { message, .. },
+        )))
+          if !mints.is_empty() && message.starts_with("too-long-mempool-chain") =>
+        {
+          stopped = Some(message);
+          break;
+        }
+        Err(err) => return Err(err.into()),
+      };
+
+      let output = OutPoint { txid, vout: 1 };
+
+      // keep later mints from spending the runes minted by earlier ones
+      if mint_to_wallet && !bitcoin_client.lock_unspent(&[output])? {
+        bail!("failed to lock UTXOs");
+      }
+
+      rune_entry.mints += 1;
+      amount += mintable;
+      mints.push(output);
+    }
+
+    // if minting stopped after the first mint, it must still be sent to the
+    // destination
+    let consolidation = if consolidate && (mints.len() > 1 || self.destination.is_some()) {
+      let unfunded_transaction = Transaction {
+        version: Version(2),
+        lock_time: LockTime::ZERO,
+        input: mints
+          .iter()
+          .map(|previous_output| TxIn {
+            previous_output: *previous_output,
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::MAX,
+            witness: Witness::new(),
+          })
+          .collect(),
+        output: vec![TxOut {
+          script_pubkey: destination.script_pubkey(),
+          value: postage,
+        }],
+      };
 
-    let signed_transaction = bitcoin_client
-      .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
-      .hex;
+      let signed_transaction = Self::sign(bitcoin_client, fee_rate, &unfunded_transaction)?;
 
-    let signed_transaction = consensus::encode::deserialize(&signed_transaction)?;
+      // without a runestone, all minted runes are assigned to the first output
+      assert_eq!(Runestone::decipher(&signed_transaction), None);
 
-    assert_eq!(
-      Runestone::decipher(&signed_transaction),
-      Some(Artifact::Runestone(runestone)),
-    );
+      Some(bitcoin_client.send_raw_transaction(&signed_transaction)?)
+    } else {
+      None
+    };
 
-    let transaction = bitcoin_client.send_raw_transaction(&signed_transaction)?;
+    let mut mints = mints.into_iter().map(|outpoint| outpoint.txid);
 
     Ok(Some(Box::new(Output {
       rune: self.rune,
@@ -113,7 +225,25 @@ impl Mint {
         divisibility: rune_entry.divisibility,
         symbol: rune_entry.symbol,
       },
-      mint: transaction,
+      mint: mints.next().unwrap(),
+      chained: mints.collect(),
+      consolidation,
+      stopped,
     })))
   }
+
+  fn sign(
+    bitcoin_client: &Client,
+    fee_rate: FeeRate,
+    unfunded_transaction: &Transaction,
+  ) -> Result<Transaction> {
+    let unsigned_transaction =
+      fund_raw_transaction(bitcoin_client, fee_rate, unfunded_transaction)?;
+
+    let signed_transaction = bitcoin_client
+      .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
+      .hex;
+
+    Ok(consensus::encode::deserialize(&signed_transaction)?)
+  }
 }
@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, crate::wallet::label::LabelType};
 
 #[derive(Debug, Parser)]
 pub(crate) struct Outputs {
@@ -15,6 +15,8 @@ pub struct Output {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub inscriptions: Option<Vec<InscriptionId>>,
   #[serde(skip_serializing_if = "Option::is_none")]
+  pub label: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
   pub runes: Option<BTreeMap<SpacedRune, Decimal>>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub sat_ranges: Option<Vec<String>>,
@@ -22,6 +24,8 @@ pub struct Output {
 
 impl Outputs {
   pub(crate) fn run(&self, wallet: Wallet) -> SubcommandResult {
+    let labels = wallet.labels()?;
+
     let mut outputs = Vec::new();
     for (output, txout) in wallet.utxos() {
       let address = wallet
@@ -61,10 +65,20 @@ impl Outputs {
         None
       };
 
+      let label = labels
+        .get(&(LabelType::Output, output.to_string()))
+        .or_else(|| {
+          address.as_ref().and_then(|address| {
+            labels.get(&(LabelType::Addr, address.assume_checked_ref().to_string()))
+          })
+        })
+        .and_then(|label| label.label.clone());
+
       outputs.push(Output {
         address,
         amount: txout.value.to_sat(),
         inscriptions,
+        label,
         output: *output,
         runes,
         sat_ranges,
@@ -4,8 +4,8 @@ use super::*;
 pub(crate) struct Send {
   #[arg(long, help = "Don't sign or broadcast transaction")]
   pub(crate) dry_run: bool,
-  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
-  fee_rate: FeeRate,
+  #[arg(long, help = FEE_RATE_HELP)]
+  fee_rate: FeeRateTarget,
   #[arg(
     long,
     help = "Target <AMOUNT> postage with sent inscriptions. [default: 10000 sat]",
@@ -33,6 +33,8 @@ pub struct Output {
 
 impl Send {
   pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let fee_rate = wallet.fee_rate(self.fee_rate)?;
+
     let address = self
       .address
       .clone()
@@ -40,14 +42,14 @@ impl Send {
 
     let unsigned_transaction = match self.asset {
       Outgoing::Amount(amount) => {
-        wallet.create_unsigned_send_amount_transaction(address, amount, self.fee_rate)?
+        wallet.create_unsigned_send_amount_transaction(address, amount, fee_rate)?
       }
       Outgoing::Rune { decimal, rune } => wallet.create_unsigned_send_or_burn_runes_transaction(
         Some(address),
         rune,
         decimal,
         self.postage,
-        self.fee_rate,
+        fee_rate,
       )?,
       Outgoing::InscriptionId(id) => wallet.create_unsigned_send_satpoint_transaction(
         address,
@@ -57,21 +59,21 @@ impl Send {
           .ok_or_else(|| anyhow!("inscription {id} not found"))?
           .satpoint,
         self.postage,
-        self.fee_rate,
+        fee_rate,
         true,
       )?,
       Outgoing::SatPoint(satpoint) => wallet.create_unsigned_send_satpoint_transaction(
         address,
         satpoint,
         self.postage,
-        self.fee_rate,
+        fee_rate,
         false,
       )?,
       Outgoing::Sat(sat) => wallet.create_unsigned_send_satpoint_transaction(
         address,
         wallet.find_sat_in_outputs(sat)?,
         self.postage,
-        self.fee_rate,
+        fee_rate,
         true,
       )?,
     };
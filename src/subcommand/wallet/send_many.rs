@@ -0,0 +1,998 @@
+use {
+  super::*,
+  sendfile::{Asset, Sendfile},
+};
+
+mod sendfile;
+
+// half of `MAX_STANDARD_TX_WEIGHT`, leaving room for the inputs and change
+// output added by `fundrawtransaction`
+const MAX_UNFUNDED_WEIGHT: u64 = 200_000;
+
+const TAPROOT_KEY_SPEND_WITNESS_WEIGHT: u64 = 66;
+
+#[derive(Debug, PartialEq)]
+enum Error {
+  ConflictingRecipients {
+    output: OutPoint,
+  },
+  DustOutput {
+    value: Amount,
+    threshold: Amount,
+    send: usize,
+  },
+  DustPostage {
+    value: Amount,
+    threshold: Amount,
+  },
+  NoSends,
+  OutputNotInWallet {
+    output: OutPoint,
+  },
+  RuneOverflow {
+    rune: SpacedRune,
+  },
+  RunicOutput {
+    output: OutPoint,
+  },
+  RunestoneSize {
+    size: usize,
+  },
+  Shortfall {
+    rune: SpacedRune,
+    have: Pile,
+    need: Pile,
+  },
+  UnlistedInscription {
+    inscription: InscriptionId,
+    output: OutPoint,
+  },
+  Weight {
+    weight: u64,
+  },
+  ZeroValue {
+    send: usize,
+    rune: SpacedRune,
+  },
+}
+
+impl Display for Error {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::ConflictingRecipients { output } => {
+        write!(f, "output {output} contains assets sent to multiple recipients")
+      }
+      Self::DustOutput {
+        value,
+        threshold,
+        send,
+      } => write!(
+        f,
+        "send {send} value {value} below dust threshold {threshold}"
+      ),
+      Self::DustPostage { value, threshold } => {
+        write!(f, "postage value {value} below dust threshold {threshold}")
+      }
+      Self::NoSends => write!(f, "send file must contain at least one send"),
+      Self::OutputNotInWallet { output } => write!(f, "output {output} not in wallet"),
+      Self::RuneOverflow { rune } => write!(f, "total amount of {rune} sent overflows"),
+      Self::RunicOutput { output } => {
+        write!(f, "output {output} contains runes and may not be sent by satpoint")
+      }
+      Self::RunestoneSize { size } => write!(
+        f,
+        "runestone size {size} over maximum standard OP_RETURN size {MAX_STANDARD_OP_RETURN_SIZE}"
+      ),
+      Self::Shortfall { rune, have, need } => {
+        write!(f, "wallet contains {have} of {rune} but need {need}")
+      }
+      Self::UnlistedInscription {
+        inscription,
+        output,
+      } => write!(
+        f,
+        "output {output} contains inscription {inscription} which is not sent to the same recipient"
+      ),
+      Self::Weight { weight } => write!(
+        f,
+        "transaction weight {weight} over maximum standard transaction weight {MAX_STANDARD_TX_WEIGHT}"
+      ),
+      Self::ZeroValue { send, rune } => {
+        write!(f, "send {send} has zero value for rune {rune}")
+      }
+    }
+  }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Parser)]
+pub(crate) struct SendMany {
+  #[arg(long, help = "Don't sign or broadcast transactions")]
+  pub(crate) dry_run: bool,
+  #[arg(long, help = FEE_RATE_HELP)]
+  fee_rate: FeeRateTarget,
+  #[arg(
+    long,
+    help = "Target <AMOUNT> postage with sent runes and rune change. [default: 10000 sat]",
+    value_name = "AMOUNT"
+  )]
+  pub(crate) postage: Option<Amount>,
+  #[arg(
+    long,
+    help = "Send assets to recipients defined in YAML or CSV <SEND_FILE>.",
+    value_name = "SEND_FILE"
+  )]
+  pub(crate) sends: PathBuf,
+  #[arg(
+    long,
+    alias = "nolimit",
+    help = "Allow OP_RETURN greater than 83 bytes. Transactions over this limit are nonstandard \
+    and will not be relayed by bitcoind in its default configuration. Do not use this flag unless \
+    you understand the implications."
+  )]
+  pub(crate) no_limit: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub transactions: Vec<TransactionOutput>,
+  pub sends: usize,
+  pub fee: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionOutput {
+  pub txid: Txid,
+  pub psbt: String,
+  pub fee: u64,
+}
+
+#[derive(Debug, Clone)]
+enum Item {
+  Amount {
+    address: Address,
+    amount: Amount,
+  },
+  Output {
+    address: Address,
+    outpoint: OutPoint,
+    value: Amount,
+  },
+  Rune {
+    address: Address,
+    rune: Rune,
+    amount: u128,
+  },
+}
+
+impl SendMany {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let fee_rate = wallet.fee_rate(self.fee_rate)?;
+
+    wallet.lock_non_cardinal_outputs()?;
+
+    let sendfile = Sendfile::load(&self.sends, &wallet)?;
+
+    let runic_outputs = wallet.get_runic_outputs()?.unwrap_or_default();
+
+    let inscribed_outputs = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<HashSet<OutPoint>>();
+
+    let balances = runic_outputs
+      .iter()
+      .filter(|output| !inscribed_outputs.contains(output))
+      .map(|output| {
+        wallet.get_runes_balances_in_output(output).map(|balance| {
+          (
+            *output,
+            balance
+              .unwrap_or_default()
+              .into_iter()
+              .map(|(spaced_rune, pile)| (spaced_rune.rune, pile.amount))
+              .collect(),
+          )
+        })
+      })
+      .collect::<Result<BTreeMap<OutPoint, BTreeMap<Rune, u128>>>>()?;
+
+    let unfunded_transactions = Self::build_transactions(
+      self.no_limit,
+      wallet.inscriptions(),
+      wallet.utxos(),
+      &runic_outputs,
+      balances,
+      &wallet.get_change_address()?,
+      self.postage,
+      &sendfile,
+    )?;
+
+    let mut transactions = Vec::new();
+    let mut locked = Vec::new();
+
+    for (i, unfunded_transaction) in unfunded_transactions.iter().enumerate() {
+      let unsigned_transaction: Transaction = consensus::encode::deserialize(
+        &fund_raw_transaction(wallet.bitcoin_client(), fee_rate, unfunded_transaction)?,
+      )?;
+
+      let last = i + 1 == unfunded_transactions.len();
+
+      // when not broadcasting, keep later transactions from reusing this
+      // transaction's funding inputs
+      if self.dry_run && !last {
+        let funding = unsigned_transaction.input[unfunded_transaction.input.len()..]
+          .iter()
+          .map(|txin| txin.previous_output)
+          .collect::<Vec<OutPoint>>();
+
+        if !wallet.bitcoin_client().lock_unspent(&funding)? {
+          bail!("failed to lock UTXOs");
+        }
+
+        locked.extend(funding);
+      }
+
+      let change = unsigned_transaction.output.len() > unfunded_transaction.output.len();
+
+      let (txid, psbt, fee) =
+        wallet.sign_and_broadcast_transaction(unsigned_transaction, self.dry_run, None)?;
+
+      // keep later transactions from spending this transaction's unconfirmed
+      // change, which is not yet known to the wallet
+      if !self.dry_run && !last && change {
+        let change = OutPoint {
+          txid,
+          vout: unfunded_transaction.output.len().try_into().unwrap(),
+        };
+
+        if !wallet.bitcoin_client().lock_unspent(&[change])? {
+          bail!("failed to lock UTXOs");
+        }
+
+        locked.push(change);
+      }
+
+      transactions.push(TransactionOutput { txid, psbt, fee });
+    }
+
+    if !locked.is_empty() && !wallet.bitcoin_client().unlock_unspent(&locked)? {
+      bail!("failed to unlock UTXOs");
+    }
+
+    Ok(Some(Box::new(Output {
+      fee: transactions.iter().map(|transaction| transaction.fee).sum(),
+      sends: sendfile.sends.len(),
+      transactions,
+    })))
+  }
+
+  fn build_transactions(
+    no_runestone_limit: bool,
+    inscriptions: &BTreeMap<SatPoint, Vec<InscriptionId>>,
+    utxos: &BTreeMap<OutPoint, TxOut>,
+    runic_outputs: &BTreeSet<OutPoint>,
+    mut balances: BTreeMap<OutPoint, BTreeMap<Rune, u128>>,
+    change_address: &Address,
+    postage: Option<Amount>,
+    sendfile: &Sendfile,
+  ) -> Result<Vec<Transaction>, Error> {
+    if sendfile.sends.is_empty() {
+      return Err(Error::NoSends);
+    }
+
+    let postage = postage.unwrap_or(TARGET_POSTAGE);
+
+    let change_script_pubkey = change_address.script_pubkey();
+
+    let change_dust_threshold = change_script_pubkey.minimal_non_dust();
+
+    if postage < change_dust_threshold {
+      return Err(Error::DustPostage {
+        value: postage,
+        threshold: change_dust_threshold,
+      });
+    }
+
+    let mut recipients = BTreeMap::<OutPoint, &Address>::new();
+    let mut listed = HashSet::<(OutPoint, InscriptionId)>::new();
+    let mut items = Vec::new();
+
+    for (i, send) in sendfile.sends.iter().enumerate() {
+      match send.asset {
+        Asset::Amount(amount) => {
+          let threshold = send.address.script_pubkey().minimal_non_dust();
+
+          if amount < threshold {
+            return Err(Error::DustOutput {
+              value: amount,
+              threshold,
+              send: i,
+            });
+          }
+
+          items.push(Item::Amount {
+            address: send.address.clone(),
+            amount,
+          });
+        }
+        Asset::Inscription(_, satpoint) | Asset::SatPoint(satpoint) => {
+          let outpoint = satpoint.outpoint;
+
+          if let Asset::Inscription(inscription, _) = send.asset {
+            listed.insert((outpoint, inscription));
+          }
+
+          if let Some(recipient) = recipients.get(&outpoint) {
+            if **recipient != send.address {
+              return Err(Error::ConflictingRecipients { output: outpoint });
+            }
+
+            continue;
+          }
+
+          if runic_outputs.contains(&outpoint) {
+            return Err(Error::RunicOutput { output: outpoint });
+          }
+
+          let value = utxos
+            .get(&outpoint)
+            .ok_or(Error::OutputNotInWallet { output: outpoint })?
+            .value;
+
+          let threshold = send.address.script_pubkey().minimal_non_dust();
+
+          if value < threshold {
+            return Err(Error::DustOutput {
+              value,
+              threshold,
+              send: i,
+            });
+          }
+
+          recipients.insert(outpoint, &send.address);
+
+          items.push(Item::Output {
+            address: send.address.clone(),
+            outpoint,
+            value,
+          });
+        }
+        Asset::Rune(rune, amount) => {
+          if amount == 0 {
+            return Err(Error::ZeroValue {
+              rune: sendfile.rune_info[&rune].spaced_rune,
+              send: i,
+            });
+          }
+
+          let threshold = send.address.script_pubkey().minimal_non_dust();
+
+          if postage < threshold {
+            return Err(Error::DustOutput {
+              value: postage,
+              threshold,
+              send: i,
+            });
+          }
+
+          items.push(Item::Rune {
+            address: send.address.clone(),
+            rune,
+            amount,
+          });
+        }
+      }
+    }
+
+    for (satpoint, ids) in inscriptions {
+      if !recipients.contains_key(&satpoint.outpoint) {
+        continue;
+      }
+
+      for id in ids {
+        if !listed.contains(&(satpoint.outpoint, *id)) {
+          return Err(Error::UnlistedInscription {
+            inscription: *id,
+            output: satpoint.outpoint,
+          });
+        }
+      }
+    }
+
+    let mut transactions = Vec::new();
+    let mut batch = Vec::new();
+    let mut built = None;
+
+    for item in items {
+      batch.push(item);
+
+      match Self::build_transaction(
+        no_runestone_limit,
+        &balances,
+        &change_script_pubkey,
+        postage,
+        sendfile,
+        &batch,
+      ) {
+        Ok(transaction) => built = Some(transaction),
+        Err(Error::RunestoneSize { .. } | Error::Weight { .. }) if batch.len() > 1 => {
+          let item = batch.pop().unwrap();
+
+          let transaction = built.take().unwrap();
+
+          for input in &transaction.input {
+            balances.remove(&input.previous_output);
+          }
+
+          transactions.push(transaction);
+
+          batch = vec![item];
+
+          built = Some(Self::build_transaction(
+            no_runestone_limit,
+            &balances,
+            &change_script_pubkey,
+            postage,
+            sendfile,
+            &batch,
+          )?);
+        }
+        Err(err) => return Err(err),
+      }
+    }
+
+    transactions.extend(built);
+
+    Ok(transactions)
+  }
+
+  fn build_transaction(
+    no_runestone_limit: bool,
+    balances: &BTreeMap<OutPoint, BTreeMap<Rune, u128>>,
+    change_script_pubkey: &ScriptBuf,
+    postage: Amount,
+    sendfile: &Sendfile,
+    batch: &[Item],
+  ) -> Result<Transaction, Error> {
+    let mut inputs = Vec::new();
+    let mut output = Vec::new();
+
+    // inscription and sat outputs come first and carry the full value of the
+    // corresponding input, so every sat lands at the same offset it had
+    for item in batch {
+      if let Item::Output {
+        address,
+        outpoint,
+        value,
+      } = item
+      {
+        inputs.push(*outpoint);
+        output.push(TxOut {
+          script_pubkey: address.script_pubkey(),
+          value: *value,
+        });
+      }
+    }
+
+    let mut edicts = Vec::new();
+    let mut input_runes_required = BTreeMap::<Rune, u128>::new();
+
+    for item in batch {
+      match item {
+        Item::Amount { address, amount } => output.push(TxOut {
+          script_pubkey: address.script_pubkey(),
+          value: *amount,
+        }),
+        Item::Output { .. } => {}
+        Item::Rune {
+          address,
+          rune,
+          amount,
+        } => {
+          let required = input_runes_required.entry(*rune).or_default();
+          *required = required
+            .checked_add(*amount)
+            .ok_or_else(|| Error::RuneOverflow {
+              rune: sendfile.rune_info[rune].spaced_rune,
+            })?;
+
+          edicts.push(Edict {
+            id: sendfile.rune_info[rune].id,
+            amount: *amount,
+            output: output.len().try_into().unwrap(),
+          });
+
+          output.push(TxOut {
+            script_pubkey: address.script_pubkey(),
+            value: postage,
+          });
+        }
+      }
+    }
+
+    let mut input_rune_balances = BTreeMap::<Rune, u128>::new();
+
+    for (outpoint, runes) in balances {
+      for (rune, required) in &input_runes_required {
+        if input_rune_balances.get(rune).copied().unwrap_or_default() >= *required {
+          continue;
+        }
+
+        if runes.get(rune).copied().unwrap_or_default() == 0 {
+          continue;
+        }
+
+        for (rune, balance) in runes {
+          *input_rune_balances.entry(*rune).or_default() += balance;
+        }
+
+        inputs.push(*outpoint);
+
+        break;
+      }
+    }
+
+    for (&rune, &need) in &input_runes_required {
+      let have = input_rune_balances.get(&rune).copied().unwrap_or_default();
+      if have < need {
+        let info = sendfile.rune_info[&rune];
+        return Err(Error::Shortfall {
+          rune: info.spaced_rune,
+          have: Pile {
+            amount: have,
+            divisibility: info.divisibility,
+            symbol: info.symbol,
+          },
+          need: Pile {
+            amount: need,
+            divisibility: info.divisibility,
+            symbol: info.symbol,
+          },
+        });
+      }
+    }
+
+    let runestone = if edicts.is_empty() {
+      None
+    } else {
+      let need_rune_change_output = input_rune_balances
+        .iter()
+        .any(|(rune, input)| *input > input_runes_required.get(rune).copied().unwrap_or_default());
+
+      let pointer = if need_rune_change_output {
+        output.push(TxOut {
+          script_pubkey: change_script_pubkey.clone(),
+          value: postage,
+        });
+
+        Some((output.len() - 1).try_into().unwrap())
+      } else {
+        None
+      };
+
+      let runestone = Runestone {
+        edicts,
+        pointer,
+        ..default()
+      };
+
+      let runestone_script_pubkey = runestone.encipher();
+      let size = runestone_script_pubkey.len();
+
+      if !no_runestone_limit && size > MAX_STANDARD_OP_RETURN_SIZE {
+        return Err(Error::RunestoneSize { size });
+      }
+
+      output.push(TxOut {
+        script_pubkey: runestone_script_pubkey,
+        value: Amount::from_sat(0),
+      });
+
+      Some(runestone)
+    };
+
+    let tx = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: inputs
+        .into_iter()
+        .map(|previous_output| TxIn {
+          previous_output,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::MAX,
+          witness: Witness::new(),
+        })
+        .collect(),
+      output,
+    };
+
+    let weight = tx.weight().to_wu()
+      + u64::try_from(tx.input.len()).unwrap() * TAPROOT_KEY_SPEND_WITNESS_WEIGHT;
+
+    if weight > MAX_UNFUNDED_WEIGHT {
+      return Err(Error::Weight { weight });
+    }
+
+    for output in &tx.output {
+      assert!(output.value >= output.script_pubkey.minimal_non_dust());
+    }
+
+    if let Some(runestone) = runestone {
+      assert_eq!(
+        Runestone::decipher(&tx),
+        Some(Artifact::Runestone(runestone)),
+      );
+    }
+
+    Ok(tx)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::subcommand::wallet::split::splitfile::RuneInfo,
+    sendfile::{Asset, Send},
+  };
+
+  fn rune_info() -> BTreeMap<Rune, RuneInfo> {
+    [(
+      Rune(0),
+      RuneInfo {
+        id: RuneId { block: 1, tx: 1 },
+        divisibility: 0,
+        symbol: None,
+        spaced_rune: SpacedRune {
+          rune: Rune(0),
+          spacers: 0,
+        },
+      },
+    )]
+    .into()
+  }
+
+  #[test]
+  fn send_file_must_have_at_least_one_send() {
+    assert_eq!(
+      SendMany::build_transactions(
+        false,
+        &BTreeMap::new(),
+        &BTreeMap::new(),
+        &BTreeSet::new(),
+        BTreeMap::new(),
+        &change(0),
+        None,
+        &Sendfile {
+          sends: Vec::new(),
+          rune_info: BTreeMap::new(),
+        },
+      )
+      .unwrap_err(),
+      Error::NoSends,
+    );
+  }
+
+  #[test]
+  fn inscriptions_and_runes_are_sent_in_one_transaction() {
+    let inscription_a = inscription_id(1);
+    let inscription_b = inscription_id(2);
+
+    let transactions = SendMany::build_transactions(
+      false,
+      &[
+        (satpoint(1, 0), vec![inscription_a]),
+        (satpoint(2, 0), vec![inscription_b]),
+      ]
+      .into(),
+      &[
+        (outpoint(1), tx_out(10_000, change(0))),
+        (outpoint(2), tx_out(20_000, change(0))),
+        (outpoint(3), tx_out(10_000, change(0))),
+      ]
+      .into(),
+      &[outpoint(3)].into(),
+      [(outpoint(3), [(Rune(0), 1000)].into())].into(),
+      &change(0),
+      None,
+      &Sendfile {
+        sends: vec![
+          Send {
+            address: address(0),
+            asset: Asset::Rune(Rune(0), 600),
+          },
+          Send {
+            address: address(1),
+            asset: Asset::Inscription(inscription_b, satpoint(2, 0)),
+          },
+          Send {
+            address: address(0),
+            asset: Asset::Inscription(inscription_a, satpoint(1, 0)),
+          },
+        ],
+        rune_info: rune_info(),
+      },
+    )
+    .unwrap();
+
+    assert_eq!(transactions.len(), 1);
+
+    let tx = &transactions[0];
+
+    assert_eq!(
+      tx.input
+        .iter()
+        .map(|txin| txin.previous_output)
+        .collect::<Vec<OutPoint>>(),
+      [outpoint(2), outpoint(1), outpoint(3)],
+    );
+
+    assert_eq!(
+      tx.output[..4],
+      [
+        TxOut {
+          script_pubkey: address(1).script_pubkey(),
+          value: Amount::from_sat(20_000),
+        },
+        TxOut {
+          script_pubkey: address(0).script_pubkey(),
+          value: Amount::from_sat(10_000),
+        },
+        TxOut {
+          script_pubkey: address(0).script_pubkey(),
+          value: TARGET_POSTAGE,
+        },
+        TxOut {
+          script_pubkey: change(0).script_pubkey(),
+          value: TARGET_POSTAGE,
+        },
+      ]
+    );
+
+    assert_eq!(
+      Runestone::decipher(tx),
+      Some(Artifact::Runestone(Runestone {
+        edicts: vec![Edict {
+          id: RuneId { block: 1, tx: 1 },
+          amount: 600,
+          output: 2,
+        }],
+        pointer: Some(3),
+        ..default()
+      })),
+    );
+  }
+
+  #[test]
+  fn unlisted_inscriptions_may_not_be_sent() {
+    assert_eq!(
+      SendMany::build_transactions(
+        false,
+        &[(satpoint(1, 0), vec![inscription_id(1), inscription_id(2)])].into(),
+        &[(outpoint(1), tx_out(10_000, change(0)))].into(),
+        &BTreeSet::new(),
+        BTreeMap::new(),
+        &change(0),
+        None,
+        &Sendfile {
+          sends: vec![Send {
+            address: address(0),
+            asset: Asset::Inscription(inscription_id(1), satpoint(1, 0)),
+          }],
+          rune_info: BTreeMap::new(),
+        },
+      )
+      .unwrap_err(),
+      Error::UnlistedInscription {
+        inscription: inscription_id(2),
+        output: outpoint(1),
+      },
+    );
+  }
+
+  #[test]
+  fn outputs_may_not_be_sent_to_multiple_recipients() {
+    assert_eq!(
+      SendMany::build_transactions(
+        false,
+        &[(satpoint(1, 0), vec![inscription_id(1), inscription_id(2)])].into(),
+        &[(outpoint(1), tx_out(10_000, change(0)))].into(),
+        &BTreeSet::new(),
+        BTreeMap::new(),
+        &change(0),
+        None,
+        &Sendfile {
+          sends: vec![
+            Send {
+              address: address(0),
+              asset: Asset::Inscription(inscription_id(1), satpoint(1, 0)),
+            },
+            Send {
+              address: address(1),
+              asset: Asset::Inscription(inscription_id(2), satpoint(1, 0)),
+            },
+          ],
+          rune_info: BTreeMap::new(),
+        },
+      )
+      .unwrap_err(),
+      Error::ConflictingRecipients {
+        output: outpoint(1),
+      },
+    );
+  }
+
+  #[test]
+  fn inscription_outputs_below_recipient_dust_threshold_may_not_be_sent() {
+    assert_eq!(
+      SendMany::build_transactions(
+        false,
+        &[(satpoint(1, 0), vec![inscription_id(1)])].into(),
+        &[(outpoint(1), tx_out(330, change(0)))].into(),
+        &BTreeSet::new(),
+        BTreeMap::new(),
+        &change(0),
+        None,
+        &Sendfile {
+          sends: vec![Send {
+            address: "mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn"
+              .parse::<Address<NetworkUnchecked>>()
+              .unwrap()
+              .assume_checked(),
+            asset: Asset::Inscription(inscription_id(1), satpoint(1, 0)),
+          }],
+          rune_info: BTreeMap::new(),
+        },
+      )
+      .unwrap_err(),
+      Error::DustOutput {
+        value: Amount::from_sat(330),
+        threshold: Amount::from_sat(546),
+        send: 0,
+      },
+    );
+  }
+
+  #[test]
+  fn rune_outputs_below_recipient_dust_threshold_may_not_be_sent() {
+    assert_eq!(
+      SendMany::build_transactions(
+        false,
+        &BTreeMap::new(),
+        &[(outpoint(1), tx_out(10_000, change(0)))].into(),
+        &[outpoint(1)].into(),
+        [(outpoint(1), [(Rune(0), 1000)].into())].into(),
+        &change(0),
+        Some(Amount::from_sat(400)),
+        &Sendfile {
+          sends: vec![Send {
+            address: "mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn"
+              .parse::<Address<NetworkUnchecked>>()
+              .unwrap()
+              .assume_checked(),
+            asset: Asset::Rune(Rune(0), 600),
+          }],
+          rune_info: rune_info(),
+        },
+      )
+      .unwrap_err(),
+      Error::DustOutput {
+        value: Amount::from_sat(400),
+        threshold: Amount::from_sat(546),
+        send: 0,
+      },
+    );
+  }
+
+  #[test]
+  fn rune_amounts_which_overflow_may_not_be_sent() {
+    assert_eq!(
+      SendMany::build_transactions(
+        false,
+        &BTreeMap::new(),
+        &[(outpoint(1), tx_out(10_000, change(0)))].into(),
+        &[outpoint(1)].into(),
+        [(outpoint(1), [(Rune(0), u128::MAX)].into())].into(),
+        &change(0),
+        None,
+        &Sendfile {
+          sends: vec![
+            Send {
+              address: address(0),
+              asset: Asset::Rune(Rune(0), u128::MAX),
+            },
+            Send {
+              address: address(1),
+              asset: Asset::Rune(Rune(0), 1),
+            },
+          ],
+          rune_info: rune_info(),
+        },
+      )
+      .unwrap_err(),
+      Error::RuneOverflow {
+        rune: SpacedRune {
+          rune: Rune(0),
+          spacers: 0,
+        },
+      },
+    );
+  }
+
+  #[test]
+  fn runic_outputs_may_not_be_sent_by_satpoint() {
+    assert_eq!(
+      SendMany::build_transactions(
+        false,
+        &BTreeMap::new(),
+        &[(outpoint(1), tx_out(10_000, change(0)))].into(),
+        &[outpoint(1)].into(),
+        BTreeMap::new(),
+        &change(0),
+        None,
+        &Sendfile {
+          sends: vec![Send {
+            address: address(0),
+            asset: Asset::SatPoint(satpoint(1, 0)),
+          }],
+          rune_info: BTreeMap::new(),
+        },
+      )
+      .unwrap_err(),
+      Error::RunicOutput {
+        output: outpoint(1),
+      },
+    );
+  }
+
+  #[test]
+  fn oversized_runestones_are_split_across_transactions() {
+    let sends = (0..20)
+      .map(|i| Send {
+        address: address(0),
+        asset: Asset::Rune(Rune(0), 1000 + i),
+      })
+      .collect::<Vec<Send>>();
+
+    let transactions = SendMany::build_transactions(
+      false,
+      &BTreeMap::new(),
+      &BTreeMap::new(),
+      &BTreeSet::new(),
+      (1..=4)
+        .map(|i| (outpoint(i), [(Rune(0), 10_000)].into()))
+        .collect(),
+      &change(0),
+      None,
+      &Sendfile {
+        sends,
+        rune_info: rune_info(),
+      },
+    )
+    .unwrap();
+
+    assert!(transactions.len() > 1);
+
+    let mut inputs = HashSet::new();
+    let mut edicts = 0;
+
+    for tx in &transactions {
+      assert!(tx.output.last().unwrap().script_pubkey.len() <= MAX_STANDARD_OP_RETURN_SIZE);
+
+      for txin in &tx.input {
+        assert!(inputs.insert(txin.previous_output));
+      }
+
+      let Some(Artifact::Runestone(runestone)) = Runestone::decipher(tx) else {
+        panic!("transaction has no runestone");
+      };
+
+      edicts += runestone.edicts.len();
+    }
+
+    assert_eq!(edicts, 20);
+  }
+}
@@ -0,0 +1,175 @@
+use {super::*, crate::subcommand::wallet::split::splitfile::RuneInfo};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SendfileUnchecked {
+  sends: Vec<SendUnchecked>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SendUnchecked {
+  address: Address<NetworkUnchecked>,
+  asset: Outgoing,
+}
+
+pub(crate) struct Sendfile {
+  pub(crate) sends: Vec<Send>,
+  pub(crate) rune_info: BTreeMap<Rune, RuneInfo>,
+}
+
+pub(crate) struct Send {
+  pub(crate) address: Address,
+  pub(crate) asset: Asset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Asset {
+  Amount(Amount),
+  Inscription(InscriptionId, SatPoint),
+  Rune(Rune, u128),
+  SatPoint(SatPoint),
+}
+
+impl Sendfile {
+  pub(crate) fn load(path: &Path, wallet: &Wallet) -> Result<Self> {
+    let network = wallet.chain().network();
+
+    let unchecked = Self::load_unchecked(path)?;
+
+    let inscription_info = wallet.inscription_info();
+
+    let mut rune_info = BTreeMap::<Rune, RuneInfo>::new();
+
+    let mut sends = Vec::new();
+
+    for send in unchecked.sends {
+      let asset = match send.asset {
+        Outgoing::Amount(amount) => Asset::Amount(amount),
+        Outgoing::InscriptionId(id) => Asset::Inscription(
+          id,
+          inscription_info
+            .get(&id)
+            .ok_or_else(|| anyhow!("inscription {id} not found"))?
+            .satpoint,
+        ),
+        Outgoing::Rune { decimal, rune } => {
+          ensure!(
+            wallet.has_rune_index(),
+            "sending runes with `ord wallet send-many` requires index created with `--index-runes`",
+          );
+
+          let info = if let Some(info) = rune_info.get(&rune.rune) {
+            info
+          } else {
+            let (id, entry, _parent) = wallet
+              .get_rune(rune.rune)?
+              .with_context(|| format!("rune `{}` has not been etched", rune.rune))?;
+            rune_info.insert(
+              rune.rune,
+              RuneInfo {
+                divisibility: entry.divisibility,
+                id,
+                spaced_rune: entry.spaced_rune,
+                symbol: entry.symbol,
+              },
+            );
+            rune_info.get(&rune.rune).unwrap()
+          };
+
+          Asset::Rune(rune.rune, decimal.to_integer(info.divisibility)?)
+        }
+        Outgoing::Sat(sat) => Asset::SatPoint(wallet.find_sat_in_outputs(sat)?),
+        Outgoing::SatPoint(satpoint) => Asset::SatPoint(satpoint),
+      };
+
+      sends.push(Send {
+        address: send.address.require_network(network)?,
+        asset,
+      });
+    }
+
+    Ok(Self { sends, rune_info })
+  }
+
+  fn load_unchecked(path: &Path) -> Result<SendfileUnchecked> {
+    if path.extension() == Some("csv".as_ref()) {
+      Self::parse_csv(&fs::read_to_string(path)?)
+    } else {
+      Ok(serde_yaml::from_reader(File::open(path)?)?)
+    }
+  }
+
+  fn parse_csv(csv: &str) -> Result<SendfileUnchecked> {
+    let mut sends = Vec::new();
+
+    for (i, line) in csv.lines().enumerate() {
+      let line = line.trim();
+
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+
+      let (asset, address) = line
+        .split_once(',')
+        .with_context(|| format!("line {}: expected `ASSET,ADDRESS`", i + 1))?;
+
+      sends.push(SendUnchecked {
+        address: address
+          .trim()
+          .parse()
+          .with_context(|| format!("line {}: invalid address", i + 1))?,
+        asset: asset
+          .trim()
+          .parse()
+          .with_context(|| format!("line {}: invalid asset", i + 1))?,
+      });
+    }
+
+    Ok(SendfileUnchecked { sends })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn example_send_file_is_valid() {
+    Sendfile::load_unchecked("sends.yaml".as_ref()).unwrap();
+  }
+
+  #[test]
+  fn csv_rows_are_asset_and_address() {
+    let sendfile = Sendfile::parse_csv(
+      "
+# airdrop
+1000 sat,bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+
+  5.5:FOO•BAR , bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+",
+    )
+    .unwrap();
+
+    assert_eq!(sendfile.sends.len(), 2);
+    assert_eq!(
+      sendfile.sends[0].asset,
+      Outgoing::Amount(Amount::from_sat(1000))
+    );
+    assert_eq!(
+      sendfile.sends[1].asset,
+      Outgoing::Rune {
+        decimal: "5.5".parse().unwrap(),
+        rune: "FOO•BAR".parse().unwrap(),
+      }
+    );
+  }
+
+  #[test]
+  fn csv_rows_must_have_address() {
+    assert_eq!(
+      Sendfile::parse_csv("1000 sat").unwrap_err().to_string(),
+      "line 1: expected `ASSET,ADDRESS`",
+    );
+  }
+}
@@ -6,11 +6,18 @@ pub(super) struct SharedArgs {
     long,
     help = "Use <COMMIT_FEE_RATE> sats/vbyte for commit transaction.\nDefaults to <FEE_RATE> if unset."
   )]
-  pub(crate) commit_fee_rate: Option<FeeRate>,
-  #[arg(long, help = "Compress inscription content with brotli.")]
-  pub(crate) compress: bool,
-  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
-  pub(crate) fee_rate: FeeRate,
+  pub(crate) commit_fee_rate: Option<FeeRateTarget>,
+  #[arg(
+    long,
+    value_name = "ALGORITHM",
+    num_args = 0..=1,
+    default_missing_value = "brotli",
+    help = "Compress inscription content with <ALGORITHM>, `brotli`, `gzip`, or `zstd`. \
+    Defaults to `brotli` if no algorithm is given."
+  )]
+  pub(crate) compress: Option<Compression>,
+  #[arg(long, help = FEE_RATE_HELP)]
+  pub(crate) fee_rate: FeeRateTarget,
   #[arg(long, help = "Don't sign or broadcast transactions.")]
   pub(crate) dry_run: bool,
   #[arg(long, alias = "nobackup", help = "Do not back up recovery key.")]
@@ -25,3 +32,16 @@ pub(super) struct SharedArgs {
   )]
   pub(crate) no_limit: bool,
 }
+
+impl SharedArgs {
+  pub(crate) fn fee_rates(&self, wallet: &Wallet) -> Result<(FeeRate, FeeRate)> {
+    let fee_rate = wallet.fee_rate(self.fee_rate)?;
+
+    let commit_fee_rate = match self.commit_fee_rate {
+      Some(commit_fee_rate) => wallet.fee_rate(commit_fee_rate)?,
+      None => fee_rate,
+    };
+
+    Ok((commit_fee_rate, fee_rate))
+  }
+}
@@ -1,6 +1,6 @@
 use {super::*, splitfile::Splitfile};
 
-mod splitfile;
+pub(super) mod splitfile;
 
 #[derive(Debug, PartialEq)]
 enum Error {
@@ -63,8 +63,8 @@ impl std::error::Error for Error {}
 pub(crate) struct Split {
   #[arg(long, help = "Don't sign or broadcast transaction")]
   pub(crate) dry_run: bool,
-  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
-  fee_rate: FeeRate,
+  #[arg(long, help = FEE_RATE_HELP)]
+  fee_rate: FeeRateTarget,
   #[arg(
     long,
     help = "Include <AMOUNT> postage with change output. [default: 10000 sat]",
@@ -101,6 +101,8 @@ impl Split {
       "`ord wallet split` requires index created with `--index-runes`",
     );
 
+    let fee_rate = wallet.fee_rate(self.fee_rate)?;
+
     wallet.lock_non_cardinal_outputs()?;
 
     let splits = Splitfile::load(&self.splits, &wallet)?;
@@ -138,11 +140,8 @@ impl Split {
       &splits,
     )?;
 
-    let unsigned_transaction = fund_raw_transaction(
-      wallet.bitcoin_client(),
-      self.fee_rate,
-      &unfunded_transaction,
-    )?;
+    let unsigned_transaction =
+      fund_raw_transaction(wallet.bitcoin_client(), fee_rate, &unfunded_transaction)?;
 
     let unsigned_transaction = consensus::encode::deserialize(&unsigned_transaction)?;
 
@@ -256,19 +255,21 @@ impl Split {
       }
     }
 
-    let runestone = Runestone {
-      edicts,
-      ..default()
+    let outputs = base + splits.outputs.len();
+
+    let runestone = match RunestoneBuilder::new()
+      .edicts(edicts)
+      .no_limit(no_runestone_limit)
+      .build(outputs)
+    {
+      Ok(runestone) => runestone,
+      Err(runestone_builder::Error::Size { size }) => return Err(Error::RunestoneSize { size }),
+      Err(err) => panic!("invalid runestone: {err}"),
     };
 
     let mut output = Vec::new();
 
     let runestone_script_pubkey = runestone.encipher();
-    let size = runestone_script_pubkey.len();
-
-    if !no_runestone_limit && size > MAX_STANDARD_OP_RETURN_SIZE {
-      return Err(Error::RunestoneSize { size });
-    }
 
     output.push(TxOut {
       script_pubkey: runestone_script_pubkey,
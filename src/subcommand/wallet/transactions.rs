@@ -1,34 +1,827 @@
-use super::*;
+use {super::*, crate::wallet::label::LabelType};
 
 #[derive(Debug, Parser)]
 pub(crate) struct Transactions {
   #[arg(long, help = "Fetch at most <LIMIT> transactions.")]
   limit: Option<u16>,
+  #[arg(long, help = "Print transactions as a table.")]
+  table: bool,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Output {
   pub transaction: Txid,
   pub confirmations: i32,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub fee: Option<u64>,
+  #[serde(default, skip_serializing_if = "Inscriptions::is_empty")]
+  pub inscriptions: Inscriptions,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub label: Option<String>,
+  #[serde(default, skip_serializing_if = "RareSats::is_empty")]
+  pub rare_sats: RareSats,
+  #[serde(default, skip_serializing_if = "Runes::is_empty")]
+  pub runes: Runes,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub unknown: Vec<OutPoint>,
 }
 
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Inscriptions {
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub created: Vec<InscriptionId>,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub received: Vec<InscriptionId>,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub sent: Vec<InscriptionId>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RareSats {
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub received: Vec<Sat>,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub sent: Vec<Sat>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Runes {
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub burned: BTreeMap<SpacedRune, Decimal>,
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub etched: BTreeMap<SpacedRune, Decimal>,
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub minted: BTreeMap<SpacedRune, Decimal>,
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub received: BTreeMap<SpacedRune, Decimal>,
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub sent: BTreeMap<SpacedRune, Decimal>,
+}
+
+#[derive(Default)]
+struct Issuance {
+  burned: BTreeMap<SpacedRune, u128>,
+  cenotaph: bool,
+  divisibility: BTreeMap<SpacedRune, u8>,
+  etched: BTreeMap<SpacedRune, u128>,
+  minted: BTreeMap<SpacedRune, u128>,
+}
+
+/// Rune entries fetched from the server, cached since a wallet's transactions
+/// usually involve the same few runes
+#[derive(Default)]
+struct RuneEntries(BTreeMap<RuneId, Option<RuneEntry>>);
+
 impl Transactions {
   pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
     let client = wallet.bitcoin_client();
 
+    let labels = wallet.labels()?;
+
+    let block_count = client.get_block_count()?;
+
+    let mut transactions = Vec::new();
+    for tx in self.list_transactions(client)? {
+      let info = client.get_transaction(&tx.info.txid, None)?;
+      let transaction = info.transaction()?;
+      transactions.push((tx.info, info.fee, transaction));
+    }
+
+    let mut history = wallet.output_history()?;
+
+    let inputs = transactions
+      .iter()
+      .filter(|(_info, _fee, transaction)| !transaction.is_coinbase())
+      .flat_map(|(_info, _fee, transaction)| &transaction.input)
+      .map(|input| input.previous_output)
+      .collect::<BTreeSet<OutPoint>>();
+
+    let indexed = wallet.get_outputs(
+      transactions
+        .iter()
+        .flat_map(|(info, _fee, transaction)| {
+          (0..transaction.output.len()).map(|vout| OutPoint {
+            txid: info.txid,
+            vout: vout.try_into().unwrap(),
+          })
+        })
+        .chain(inputs.iter().copied())
+        .filter(|outpoint| !history.contains_key(outpoint))
+        .collect::<BTreeSet<OutPoint>>()
+        .into_iter()
+        .collect(),
+    )?;
+
+    // unspent wallet outputs are recorded when the wallet is built, so only
+    // spent outputs which weren't recorded may have belonged to the wallet.
+    // their contents are recovered from the index if it hasn't processed the
+    // spend yet.
+    let mut unknown = BTreeSet::new();
+    let mut mine = BTreeMap::<Address<NetworkUnchecked>, bool>::new();
+
+    for (outpoint, output) in &indexed {
+      if !output.spent && !inputs.contains(outpoint) {
+        continue;
+      }
+
+      let Some(address) = &output.address else {
+        continue;
+      };
+
+      let is_mine = match mine.get(address) {
+        Some(is_mine) => *is_mine,
+        None => {
+          let is_mine = client
+            .get_address_info(&address.clone().assume_checked())?
+            .is_mine
+            .unwrap_or_default();
+          mine.insert(address.clone(), is_mine);
+          is_mine
+        }
+      };
+
+      if !is_mine {
+        continue;
+      }
+
+      if output.indexed {
+        history.insert(*outpoint, output.clone());
+      } else {
+        unknown.insert(*outpoint);
+      }
+    }
+
+    let mut rune_entries = RuneEntries::default();
+
     let mut output = Vec::new();
-    for tx in client.list_transactions(
-      None,
-      Some(self.limit.unwrap_or(u16::MAX).into()),
-      None,
-      None,
-    )? {
+    for (info, fee, transaction) in transactions {
+      let txid = info.txid;
+
+      let outpoints = (0..transaction.output.len())
+        .map(|vout| OutPoint {
+          txid,
+          vout: vout.try_into().unwrap(),
+        })
+        .collect::<Vec<OutPoint>>();
+
+      let inputs = transaction
+        .input
+        .iter()
+        .filter_map(|input| history.get(&input.previous_output))
+        .collect::<Vec<&api::Output>>();
+
+      let outputs = outpoints
+        .iter()
+        .filter_map(|outpoint| history.get(outpoint))
+        .collect::<Vec<&api::Output>>();
+
+      let fee = if !inputs.is_empty() && inputs.len() == transaction.input.len() {
+        inputs
+          .iter()
+          .map(|input| input.value)
+          .sum::<u64>()
+          .checked_sub(
+            transaction
+              .output
+              .iter()
+              .map(|output| output.value.to_sat())
+              .sum(),
+          )
+      } else {
+        fee.map(|fee| fee.unsigned_abs().to_sat())
+      };
+
+      let issuance = if wallet.has_rune_index() {
+        let mut issuance = Issuance::load(
+          &wallet,
+          &mut rune_entries,
+          txid,
+          &transaction,
+          block_count,
+          info.confirmations,
+        )?;
+
+        // the runes held by every output of a confirmed transaction show
+        // whether its mint succeeded, unless some have since been spent
+        if info.confirmations > 0 {
+          let balances = transaction
+            .output
+            .iter()
+            .zip(&outpoints)
+            .filter(|(tx_out, _outpoint)| !tx_out.script_pubkey.is_op_return())
+            .map(|(_tx_out, outpoint)| {
+              history
+                .get(outpoint)
+                .or_else(|| indexed.get(outpoint).filter(|output| output.indexed))
+            })
+            .collect::<Option<Vec<&api::Output>>>();
+
+          if let Some(balances) = balances {
+            issuance.verify_mints(&inputs, &balances);
+          }
+        }
+
+        issuance
+      } else {
+        Issuance::default()
+      };
+
       output.push(Output {
-        transaction: tx.info.txid,
-        confirmations: tx.info.confirmations,
+        transaction: txid,
+        confirmations: info.confirmations,
+        fee,
+        inscriptions: Inscriptions::new(txid, &transaction, &inputs, &outputs),
+        label: labels
+          .get(&(LabelType::Tx, txid.to_string()))
+          .and_then(|label| label.label.clone()),
+        rare_sats: if wallet.has_sat_index() {
+          RareSats::new(&inputs, &outputs)
+        } else {
+          RareSats::default()
+        },
+        runes: Runes::new(issuance, &inputs, &outputs),
+        unknown: transaction
+          .input
+          .iter()
+          .map(|input| input.previous_output)
+          .chain(outpoints)
+          .filter(|outpoint| unknown.contains(outpoint))
+          .collect(),
       });
     }
 
+    if self.table {
+      println!(
+        "{:<64}  {:>13}  {:>8}  {:<16}  activity",
+        "transaction", "confirmations", "fee", "label"
+      );
+
+      for output in output {
+        println!(
+          "{:<64}  {:>13}  {:>8}  {:<16}  {}",
+          output.transaction,
+          output.confirmations,
+          output
+            .fee
+            .map(|fee| fee.to_string())
+            .unwrap_or_else(|| "-".into()),
+          output.label.as_deref().unwrap_or("-"),
+          output.activity(),
+        );
+      }
+
+      return Ok(None);
+    }
+
     Ok(Some(Box::new(output)))
   }
+
+  /// List the most recent `--limit` transactions, oldest first. Bitcoin Core
+  /// lists a transaction once for each wallet address it involves, so pages
+  /// are fetched until enough distinct transactions have been found.
+  fn list_transactions(
+    &self,
+    client: &Client,
+  ) -> Result<Vec<bitcoincore_rpc::json::ListTransactionResult>> {
+    let limit = self.limit.map_or(usize::MAX, usize::from);
+    let count = usize::from(self.limit.unwrap_or(u16::MAX));
+
+    let mut seen = HashSet::new();
+    let mut transactions = Vec::new();
+    let mut skip = 0;
+
+    loop {
+      let page = client.list_transactions(None, Some(count), Some(skip), None)?;
+
+      let len = page.len();
+
+      skip += len;
+
+      // pages list transactions oldest first
+      for tx in page.into_iter().rev() {
+        if transactions.len() < limit && seen.insert(tx.info.txid) {
+          transactions.push(tx);
+        }
+      }
+
+      if transactions.len() >= limit || len < count {
+        break;
+      }
+    }
+
+    transactions.reverse();
+
+    Ok(transactions)
+  }
+}
+
+impl Output {
+  fn activity(&self) -> String {
+    fn list<T: Display>(items: impl IntoIterator<Item = T>) -> String {
+      items
+        .into_iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+    }
+
+    fn runes(balances: &BTreeMap<SpacedRune, Decimal>) -> String {
+      list(
+        balances
+          .iter()
+          .map(|(rune, amount)| format!("{amount}:{rune}")),
+      )
+    }
+
+    let mut activity = Vec::new();
+
+    for (action, ids) in [
+      ("created", &self.inscriptions.created),
+      ("received", &self.inscriptions.received),
+      ("sent", &self.inscriptions.sent),
+    ] {
+      if !ids.is_empty() {
+        activity.push(format!("inscriptions {action}: {}", list(ids)));
+      }
+    }
+
+    for (action, balances) in [
+      ("etched", &self.runes.etched),
+      ("minted", &self.runes.minted),
+      ("received", &self.runes.received),
+      ("sent", &self.runes.sent),
+      ("burned", &self.runes.burned),
+    ] {
+      if !balances.is_empty() {
+        activity.push(format!("runes {action}: {}", runes(balances)));
+      }
+    }
+
+    for (action, sats) in [
+      ("received", &self.rare_sats.received),
+      ("sent", &self.rare_sats.sent),
+    ] {
+      if !sats.is_empty() {
+        activity.push(format!(
+          "rare sats {action}: {}",
+          list(sats.iter().map(|sat| sat.name()))
+        ));
+      }
+    }
+
+    if !self.unknown.is_empty() {
+      activity.push(format!("unknown outputs: {}", list(&self.unknown)));
+    }
+
+    if activity.is_empty() {
+      "-".into()
+    } else {
+      activity.join("; ")
+    }
+  }
+}
+
+impl Inscriptions {
+  fn new(
+    txid: Txid,
+    transaction: &Transaction,
+    inputs: &[&api::Output],
+    outputs: &[&api::Output],
+  ) -> Self {
+    let spent = inputs
+      .iter()
+      .flat_map(|input| input.inscriptions.iter().flatten().copied())
+      .collect::<BTreeSet<InscriptionId>>();
+
+    let kept = outputs
+      .iter()
+      .flat_map(|output| output.inscriptions.iter().flatten().copied())
+      .collect::<BTreeSet<InscriptionId>>();
+
+    let created = (0..ParsedEnvelope::from_transaction(transaction).len())
+      .map(|index| InscriptionId {
+        txid,
+        index: index.try_into().unwrap(),
+      })
+      .collect::<Vec<InscriptionId>>();
+
+    Self {
+      received: kept
+        .difference(&spent)
+        .filter(|id| !created.contains(id))
+        .copied()
+        .collect(),
+      sent: spent.difference(&kept).copied().collect(),
+      created,
+    }
+  }
+
+  fn is_empty(&self) -> bool {
+    self.created.is_empty() && self.received.is_empty() && self.sent.is_empty()
+  }
+}
+
+impl RareSats {
+  fn new(inputs: &[&api::Output], outputs: &[&api::Output]) -> Self {
+    fn rare(outputs: &[&api::Output]) -> BTreeSet<Sat> {
+      outputs
+        .iter()
+        .flat_map(|output| output.sat_ranges.iter().flatten())
+        .map(|(start, _end)| Sat(*start))
+        .filter(|sat| !sat.common())
+        .collect()
+    }
+
+    let spent = rare(inputs);
+    let kept = rare(outputs);
+
+    Self {
+      received: kept.difference(&spent).copied().collect(),
+      sent: spent.difference(&kept).copied().collect(),
+    }
+  }
+
+  fn is_empty(&self) -> bool {
+    self.received.is_empty() && self.sent.is_empty()
+  }
+}
+
+impl RuneEntries {
+  fn get(&mut self, wallet: &Wallet, id: RuneId) -> Result<Option<RuneEntry>> {
+    if let Some(entry) = self.0.get(&id) {
+      return Ok(*entry);
+    }
+
+    let entry = wallet
+      .get_rune_by_id(id)?
+      .map(|(_id, entry, _parent)| entry);
+
+    self.0.insert(id, entry);
+
+    Ok(entry)
+  }
+}
+
+impl Issuance {
+  fn load(
+    wallet: &Wallet,
+    rune_entries: &mut RuneEntries,
+    txid: Txid,
+    transaction: &Transaction,
+    block_count: u64,
+    confirmations: i32,
+  ) -> Result<Self> {
+    let mut issuance = Self::default();
+
+    let Some(artifact) = Runestone::decipher(transaction) else {
+      return Ok(issuance);
+    };
+
+    let mut etched = None;
+
+    if let Artifact::Runestone(Runestone {
+      etching: Some(_), ..
+    }) = artifact
+    {
+      if let Some(spaced_rune) = wallet.get_etching(txid)? {
+        if let Some((id, entry, _parent)) = wallet.get_rune(spaced_rune.rune)? {
+          rune_entries.0.insert(id, Some(entry));
+          issuance
+            .divisibility
+            .insert(entry.spaced_rune, entry.divisibility);
+          issuance.etched.insert(entry.spaced_rune, entry.premine);
+          etched = Some((id, entry.spaced_rune));
+        }
+      }
+    }
+
+    // mints in cenotaphs are burned, and mints outside of the rune's mint
+    // terms mint nothing. the cap is only checked for unconfirmed mints, since
+    // the rune entry already counts confirmed ones.
+    if let (Some(id), Artifact::Runestone(_)) = (artifact.mint(), &artifact) {
+      if let Some(entry) = rune_entries.get(wallet, id)? {
+        let mintable = match u64::try_from(confirmations) {
+          Ok(0) | Err(_) => entry.mintable(block_count + 1),
+          Ok(confirmations) => RuneEntry { mints: 0, ..entry }
+            .mintable((block_count + 1).saturating_sub(confirmations)),
+        };
+
+        if let Ok(amount) = mintable {
+          issuance
+            .divisibility
+            .insert(entry.spaced_rune, entry.divisibility);
+          issuance.minted.insert(entry.spaced_rune, amount);
+        }
+      }
+    }
+
+    match artifact {
+      Artifact::Cenotaph(_) => issuance.cenotaph = true,
+      Artifact::Runestone(runestone) => {
+        for edict in runestone.edicts {
+          let burn = transaction
+            .output
+            .get(usize::try_from(edict.output).unwrap())
+            .is_some_and(|output| output.script_pubkey.is_op_return());
+
+          if !burn || edict.amount == 0 {
+            continue;
+          }
+
+          let spaced_rune = if edict.id == RuneId::default() {
+            etched.map(|(_id, spaced_rune)| spaced_rune)
+          } else {
+            rune_entries.get(wallet, edict.id)?.map(|entry| {
+              issuance
+                .divisibility
+                .insert(entry.spaced_rune, entry.divisibility);
+              entry.spaced_rune
+            })
+          };
+
+          if let Some(spaced_rune) = spaced_rune {
+            *issuance.burned.entry(spaced_rune).or_default() += edict.amount;
+          }
+        }
+      }
+    }
+
+    Ok(issuance)
+  }
+
+  /// Drop mints which didn't increase the runes held by `outputs`, every
+  /// output of the transaction, beyond those spent from `inputs`, as happens
+  /// when a mint is confirmed after the rune's cap was reached
+  fn verify_mints(&mut self, inputs: &[&api::Output], outputs: &[&api::Output]) {
+    let spent = balances(inputs);
+    let held = balances(outputs);
+
+    fn get(balances: &BTreeMap<SpacedRune, u128>, spaced_rune: &SpacedRune) -> u128 {
+      balances.get(spaced_rune).copied().unwrap_or_default()
+    }
+
+    self.minted.retain(|spaced_rune, amount| {
+      get(&held, spaced_rune) + get(&self.burned, spaced_rune)
+        >= get(&spent, spaced_rune) + get(&self.etched, spaced_rune) + *amount
+    });
+  }
+}
+
+fn balances(outputs: &[&api::Output]) -> BTreeMap<SpacedRune, u128> {
+  let mut balances = BTreeMap::<SpacedRune, u128>::new();
+
+  for (spaced_rune, pile) in outputs
+    .iter()
+    .flat_map(|output| output.runes.iter().flatten())
+  {
+    *balances.entry(*spaced_rune).or_default() += pile.amount;
+  }
+
+  balances
+}
+
+impl Runes {
+  fn new(mut issuance: Issuance, inputs: &[&api::Output], outputs: &[&api::Output]) -> Self {
+    let mut balances = |outputs: &[&api::Output]| {
+      let mut balances = BTreeMap::<SpacedRune, u128>::new();
+
+      for (spaced_rune, pile) in outputs
+        .iter()
+        .flat_map(|output| output.runes.iter().flatten())
+      {
+        issuance
+          .divisibility
+          .insert(*spaced_rune, pile.divisibility);
+        *balances.entry(*spaced_rune).or_default() += pile.amount;
+      }
+
+      balances
+    };
+
+    let spent = balances(inputs);
+    let kept = balances(outputs);
+
+    // cenotaphs burn all input runes
+    if issuance.cenotaph {
+      for (spaced_rune, amount) in &spent {
+        *issuance.burned.entry(*spaced_rune).or_default() += amount;
+      }
+    }
+
+    let mut received = BTreeMap::new();
+    let mut sent = BTreeMap::new();
+
+    for spaced_rune in spent
+      .keys()
+      .chain(kept.keys())
+      .chain(issuance.etched.keys())
+      .chain(issuance.minted.keys())
+      .collect::<BTreeSet<&SpacedRune>>()
+    {
+      let get = |balances: &BTreeMap<SpacedRune, u128>| {
+        balances.get(spaced_rune).copied().unwrap_or_default()
+      };
+
+      let inflow = get(&kept);
+      let outflow = get(&spent) + get(&issuance.etched) + get(&issuance.minted);
+
+      if inflow > outflow {
+        received.insert(*spaced_rune, inflow - outflow);
+      } else {
+        let amount = (outflow - inflow).saturating_sub(get(&issuance.burned));
+        if amount > 0 {
+          sent.insert(*spaced_rune, amount);
+        }
+      }
+    }
+
+    let decimals = |balances: BTreeMap<SpacedRune, u128>| {
+      balances
+        .into_iter()
+        .filter(|(_spaced_rune, amount)| *amount > 0)
+        .map(|(spaced_rune, value)| {
+          (
+            spaced_rune,
+            Decimal {
+              value,
+              scale: issuance
+                .divisibility
+                .get(&spaced_rune)
+                .copied()
+                .unwrap_or_default(),
+            },
+          )
+        })
+        .collect()
+    };
+
+    Self {
+      burned: decimals(issuance.burned.clone()),
+      etched: decimals(issuance.etched.clone()),
+      minted: decimals(issuance.minted.clone()),
+      received: decimals(received),
+      sent: decimals(sent),
+    }
+  }
+
+  fn is_empty(&self) -> bool {
+    self.burned.is_empty()
+      && self.etched.is_empty()
+      && self.minted.is_empty()
+      && self.received.is_empty()
+      && self.sent.is_empty()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn output(
+    n: u32,
+    runes: Option<BTreeMap<SpacedRune, Pile>>,
+    sat_ranges: Option<Vec<(u64, u64)>>,
+  ) -> api::Output {
+    api::Output::new(
+      Chain::Mainnet,
+      None,
+      outpoint(n),
+      tx_out(1000, address(0)),
+      true,
+      runes,
+      sat_ranges,
+      false,
+    )
+  }
+
+  fn pile(amount: u128) -> Pile {
+    Pile {
+      amount,
+      divisibility: 1,
+      symbol: None,
+    }
+  }
+
+  #[test]
+  fn rare_sats_moved() {
+    let input = output(1, None, Some(vec![(0, 1000)]));
+    let change = output(2, None, Some(vec![(1000, 2000)]));
+    let received = output(
+      3,
+      None,
+      Some(vec![(50 * COIN_VALUE, 50 * COIN_VALUE + 1000)]),
+    );
+
+    assert_eq!(
+      RareSats::new(&[&input], &[&change, &received]),
+      RareSats {
+        received: vec![Sat(50 * COIN_VALUE)],
+        sent: vec![Sat(0)],
+      },
+    );
+  }
+
+  #[test]
+  fn rune_balances_are_split_into_minted_received_and_sent() {
+    let foo = SpacedRune {
+      rune: Rune(0),
+      spacers: 0,
+    };
+
+    let bar = SpacedRune {
+      rune: Rune(1),
+      spacers: 0,
+    };
+
+    let input = output(1, Some([(foo, pile(100))].into()), None);
+    let change = output(2, Some([(foo, pile(40)), (bar, pile(15))].into()), None);
+
+    assert_eq!(
+      Runes::new(
+        Issuance {
+          minted: [(bar, 10)].into(),
+          ..default()
+        },
+        &[&input],
+        &[&change],
+      ),
+      Runes {
+        minted: [(
+          bar,
+          Decimal {
+            value: 10,
+            scale: 1
+          }
+        )]
+        .into(),
+        received: [(bar, Decimal { value: 5, scale: 1 })].into(),
+        sent: [(
+          foo,
+          Decimal {
+            value: 60,
+            scale: 1
+          }
+        )]
+        .into(),
+        ..default()
+      },
+    );
+  }
+
+  #[test]
+  fn cenotaphs_burn_input_runes() {
+    let foo = SpacedRune {
+      rune: Rune(0),
+      spacers: 0,
+    };
+
+    let input = output(1, Some([(foo, pile(100))].into()), None);
+
+    assert_eq!(
+      Runes::new(
+        Issuance {
+          cenotaph: true,
+          ..default()
+        },
+        &[&input],
+        &[],
+      ),
+      Runes {
+        burned: [(
+          foo,
+          Decimal {
+            value: 100,
+            scale: 1
+          }
+        )]
+        .into(),
+        ..default()
+      },
+    );
+  }
+
+  #[test]
+  fn mints_which_did_not_increase_balances_are_dropped() {
+    let foo = SpacedRune {
+      rune: Rune(0),
+      spacers: 0,
+    };
+
+    let bar = SpacedRune {
+      rune: Rune(1),
+      spacers: 0,
+    };
+
+    let input = output(1, Some([(foo, pile(100))].into()), None);
+    let change = output(2, Some([(foo, pile(100)), (bar, pile(10))].into()), None);
+
+    let mut issuance = Issuance {
+      minted: [(foo, 10), (bar, 10)].into(),
+      ..default()
+    };
+
+    issuance.verify_mints(&[&input], &[&change]);
+
+    assert_eq!(issuance.minted, [(bar, 10)].into());
+  }
 }
@@ -25,8 +25,8 @@ mod tests {
       .unindent(),
       "<h1>Latest Inscriptions</h1>
       <div class=thumbnails>
-        <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
-        <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+        <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1/thumbnail></iframe></a>
+        <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2/thumbnail></iframe></a>
       </div>
       "
       .unindent(),
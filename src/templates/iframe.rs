@@ -27,7 +27,7 @@ impl Display for Iframe {
       write!(
         f,
         "<a href=/inscription/{}>\
-          <iframe sandbox=allow-scripts scrolling=no loading=lazy src=/preview/{}>\
+          <iframe sandbox=allow-scripts scrolling=no loading=lazy src=/preview/{}/thumbnail>\
           </iframe>\
         </a>",
         self.inscription_id, self.inscription_id,
@@ -51,7 +51,7 @@ mod tests {
     assert_regex_match!(
       Iframe::thumbnail(inscription_id(1))
       .0.to_string(),
-      "<a href=/inscription/1{64}i1><iframe sandbox=allow-scripts scrolling=no loading=lazy src=/preview/1{64}i1></iframe></a>",
+      "<a href=/inscription/1{64}i1><iframe sandbox=allow-scripts scrolling=no loading=lazy src=/preview/1{64}i1/thumbnail></iframe></a>",
     );
   }
 
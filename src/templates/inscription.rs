@@ -252,7 +252,7 @@ mod tests {
           <dt>parents</dt>
           <dd>
             <div class=thumbnails>
-              <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+              <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2/thumbnail></iframe></a>
             </div>
             <div class=center>
               <a href=/parents/1{64}i1>all</a>
@@ -322,8 +322,8 @@ mod tests {
           <dt>children</dt>
           <dd>
             <div class=thumbnails>
-              <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
-              <a href=/inscription/3{64}i3><iframe .* src=/preview/3{64}i3></iframe></a>
+              <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2/thumbnail></iframe></a>
+              <a href=/inscription/3{64}i3><iframe .* src=/preview/3{64}i3/thumbnail></iframe></a>
             </div>
             <div class=center>
               <a href=/children/1{64}i1>all \\(2\\)</a>
@@ -393,7 +393,7 @@ mod tests {
           <dt>children</dt>
           <dd>
             <div class=thumbnails>
-              <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+              <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2/thumbnail></iframe></a>
             </div>
             <div class=center>
               <a href=/children/1{64}i1>all \\(1\\)</a>
@@ -20,6 +20,17 @@ pub(crate) struct PreviewFontHtml {
 pub(crate) struct PreviewImageHtml {
   pub(crate) image_rendering: ImageRendering,
   pub(crate) inscription_id: InscriptionId,
+  pub(crate) thumbnail: bool,
+}
+
+impl PreviewImageHtml {
+  fn src(&self) -> String {
+    if self.thumbnail {
+      format!("/thumbnail/{}", self.inscription_id)
+    } else {
+      format!("/content/{}", self.inscription_id)
+    }
+  }
 }
 
 #[derive(Boilerplate)]
@@ -74,7 +74,7 @@ mod tests {
         }),
       },
       "<h1>B•CGDENLQRQWDSLRUGSNLBTMFIJAV</h1>
-.*<a href=/inscription/.*<iframe .* src=/preview/0{64}i0></iframe></a>.*
+.*<a href=/inscription/.*<iframe .* src=/preview/0{64}i0/thumbnail></iframe></a>.*
 <dl>
   <dt>number</dt>
   <dd>25</dd>
@@ -12,9 +12,13 @@ use {
   fee_rate::FeeRate,
   index::entry::Entry,
   indicatif::{ProgressBar, ProgressStyle},
+  label::{Label, LabelType},
   log::log_enabled,
   miniscript::descriptor::{DescriptorSecretKey, DescriptorXKey, Wildcard},
-  redb::{Database, DatabaseError, ReadableTable, RepairSession, StorageError, TableDefinition},
+  redb::{
+    Database, DatabaseError, ReadableTable, RepairSession, StorageError, TableDefinition,
+    TableError,
+  },
   reqwest::header,
   std::sync::Once,
   transaction_builder::TransactionBuilder,
@@ -22,11 +26,14 @@ use {
 
 pub mod batch;
 pub mod entry;
+pub mod label;
 pub mod transaction_builder;
 pub mod wallet_constructor;
 
 const SCHEMA_VERSION: u64 = 1;
 
+define_table! { LABELS, &str, &str }
+define_table! { OUTPOINT_TO_OUTPUT_INFO, &str, &str }
 define_table! { RUNE_TO_ETCHING, u128, EtchingEntryValue }
 define_table! { STATISTICS, u64, u64 }
 
@@ -282,15 +289,24 @@ impl Wallet {
   pub(crate) fn get_rune(
     &self,
     rune: Rune,
+  ) -> Result<Option<(RuneId, RuneEntry, Option<InscriptionId>)>> {
+    self.get_rune_by_query(&SpacedRune { rune, spacers: 0 }.to_string())
+  }
+
+  pub(crate) fn get_rune_by_id(
+    &self,
+    id: RuneId,
+  ) -> Result<Option<(RuneId, RuneEntry, Option<InscriptionId>)>> {
+    self.get_rune_by_query(&id.to_string())
+  }
+
+  fn get_rune_by_query(
+    &self,
+    query: &str,
   ) -> Result<Option<(RuneId, RuneEntry, Option<InscriptionId>)>> {
     let response = self
       .ord_client
-      .get(
-        self
-          .rpc_url
-          .join(&format!("/rune/{}", SpacedRune { rune, spacers: 0 }))
-          .unwrap(),
-      )
+      .get(self.rpc_url.join(&format!("/rune/{query}")).unwrap())
       .send()?;
 
     if response.status() == StatusCode::NOT_FOUND {
@@ -304,6 +320,44 @@ impl Wallet {
     Ok(Some((rune_json.id, rune_json.entry, rune_json.parent)))
   }
 
+  pub(crate) fn get_etching(&self, txid: Txid) -> Result<Option<SpacedRune>> {
+    let response = self
+      .ord_client
+      .get(self.rpc_url.join(&format!("/tx/{txid}")).unwrap())
+      .send()?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+
+    let response = response.error_for_status()?;
+
+    Ok(serde_json::from_str::<api::Transaction>(&response.text()?)?.etching)
+  }
+
+  pub(crate) fn get_outputs(
+    &self,
+    outputs: Vec<OutPoint>,
+  ) -> Result<BTreeMap<OutPoint, api::Output>> {
+    if outputs.is_empty() {
+      return Ok(BTreeMap::new());
+    }
+
+    let response = self
+      .ord_client
+      .post(self.rpc_url.join("/outputs").unwrap())
+      .json(&outputs)
+      .send()?
+      .error_for_status()?;
+
+    Ok(
+      outputs
+        .into_iter()
+        .zip(serde_json::from_str::<Vec<api::Output>>(&response.text()?)?)
+        .collect(),
+    )
+  }
+
   pub(crate) fn get_change_address(&self) -> Result<Address> {
     Ok(
       self
@@ -322,10 +376,18 @@ impl Wallet {
     self.has_rune_index
   }
 
+  pub(crate) fn fee_rate(&self, target: FeeRateTarget) -> Result<FeeRate> {
+    target.resolve(&self.bitcoin_client, self.settings.max_fee_rate())
+  }
+
   pub(crate) fn chain(&self) -> Chain {
     self.settings.chain()
   }
 
+  pub(crate) fn media_types(&self) -> &[MediaType] {
+    self.settings.media_types()
+  }
+
   pub(crate) fn integration_test(&self) -> bool {
     self.settings.integration_test()
   }
@@ -684,6 +746,8 @@ impl Wallet {
         let mut tx = database.begin_write()?;
         tx.set_quick_repair(true);
 
+        tx.open_table(LABELS)?;
+        tx.open_table(OUTPOINT_TO_OUTPUT_INFO)?;
         tx.open_table(RUNE_TO_ETCHING)?;
 
         tx.open_table(STATISTICS)?
@@ -759,6 +823,119 @@ impl Wallet {
     )
   }
 
+  pub(crate) fn import_labels(&self, labels: &[Label]) -> Result {
+    let mut wtx = self.database.begin_write()?;
+    wtx.set_quick_repair(true);
+
+    {
+      let mut table = wtx.open_table(LABELS)?;
+
+      for label in labels {
+        table.insert(label.key().as_str(), serde_json::to_string(label)?.as_str())?;
+      }
+    }
+
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn labels(&self) -> Result<BTreeMap<(LabelType, String), Label>> {
+    let rtx = self.database.begin_read()?;
+
+    // wallets created before labels were supported do not have a labels table
+    let table = match rtx.open_table(LABELS) {
+      Ok(table) => table,
+      Err(TableError::TableDoesNotExist(_)) => return Ok(BTreeMap::new()),
+      Err(err) => return Err(err.into()),
+    };
+
+    let mut labels = BTreeMap::new();
+
+    for result in table.iter()? {
+      let (_key, value) = result?;
+      let label = serde_json::from_str::<Label>(value.value())?;
+      labels.insert((label.r#type, label.r#ref.clone()), label);
+    }
+
+    Ok(labels)
+  }
+
+  /// Record the contents of the wallet's unspent outputs, so that the
+  /// inscriptions, runes, and sats they contained are still known after they
+  /// are spent and removed from the index. Called when the wallet is built,
+  /// before any command can spend them, so only outputs received and spent
+  /// without running ord in between are not recorded.
+  pub(crate) fn save_output_info(&self) -> Result {
+    let new = {
+      let rtx = self.database.begin_read()?;
+
+      let table = match rtx.open_table(OUTPOINT_TO_OUTPUT_INFO) {
+        Ok(table) => Some(table),
+        Err(TableError::TableDoesNotExist(_)) => None,
+        Err(err) => return Err(err.into()),
+      };
+
+      let mut new = Vec::new();
+
+      for (outpoint, info) in &self.output_info {
+        if let Some(table) = &table {
+          if table.get(outpoint.to_string().as_str())?.is_some() {
+            continue;
+          }
+        }
+
+        new.push((outpoint, info));
+      }
+
+      new
+    };
+
+    if new.is_empty() {
+      return Ok(());
+    }
+
+    let mut wtx = self.database.begin_write()?;
+    wtx.set_quick_repair(true);
+
+    {
+      let mut table = wtx.open_table(OUTPOINT_TO_OUTPUT_INFO)?;
+
+      for (outpoint, info) in new {
+        table.insert(
+          outpoint.to_string().as_str(),
+          serde_json::to_string(info)?.as_str(),
+        )?;
+      }
+    }
+
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn output_history(&self) -> Result<BTreeMap<OutPoint, api::Output>> {
+    let rtx = self.database.begin_read()?;
+
+    let table = match rtx.open_table(OUTPOINT_TO_OUTPUT_INFO) {
+      Ok(table) => table,
+      Err(TableError::TableDoesNotExist(_)) => return Ok(BTreeMap::new()),
+      Err(err) => return Err(err.into()),
+    };
+
+    let mut history = BTreeMap::new();
+
+    for result in table.iter()? {
+      let (key, value) = result?;
+      history.insert(
+        key.value().parse()?,
+        serde_json::from_str::<api::Output>(value.value())?,
+      );
+    }
+
+    Ok(history)
+  }
+
   pub(super) fn sign_and_broadcast_transaction(
     &self,
     unsigned_transaction: Transaction,
@@ -4,12 +4,14 @@ use super::*;
 #[derive(Serialize, Deserialize, Default, PartialEq, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct Entry {
+  pub compress: Option<Compression>,
   pub file: Option<PathBuf>,
   pub delegate: Option<InscriptionId>,
   pub satpoint: Option<SatPoint>,
   pub destination: Option<Address<NetworkUnchecked>>,
   pub metadata: Option<serde_yaml::Value>,
   pub metaprotocol: Option<String>,
+  pub properties: Option<Properties>,
 }
 
 impl Entry {
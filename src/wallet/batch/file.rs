@@ -14,6 +14,7 @@ pub struct File {
   pub satpoint: Option<SatPoint>,
   pub inscriptions: Vec<batch::entry::Entry>,
   pub etching: Option<batch::Etching>,
+  pub metadata_schema: Option<PathBuf>,
 }
 
 impl File {
@@ -102,15 +103,63 @@ impl File {
       }
     }
 
+    if let Some(schema) = &batchfile.metadata_schema {
+      batchfile.validate_metadata(schema)?;
+    }
+
     Ok(batchfile)
   }
 
+  /// Check the metadata of every entry against the JSON Schema at `path`,
+  /// reporting all violations at once. Entries without metadata are checked as
+  /// `null`, so schemas which require an object also require metadata.
+  fn validate_metadata(&self, path: &Path) -> Result {
+    let schema: serde_json::Value = serde_yaml::from_reader(
+      fs::File::open(path)
+        .with_context(|| format!("failed to open metadata schema `{}`", path.display()))?,
+    )
+    .with_context(|| format!("failed to parse metadata schema `{}`", path.display()))?;
+
+    let validator = jsonschema::validator_for(&schema)
+      .map_err(|err| anyhow!("invalid metadata schema `{}`: {err}", path.display()))?;
+
+    let mut errors = Vec::new();
+
+    for (i, entry) in self.inscriptions.iter().enumerate() {
+      let metadata = match serde_json::to_value(&entry.metadata) {
+        Ok(metadata) => metadata,
+        Err(err) => {
+          errors.push(format!("inscription {i}: {err}"));
+          continue;
+        }
+      };
+
+      for error in validator.iter_errors(&metadata) {
+        let location = error.instance_path.to_string();
+
+        errors.push(format!(
+          "inscription {i} at `{}`: {error}",
+          if location.is_empty() { "/" } else { &location },
+        ));
+      }
+    }
+
+    ensure!(
+      errors.is_empty(),
+      "metadata does not satisfy schema `{}`:\n{}",
+      path.display(),
+      errors.join("\n"),
+    );
+
+    Ok(())
+  }
+
   pub(crate) fn inscriptions(
     &self,
     wallet: &Wallet,
     utxos: &BTreeMap<OutPoint, TxOut>,
     parent_values: Vec<u64>,
-    compress: bool,
+    compress: Option<Compression>,
   ) -> Result<(
     Vec<Inscription>,
     Vec<(SatPoint, TxOut)>,
@@ -133,13 +182,15 @@ impl File {
 
       inscriptions.push(Inscription::new(
         wallet.chain(),
-        compress,
+        entry.compress.or(compress),
         entry.delegate,
+        wallet.media_types(),
         entry.metadata()?,
         entry.metaprotocol.clone(),
         self.parents.clone(),
         entry.file.clone(),
         Some(pointer),
+        entry.properties.clone(),
         self
           .etching
           .and_then(|etch| (i == 0).then_some(etch.rune.rune)),
@@ -393,6 +444,7 @@ inscriptions:
           }),
           turbo: true,
         }),
+        metadata_schema: None,
         inscriptions: vec![
           batch::Entry {
             file: Some("mango.avif".into()),
@@ -423,6 +475,7 @@ inscriptions:
             ..default()
           },
           batch::Entry {
+            compress: Some(Compression::Gzip),
             file: Some("token.json".into()),
             metaprotocol: Some("DOPEPROTOCOL-42069".into()),
             ..default()
@@ -439,6 +492,21 @@ inscriptions:
               mapping.insert("author".into(), "Satoshi Nakamoto".into());
               mapping
             })),
+            properties: Some(Properties {
+              attributes: properties::Attributes {
+                title: Some("Tulip".into()),
+                traits: [("color".into(), properties::Trait::String("red".into()))].into(),
+              },
+              gallery: vec![properties::Item {
+                id: "6ac5cacb768794f4fd7a78bf00f2074891fce68bd65c4ff36e77177237aacacai0"
+                  .parse()
+                  .unwrap(),
+                attributes: properties::Attributes {
+                  title: Some("Mango".into()),
+                  traits: BTreeMap::new(),
+                },
+              }],
+            }),
             ..default()
           },
         ],
@@ -462,4 +530,102 @@ inscriptions:
 
     assert!(batch::File::load(batch_file.as_path()).is_ok());
   }
+
+  #[test]
+  fn metadata_is_validated_against_schema() {
+    let tempdir = TempDir::new().unwrap();
+
+    let schema = tempdir.path().join("schema.json");
+
+    fs::write(
+      &schema,
+      r#"{
+  "type": "object",
+  "properties": {
+    "color": { "enum": ["red", "blue"] },
+    "level": { "type": "integer" }
+  },
+  "required": ["color"]
+}"#,
+    )
+    .unwrap();
+
+    let batch_file = tempdir.path().join("batch.yaml");
+
+    fs::write(
+      &batch_file,
+      format!(
+        "
+mode: separate-outputs
+metadata_schema: {}
+inscriptions:
+- file: a.txt
+  metadata:
+    color: red
+    level: 1
+- file: b.txt
+  metadata:
+    color: green
+    level: high
+- file: c.txt
+",
+        schema.display()
+      ),
+    )
+    .unwrap();
+
+    assert_eq!(
+      batch::File::load(&batch_file).unwrap_err().to_string(),
+      format!(
+        "metadata does not satisfy schema `{}`:
+inscription 1 at `/color`: \"green\" is not one of \"red\" or \"blue\"
+inscription 1 at `/level`: \"high\" is not of type \"integer\"
+inscription 2 at `/`: null is not of type \"object\"",
+        schema.display()
+      ),
+    );
+
+    fs::write(
+      &batch_file,
+      format!(
+        "
+mode: separate-outputs
+metadata_schema: {}
+inscriptions:
+- file: a.txt
+  metadata:
+    color: blue
+",
+        schema.display()
+      ),
+    )
+    .unwrap();
+
+    assert!(batch::File::load(&batch_file).is_ok());
+  }
+
+  #[test]
+  fn invalid_metadata_schema_is_an_error() {
+    let tempdir = TempDir::new().unwrap();
+
+    let schema = tempdir.path().join("schema.json");
+
+    fs::write(&schema, r#"{"type": "foo"}"#).unwrap();
+
+    let batch_file = tempdir.path().join("batch.yaml");
+
+    fs::write(
+      &batch_file,
+      format!(
+        "mode: separate-outputs\nmetadata_schema: {}\ninscriptions:\n- file: a.txt\n",
+        schema.display()
+      ),
+    )
+    .unwrap();
+
+    assert!(batch::File::load(&batch_file)
+      .unwrap_err()
+      .to_string()
+      .starts_with(&format!("invalid metadata schema `{}`", schema.display())));
+  }
 }
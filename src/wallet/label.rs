@@ -0,0 +1,121 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelType {
+  Addr,
+  Input,
+  Output,
+  Pubkey,
+  Tx,
+  Xpub,
+}
+
+impl Display for LabelType {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Addr => "addr",
+        Self::Input => "input",
+        Self::Output => "output",
+        Self::Pubkey => "pubkey",
+        Self::Tx => "tx",
+        Self::Xpub => "xpub",
+      }
+    )
+  }
+}
+
+/// A BIP-329 wallet label record. Fields which ord doesn't use, such as the
+/// `height`, `fee`, and `keypath` fields other wallets export, are kept in
+/// `extra`, so that they survive being imported and exported again.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Label {
+  pub r#type: LabelType,
+  pub r#ref: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub label: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub origin: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub spendable: Option<bool>,
+  #[serde(flatten)]
+  pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl Label {
+  pub(crate) fn key(&self) -> String {
+    format!("{} {}", self.r#type, self.r#ref)
+  }
+
+  pub(crate) fn check(&self) -> Result {
+    match self.r#type {
+      LabelType::Input | LabelType::Output => {
+        self
+          .r#ref
+          .parse::<OutPoint>()
+          .with_context(|| format!("invalid {} label ref `{}`", self.r#type, self.r#ref))?;
+      }
+      LabelType::Tx => {
+        self
+          .r#ref
+          .parse::<Txid>()
+          .with_context(|| format!("invalid tx label ref `{}`", self.r#ref))?;
+      }
+      LabelType::Addr | LabelType::Pubkey | LabelType::Xpub => {}
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn bip_329_records_round_trip() {
+    for json in [
+      r#"{"type":"tx","ref":"f91d0a8a78462bc59398f2c5d7a84fcff491c26ba54c4833478b202796c8aafd","label":"Transaction","origin":"wpkh([d34db33f/84'/0'/0'])"}"#,
+      r#"{"type":"addr","ref":"bc1q34aq5drpuwy3wgl9lhup9892qp6svr8ldzyy7c","label":"Address"}"#,
+      r#"{"type":"output","ref":"f91d0a8a78462bc59398f2c5d7a84fcff491c26ba54c4833478b202796c8aafd:1","label":"Output","spendable":false}"#,
+      r#"{"type":"xpub","ref":"xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"}"#,
+    ] {
+      let label = serde_json::from_str::<Label>(json).unwrap();
+      label.check().unwrap();
+      assert_eq!(serde_json::to_string(&label).unwrap(), json);
+    }
+  }
+
+  #[test]
+  fn unknown_fields_round_trip() {
+    let json = r#"{"type":"output","ref":"f91d0a8a78462bc59398f2c5d7a84fcff491c26ba54c4833478b202796c8aafd:1","label":"Output","fmv":{"USD":43.21},"height":800000,"keypath":"/1/123","value":1000}"#;
+
+    let label = serde_json::from_str::<Label>(json).unwrap();
+
+    assert_eq!(label.extra["height"], 800000);
+    assert_eq!(label.extra["keypath"], "/1/123");
+
+    assert_eq!(serde_json::to_string(&label).unwrap(), json);
+  }
+
+  #[test]
+  fn refs_are_checked() {
+    assert_eq!(
+      Label {
+        r#type: LabelType::Output,
+        r#ref: "foo".into(),
+        label: None,
+        origin: None,
+        spendable: None,
+        extra: BTreeMap::new(),
+      }
+      .check()
+      .unwrap_err()
+      .to_string(),
+      "invalid output label ref `foo`",
+    );
+  }
+}
@@ -125,7 +125,7 @@ impl WalletConstructor {
 
     let status = self.get_server_status()?;
 
-    Ok(Wallet {
+    let wallet = Wallet {
       bitcoin_client,
       database,
       has_rune_index: status.rune_index,
@@ -138,7 +138,11 @@ impl WalletConstructor {
       rpc_url: self.rpc_url,
       settings: self.settings,
       utxos,
-    })
+    };
+
+    wallet.save_output_info()?;
+
+    Ok(wallet)
   }
 
   fn get_output_info(&self, outputs: Vec<OutPoint>) -> Result<BTreeMap<OutPoint, api::Output>> {
@@ -1,17 +1,28 @@
 use {
   super::*,
+  base64::{engine::general_purpose, Engine},
   bitcoin::{
-    absolute::LockTime, consensus::Encodable, opcodes, script, transaction::Version, ScriptBuf,
-    Sequence, Transaction, TxIn, TxOut, Witness,
+    absolute::LockTime,
+    consensus::Encodable,
+    key::{Secp256k1, UntweakedPublicKey},
+    opcodes,
+    psbt::Psbt,
+    script,
+    taproot::{LeafVersion, TaprootBuilder},
+    transaction::Version,
+    ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness,
   },
   ord::{
-    subcommand::decode::{CompactInscription, CompactOutput, RawOutput},
+    subcommand::decode::{
+      CompactInscription, CompactOutput, ExplainOutput, MempoolOutput, RawOutput,
+    },
     Envelope, Inscription,
   },
+  ordinals::{Cenotaph, Explanation, ExplanationField, Flaw},
 };
 
-fn transaction() -> Vec<u8> {
-  let script = script::Builder::new()
+fn envelope_script() -> ScriptBuf {
+  script::Builder::new()
     .push_opcode(opcodes::OP_FALSE)
     .push_opcode(opcodes::all::OP_IF)
     .push_slice(b"ord")
@@ -20,11 +31,13 @@ fn transaction() -> Vec<u8> {
     .push_slice([])
     .push_slice([0, 1, 2, 3])
     .push_opcode(opcodes::all::OP_ENDIF)
-    .into_script();
+    .into_script()
+}
 
+fn transaction() -> Vec<u8> {
   let mut witness = Witness::new();
 
-  witness.push(script);
+  witness.push(envelope_script());
   witness.push([]);
 
   let transaction = Transaction {
@@ -150,3 +163,264 @@ fn compact() {
     },
   );
 }
+
+#[test]
+fn explain() {
+  pretty_assert_eq!(
+    CommandBuilder::new("decode --explain --file transaction.bin")
+      .write("transaction.bin", transaction())
+      .run_and_deserialize_output::<ExplainOutput>(),
+    ExplainOutput {
+      burned: None,
+      explanation: Some(Explanation {
+        edicts: Vec::new(),
+        fields: Vec::new(),
+        flaw: None,
+        flaw_offset: None,
+        payload: 0,
+        vout: 0,
+      }),
+      runestone: Some(Artifact::Runestone(Runestone::default())),
+    },
+  );
+}
+
+#[test]
+fn explain_cenotaph() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let rune = Rune(RUNE);
+
+  let etched = etch(&core, &ord, rune);
+
+  let transaction = Transaction {
+    version: Version(2),
+    lock_time: LockTime::ZERO,
+    input: vec![TxIn {
+      previous_output: etched.output.rune.unwrap().location.unwrap(),
+      script_sig: ScriptBuf::new(),
+      sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+      witness: Witness::new(),
+    }],
+    output: vec![TxOut {
+      script_pubkey: script::Builder::new()
+        .push_opcode(opcodes::all::OP_RETURN)
+        .push_opcode(Runestone::MAGIC_NUMBER)
+        .push_slice([22, 0, 126, 0])
+        .into_script(),
+      value: Amount::from_sat(0),
+    }],
+  };
+
+  let mut buffer = Vec::new();
+
+  transaction.consensus_encode(&mut buffer).unwrap();
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  CommandBuilder::new("--regtest --index-runes decode --explain --file transaction.bin")
+    .temp_dir(tempdir.clone())
+    .write("transaction.bin", &buffer)
+    .core(&core)
+    .stdout_regex(r#".*"burned": null.*"#)
+    .run_and_extract_stdout();
+
+  assert!(!tempdir.path().join("regtest/index.redb").exists());
+
+  CommandBuilder::new("--regtest --index-runes index update")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .run_and_extract_stdout();
+
+  pretty_assert_eq!(
+    CommandBuilder::new("--regtest --index-runes decode --explain --file transaction.bin")
+      .temp_dir(tempdir)
+      .write("transaction.bin", buffer)
+      .core(&core)
+      .run_and_deserialize_output::<ExplainOutput>(),
+    ExplainOutput {
+      burned: Some(
+        [(
+          SpacedRune { rune, spacers: 0 },
+          Pile {
+            amount: 1000,
+            divisibility: 0,
+            symbol: Some('¢'),
+          },
+        )]
+        .into(),
+      ),
+      explanation: Some(Explanation {
+        edicts: Vec::new(),
+        fields: vec![
+          ExplanationField {
+            name: Some("pointer".into()),
+            offset: 0,
+            tag: 22,
+            value: Some(0),
+          },
+          ExplanationField {
+            name: Some("cenotaph".into()),
+            offset: 2,
+            tag: 126,
+            value: Some(0),
+          },
+        ],
+        flaw: Some(Flaw::UnrecognizedEvenTag),
+        flaw_offset: Some(2),
+        payload: 4,
+        vout: 0,
+      }),
+      runestone: Some(Artifact::Cenotaph(Cenotaph {
+        flaw: Some(Flaw::UnrecognizedEvenTag),
+        ..default()
+      })),
+    },
+  );
+}
+
+#[test]
+fn from_hex() {
+  pretty_assert_eq!(
+    CommandBuilder::new(format!(
+      "decode --compact --hex {}",
+      hex::encode(transaction())
+    ))
+    .run_and_deserialize_output::<CompactOutput>(),
+    CommandBuilder::new("decode --compact --file transaction.bin")
+      .write("transaction.bin", transaction())
+      .run_and_deserialize_output::<CompactOutput>(),
+  );
+}
+
+#[test]
+fn from_hex_stdin() {
+  pretty_assert_eq!(
+    CommandBuilder::new("decode --compact")
+      .stdin(format!("{}\n", hex::encode(transaction())).into())
+      .run_and_deserialize_output::<CompactOutput>(),
+    CommandBuilder::new("decode --compact --file transaction.bin")
+      .write("transaction.bin", transaction())
+      .run_and_deserialize_output::<CompactOutput>(),
+  );
+}
+
+#[test]
+fn from_psbt() {
+  let secp = Secp256k1::new();
+
+  let internal_key = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+    .parse::<UntweakedPublicKey>()
+    .unwrap();
+
+  let script = envelope_script();
+
+  let control_block = TaprootBuilder::new()
+    .add_leaf(0, script.clone())
+    .unwrap()
+    .finalize(&secp, internal_key)
+    .unwrap()
+    .control_block(&(script.clone(), LeafVersion::TapScript))
+    .unwrap();
+
+  let mut psbt = Psbt::from_unsigned_tx(Transaction {
+    version: Version(2),
+    lock_time: LockTime::ZERO,
+    input: vec![TxIn {
+      previous_output: OutPoint::null(),
+      script_sig: ScriptBuf::new(),
+      sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+      witness: Witness::new(),
+    }],
+    output: vec![TxOut {
+      script_pubkey: Runestone::default().encipher(),
+      value: Amount::from_sat(0),
+    }],
+  })
+  .unwrap();
+
+  psbt.inputs[0]
+    .tap_scripts
+    .insert(control_block, (script, LeafVersion::TapScript));
+
+  pretty_assert_eq!(
+    CommandBuilder::new(format!(
+      "decode --compact --psbt {}",
+      general_purpose::STANDARD.encode(psbt.serialize())
+    ))
+    .run_and_deserialize_output::<CompactOutput>(),
+    CompactOutput {
+      inscriptions: vec![CompactInscription {
+        body: Some("00010203".into()),
+        content_encoding: None,
+        content_type: Some("text/plain;charset=utf-8".into()),
+        duplicate_field: false,
+        incomplete_field: false,
+        metadata: None,
+        metaprotocol: None,
+        parents: Vec::new(),
+        pointer: None,
+        unrecognized_even_field: false,
+      }],
+      runestone: Some(Artifact::Runestone(Runestone::default())),
+    },
+  );
+}
+
+#[test]
+fn mempool() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  pretty_assert_eq!(
+    CommandBuilder::new("decode --mempool")
+      .core(&core)
+      .run_and_deserialize_output::<MempoolOutput>(),
+    MempoolOutput {
+      transactions: [(
+        output.reveal,
+        CompactOutput {
+          inscriptions: vec![CompactInscription {
+            body: Some(hex::encode("FOO")),
+            content_encoding: None,
+            content_type: Some("text/plain;charset=utf-8".into()),
+            duplicate_field: false,
+            incomplete_field: false,
+            metadata: None,
+            metaprotocol: None,
+            parents: Vec::new(),
+            pointer: None,
+            unrecognized_even_field: false,
+          }],
+          runestone: None,
+        },
+      )]
+      .into(),
+    },
+  );
+
+  core.mine_blocks(1);
+
+  pretty_assert_eq!(
+    CommandBuilder::new("decode --mempool")
+      .core(&core)
+      .run_and_deserialize_output::<MempoolOutput>(),
+    MempoolOutput {
+      transactions: BTreeMap::new(),
+    },
+  );
+}
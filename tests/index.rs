@@ -90,3 +90,172 @@ fn export_inscription_number_to_id_tsv() {
     &ord::Object::InscriptionId(inscription),
   );
 }
+
+#[test]
+fn enable_backfills_rune_index() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  core.mine_blocks(1);
+
+  let (tempdir, _) = CommandBuilder::new("--regtest index update")
+    .core(&core)
+    .run();
+
+  CommandBuilder::new("--regtest balances")
+    .core(&core)
+    .temp_dir(tempdir.clone())
+    .expected_exit_code(1)
+    .expected_stderr("error: `ord balances` requires index created with `--index-runes` flag\n")
+    .run_and_extract_stdout();
+
+  CommandBuilder::new("--regtest index enable runes")
+    .core(&core)
+    .temp_dir(tempdir.clone())
+    .run_and_extract_stdout();
+
+  CommandBuilder::new("--regtest balances")
+    .core(&core)
+    .temp_dir(tempdir.clone())
+    .run_and_deserialize_output::<Balances>();
+
+  CommandBuilder::new("--regtest index enable runes")
+    .core(&core)
+    .temp_dir(tempdir)
+    .expected_exit_code(1)
+    .expected_stderr("error: `runes` index is already enabled\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn prune_depth_must_cover_reorgs() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("--prune 20 index update")
+    .core(&core)
+    .expected_exit_code(1)
+    .expected_stderr("error: --prune must be at least 21 blocks\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn compact_shrinks_index() {
+  let core = mockcore::spawn();
+  core.mine_blocks(10);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("index.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .core(&core)
+    .run_and_extract_stdout();
+
+  let output = CommandBuilder::new(format!("--index {} index compact", index_path.display()))
+    .core(&core)
+    .expected_stderr(
+      "warning: reorg savepoints were deleted to compact the index, and a new savepoint was \
+      created at height 11. Reorgs of blocks below height 11 cannot be rolled back and require \
+      rebuilding the index.\n",
+    )
+    .run_and_deserialize_output::<ord::subcommand::index::compact::Output>();
+
+  assert!(output.before > 0);
+  assert!(output.after <= output.before);
+  assert_eq!(output.after, fs::metadata(&index_path).unwrap().len());
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .core(&core)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn compact_refuses_while_index_is_in_use() {
+  let core = mockcore::spawn();
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("index.redb");
+
+  let port = TcpListener::bind("127.0.0.1:0")
+    .unwrap()
+    .local_addr()
+    .unwrap()
+    .port();
+
+  let mut child = CommandBuilder::new(format!(
+    "--index {} server --address 127.0.0.1 --http-port {port}",
+    index_path.display()
+  ))
+  .core(&core)
+  .command()
+  .spawn()
+  .unwrap();
+
+  for attempt in 0.. {
+    if reqwest::blocking::get(format!("http://localhost:{port}/blockcount")).is_ok() {
+      break;
+    }
+
+    if attempt == 100 {
+      panic!("Server did not start");
+    }
+
+    thread::sleep(Duration::from_millis(50));
+  }
+
+  CommandBuilder::new(format!("--index {} index compact", index_path.display()))
+    .core(&core)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: failed to open index: index at `{}` is in use by another process, such as `ord server`\n",
+      index_path.display()
+    ))
+    .run_and_extract_stdout();
+
+  child.kill().unwrap();
+  child.wait().unwrap();
+}
+
+#[test]
+fn checksum() {
+  let core = mockcore::spawn();
+  core.mine_blocks(3);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let checksum = |index: &str, args: &str| {
+    CommandBuilder::new(format!(
+      "--index {} index checksum {args}",
+      tempdir.path().join(index).display()
+    ))
+    .core(&core)
+    .run_and_deserialize_output::<ord::subcommand::index::checksum::Output>()
+  };
+
+  for index in ["a.redb", "b.redb"] {
+    CommandBuilder::new(format!(
+      "--index {} index update",
+      tempdir.path().join(index).display()
+    ))
+    .core(&core)
+    .run_and_extract_stdout();
+  }
+
+  let a = checksum("a.redb", "");
+
+  assert_eq!(a.height, 3);
+  assert_eq!(a, checksum("b.redb", ""));
+  assert_eq!(a, checksum("a.redb", "--height 3"));
+  assert_ne!(a.checksum, checksum("a.redb", "--height 2").checksum);
+
+  CommandBuilder::new(format!(
+    "--index {} index checksum --height 4",
+    tempdir.path().join("a.redb").display()
+  ))
+  .core(&core)
+  .expected_stderr("error: block 4 has not been indexed\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
@@ -1,4 +1,7 @@
-use {super::*, ord::subcommand::index::info::TransactionsOutput};
+use {
+  super::*,
+  ord::subcommand::index::info::{TableOutput, TransactionsOutput},
+};
 
 #[test]
 fn json_with_satoshi_index() {
@@ -124,3 +127,24 @@ fn transactions() {
   assert_eq!(output[1].end, 11);
   assert_eq!(output[1].count, 10);
 }
+
+#[test]
+fn tables() {
+  let core = mockcore::spawn();
+
+  let (tempdir, _) = CommandBuilder::new("index update").core(&core).run();
+
+  let output = CommandBuilder::new("index info --tables")
+    .temp_dir(tempdir)
+    .core(&core)
+    .run_and_deserialize_output::<BTreeMap<String, TableOutput>>();
+
+  let height_to_block_header = &output["HEIGHT_TO_BLOCK_HEADER"];
+
+  assert_eq!(height_to_block_header.entries, 1);
+  assert!(height_to_block_header.stored_bytes > 0);
+  assert!(height_to_block_header.total_bytes >= height_to_block_header.stored_bytes);
+
+  assert_eq!(output["OUTPOINT_TO_UTXO_ENTRY"].entries, 1);
+  assert_eq!(output["SAT_TO_SEQUENCE_NUMBER"].entries, 0);
+}
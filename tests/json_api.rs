@@ -180,6 +180,7 @@ fn get_inscription() {
       next: None,
       value: Some(10000),
       parents: Vec::new(),
+      pending_spend: None,
       previous: None,
       rune: None,
       sat: Some(Sat(50 * COIN_VALUE)),
@@ -236,6 +237,7 @@ fn get_inscription_with_metaprotocol() {
       next: None,
       value: Some(10000),
       parents: Vec::new(),
+      pending_spend: None,
       previous: None,
       rune: None,
       sat: Some(Sat(50 * COIN_VALUE)),
@@ -412,6 +414,7 @@ fn get_output() {
         InscriptionId { txid, index: 2 },
       ]),
       indexed: true,
+      pending_spend: None,
       runes: None,
       sat_ranges: Some(vec![
         (5000000000, 10000000000,),
@@ -574,6 +577,7 @@ fn get_status() {
       json_api: true,
       lost_sats: 0,
       minimum_rune_for_next_block: Rune(99218849511960410),
+      replication_lag: None,
       rune_index: true,
       runes: 0,
       sat_index: true,
@@ -815,6 +819,7 @@ fn outputs_address() {
         vout: 0
       },
       indexed: true,
+      pending_spend: None,
       runes: Some(BTreeMap::new()),
       sat_ranges: None,
       script_pubkey: ScriptBuf::from(
@@ -859,6 +864,7 @@ fn outputs_address() {
         vout: 0
       },
       indexed: true,
+      pending_spend: None,
       runes: Some(expected_runes),
       sat_ranges: None,
       script_pubkey: ScriptBuf::from(
@@ -893,6 +899,7 @@ fn outputs_address() {
         vout: 0
       },
       indexed: true,
+      pending_spend: None,
       runes: Some(BTreeMap::new()),
       sat_ranges: None,
       script_pubkey: ScriptBuf::from(
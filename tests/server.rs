@@ -35,6 +35,78 @@ fn run() {
   child.wait().unwrap();
 }
 
+#[test]
+fn read_only_server_follows_published_snapshots() {
+  let core = mockcore::spawn();
+  core.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("index.redb");
+
+  CommandBuilder::new(format!(
+    "--index {} index update --snapshot",
+    index_path.display()
+  ))
+  .core(&core)
+  .run_and_extract_stdout();
+
+  let port = TcpListener::bind("127.0.0.1:0")
+    .unwrap()
+    .local_addr()
+    .unwrap()
+    .port();
+
+  let mut child = CommandBuilder::new(format!(
+    "--index {} server --read-only --address 127.0.0.1 --http-port {port}",
+    index_path.display()
+  ))
+  .core(&core)
+  .command()
+  .spawn()
+  .unwrap();
+
+  let block_count = |expected: &str| {
+    for attempt in 0.. {
+      if let Ok(response) = reqwest::blocking::get(format!("http://localhost:{port}/blockcount")) {
+        if response.status() == 200 && response.text().unwrap() == expected {
+          break;
+        }
+      }
+
+      if attempt == 100 {
+        panic!("Server did not reach block count {expected}");
+      }
+
+      thread::sleep(Duration::from_millis(50));
+    }
+  };
+
+  block_count("2");
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(format!(
+    "--index {} index update --snapshot",
+    index_path.display()
+  ))
+  .core(&core)
+  .run_and_extract_stdout();
+
+  block_count("3");
+
+  assert!(
+    reqwest::blocking::get(format!("http://localhost:{port}/status"))
+      .unwrap()
+      .text()
+      .unwrap()
+      .contains("<dt>replication lag</dt>\n  <dd>0 blocks</dd>")
+  );
+
+  child.kill().unwrap();
+  child.wait().unwrap();
+}
+
 #[test]
 fn address_page_shows_outputs_and_sat_balance() {
   let core = mockcore::spawn();
@@ -218,8 +290,8 @@ fn address_page_shows_aggregated_inscriptions() {
 <dl>.*
   <dt>inscriptions</dt>
   <dd class=thumbnails>
-    <a href=/inscription/[[:xdigit:]]{64}i\d><iframe .* src=/preview/[[:xdigit:]]{64}i\d></iframe></a>
-    <a href=/inscription/[[:xdigit:]]{64}i\d><iframe .* src=/preview/[[:xdigit:]]{64}i\d></iframe></a>
+    <a href=/inscription/[[:xdigit:]]{64}i\d><iframe .* src=/preview/[[:xdigit:]]{64}i\d/thumbnail></iframe></a>
+    <a href=/inscription/[[:xdigit:]]{64}i\d><iframe .* src=/preview/[[:xdigit:]]{64}i\d/thumbnail></iframe></a>
   </dd>.*"
     ,
   );
@@ -898,3 +970,89 @@ fn ctrl_c() {
     thread::sleep(Duration::from_millis(50));
   }
 }
+
+#[test]
+fn additional_chains_are_served_at_their_hostnames() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let signet = mockcore::builder().network(Network::Signet).build();
+
+  core.mine_blocks(2);
+  signet.mine_blocks(5);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let cookie_file = tempdir.path().join("cookie");
+
+  fs::write(&cookie_file, "username:password").unwrap();
+
+  let config = tempdir.path().join("ord.yaml");
+
+  fs::write(
+    &config,
+    format!(
+      "chains:
+  signet:
+    bitcoin_rpc_url: {}
+    cookie_file: {}
+    hostname: signet.localhost
+",
+      signet.url(),
+      cookie_file.display(),
+    ),
+  )
+  .unwrap();
+
+  let ord =
+    TestServer::spawn_with_args(&core, &["--regtest", "--config", config.to_str().unwrap()]);
+
+  let client = reqwest::blocking::Client::new();
+
+  let signet_get = |path: &str| {
+    let response = client
+      .get(ord.url().join(path).unwrap())
+      .header(reqwest::header::HOST, "signet.localhost:80")
+      .send()
+      .unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "bad status for {path}");
+    response.text().unwrap()
+  };
+
+  ord.assert_response("/update", "3");
+  assert_eq!(signet_get("/update"), "6");
+
+  ord.assert_response("/blockcount", "3");
+  assert_eq!(signet_get("/blockcount"), "6");
+
+  ord.assert_response("/r/blockheight", "2");
+  assert_eq!(signet_get("/r/blockheight"), "5");
+
+  let signet_hashes = signet.state().hashes.clone();
+
+  assert_eq!(
+    signet_get("/r/blockhash/0"),
+    format!("\"{}\"", signet_hashes[0])
+  );
+
+  let page = signet_get("/block/5");
+
+  assert!(page.contains(&signet_hashes[5].to_string()));
+
+  let link = Regex::new("<a class=prev href=(/block/[0-9]+)>")
+    .unwrap()
+    .captures(&page)
+    .unwrap()[1]
+    .to_string();
+
+  assert_eq!(link, "/block/4");
+
+  let page = signet_get(&link);
+
+  assert!(page.contains("<h1>Block 4</h1>"));
+  assert!(page.contains(&signet_hashes[4].to_string()));
+
+  assert_eq!(ord.request("/block/4").status(), StatusCode::NOT_FOUND);
+  assert_eq!(
+    ord.request("/signet/blockcount").status(),
+    StatusCode::NOT_FOUND
+  );
+}
@@ -12,6 +12,7 @@ fn default() {
   "bitcoin_rpc_url": "127.0.0.1:8332",
   "bitcoin_rpc_username": null,
   "chain": "mainnet",
+  "chains": null,
   "commit_interval": 5000,
   "config": null,
   "config_dir": null,
@@ -19,6 +20,7 @@ fn default() {
   "data_dir": ".*",
   "height_limit": null,
   "hidden": \[\],
+  "hostname": null,
   "http_port": null,
   "index": ".*index\.redb",
   "index_addresses": false,
@@ -27,7 +29,10 @@ fn default() {
   "index_sats": false,
   "index_transactions": false,
   "integration_test": false,
+  "max_fee_rate": null,
+  "media_types": null,
   "no_index_inscriptions": false,
+  "prune": null,
   "server_password": null,
   "server_url": null,
   "server_username": null
@@ -49,6 +54,7 @@ fn config_is_loaded_from_config_option() {
     .stdout_regex(
       r#".*
   "chain": "regtest",
+  "chains": null,
 .*"#,
     )
     .run_and_extract_stdout();
@@ -93,6 +99,7 @@ fn config_is_loaded_from_config_dir() {
   .stdout_regex(
     r#".*
   "chain": "regtest",
+  "chains": null,
 .*"#,
   )
   .run_and_extract_stdout();
@@ -105,6 +112,7 @@ fn config_is_loaded_from_data_dir() {
     .stdout_regex(
       r#".*
   "chain": "regtest",
+  "chains": null,
 .*"#,
     )
     .run_and_extract_stdout();
@@ -116,6 +124,7 @@ fn env_is_loaded() {
     .stdout_regex(
       r#".*
   "chain": "mainnet",
+  "chains": null,
 .*"#,
     )
     .run_and_extract_stdout();
@@ -125,6 +134,7 @@ fn env_is_loaded() {
     .stdout_regex(
       r#".*
   "chain": "regtest",
+  "chains": null,
 .*"#,
     )
     .run_and_extract_stdout();
@@ -23,6 +23,7 @@ mod runics;
 mod sats;
 mod selection;
 mod send;
+mod send_many;
 mod sign;
 mod split;
 mod transactions;
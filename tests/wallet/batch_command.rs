@@ -74,6 +74,109 @@ fn batch_inscribe_can_create_one_inscription() {
   );
 }
 
+#[test]
+fn batch_inscribe_with_compression() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet batch --compress=gzip --fee-rate 1 --batch batch.yaml")
+    .write("inscription.txt", "a".repeat(1000))
+    .write(
+      "batch.yaml",
+      "mode: separate-outputs
+inscriptions:
+- file: inscription.txt
+- file: inscription.txt
+  compress: zstd
+",
+    )
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  core.mine_blocks(1);
+
+  ord.assert_response_regex(
+    format!("/inscription/{}", output.inscriptions[0].id),
+    r".*<dt>content encoding</dt>\s*<dd>gzip</dd>.*",
+  );
+
+  ord.assert_response_regex(
+    format!("/inscription/{}", output.inscriptions[1].id),
+    r".*<dt>content encoding</dt>\s*<dd>zstd</dd>.*",
+  );
+}
+
+#[test]
+fn batch_inscribe_with_properties() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (item, _) = inscribe(&core, &ord);
+
+  let output = CommandBuilder::new("wallet batch --fee-rate 1 --batch batch.yaml")
+    .write("inscription.txt", "Hello World")
+    .write(
+      "batch.yaml",
+      format!(
+        "mode: shared-output
+inscriptions:
+- file: inscription.txt
+  properties:
+    attributes:
+      title: foo
+    gallery:
+    - id: {item}
+      attributes:
+        traits:
+          bar: true
+"
+      ),
+    )
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  core.mine_blocks(1);
+
+  let id = output.inscriptions[0].id;
+
+  assert_eq!(
+    ord
+      .json_request(format!("/r/inscription/{id}/properties"))
+      .json::<ord::Properties>()
+      .unwrap(),
+    ord::Properties {
+      attributes: ord::properties::Attributes {
+        title: Some("foo".into()),
+        traits: BTreeMap::new(),
+      },
+      gallery: vec![ord::properties::Item {
+        id: item,
+        attributes: ord::properties::Attributes {
+          title: None,
+          traits: [("bar".into(), ord::properties::Trait::Bool(true))].into(),
+        },
+      }],
+    },
+  );
+
+  ord.assert_response_regex(
+    format!("/inscription/{id}"),
+    format!(r".*<dt>title</dt>\s*<dd>foo</dd>.*<dt>gallery</dt>.*/preview/{item}.*"),
+  );
+}
+
 #[test]
 fn batch_inscribe_with_multiple_inscriptions() {
   let core = mockcore::spawn();
@@ -291,6 +394,66 @@ fn batch_inscribe_and_etch_with_two_parents() {
   assert_eq!(request.headers().get("content-type").unwrap(), "audio/wav");
 }
 
+#[test]
+fn batch_inscribe_with_metadata_schema() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let schema = r#"{"type": "object", "properties": {"level": {"type": "integer"}}}"#;
+
+  CommandBuilder::new("wallet batch --fee-rate 1 --batch batch.yaml")
+    .write("inscription.txt", "Hello World")
+    .write("schema.json", schema)
+    .write(
+      "batch.yaml",
+      "mode: separate-outputs
+metadata_schema: schema.json
+inscriptions:
+- file: inscription.txt
+  metadata:
+    level: 1
+- file: inscription.txt
+  metadata:
+    level: high
+",
+    )
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: metadata does not satisfy schema `schema.json`:
+inscription 1 at `/level`: \"high\" is not of type \"integer\"
+",
+    )
+    .run_and_extract_stdout();
+
+  assert!(core.mempool().is_empty());
+
+  let output = CommandBuilder::new("wallet batch --fee-rate 1 --batch batch.yaml")
+    .write("inscription.txt", "Hello World")
+    .write("schema.json", schema)
+    .write(
+      "batch.yaml",
+      "mode: separate-outputs
+metadata_schema: schema.json
+inscriptions:
+- file: inscription.txt
+  metadata:
+    level: 1
+",
+    )
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  assert_eq!(output.inscriptions.len(), 1);
+}
+
 #[test]
 fn batch_inscribe_respects_dry_run_flag() {
   let core = mockcore::spawn();
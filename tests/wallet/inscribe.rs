@@ -124,6 +124,35 @@ fn inscribe_unknown_file_extension() {
     .run_and_extract_stdout();
 }
 
+#[test]
+fn inscribe_with_configured_media_type() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let output =
+    CommandBuilder::new("--config ord.yaml wallet inscribe --file data.csv --fee-rate 1")
+      .write(
+        "ord.yaml",
+        "media_types:\n- content_type: text/csv\n  extensions: [csv]\n  media: text\n",
+      )
+      .write("data.csv", "a,b")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Batch>();
+
+  core.mine_blocks(1);
+
+  let request = ord.request(format!("/content/{}", output.inscriptions[0].id));
+
+  assert_eq!(request.status(), 200);
+  assert_eq!(request.headers().get("content-type").unwrap(), "text/csv");
+  assert_eq!(request.text().unwrap(), "a,b");
+}
+
 #[test]
 fn inscribe_exceeds_chain_limit() {
   let core = mockcore::builder().network(Network::Signet).build();
@@ -1144,6 +1173,91 @@ fn server_can_decompress_brotli() {
   assert_eq!(response.bytes().unwrap().deref(), [0; 350_000]);
 }
 
+#[test]
+fn server_can_decompress_gzip_and_zstd() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let mut inscriptions = Vec::new();
+
+  for algorithm in ["gzip", "zstd"] {
+    let Batch {
+      inscriptions: batch,
+      ..
+    } = CommandBuilder::new(format!(
+      "wallet inscribe --compress={algorithm} --file foo.txt --fee-rate 1"
+    ))
+    .write("foo.txt", [0; 350_000])
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output();
+
+    core.mine_blocks(1);
+
+    inscriptions.push((algorithm, batch[0].id));
+  }
+
+  ord.sync_server();
+
+  for (algorithm, inscription) in &inscriptions {
+    let response = reqwest::blocking::get(
+      ord
+        .url()
+        .join(format!("/content/{inscription}").as_ref())
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+    assert_regex_match!(
+      response.text().unwrap(),
+      format!("inscription content encoding `{algorithm}` is not acceptable.*")
+    );
+  }
+
+  let test_server = TestServer::spawn_with_server_args(&core, &[], &["--decompress"]);
+
+  test_server.sync_server();
+
+  for (_, inscription) in &inscriptions {
+    let response = reqwest::blocking::get(
+      test_server
+        .url()
+        .join(format!("/content/{inscription}").as_ref())
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.bytes().unwrap().deref(), [0; 350_000]);
+  }
+
+  let test_server = TestServer::spawn_with_server_args(
+    &core,
+    &[],
+    &["--decompress", "--decompress-limit", "349999"],
+  );
+
+  test_server.sync_server();
+
+  for (_, inscription) in &inscriptions {
+    let response = reqwest::blocking::get(
+      test_server
+        .url()
+        .join(format!("/content/{inscription}").as_ref())
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+  }
+}
+
 #[test]
 fn file_inscribe_with_delegate_inscription() {
   let core = mockcore::spawn();
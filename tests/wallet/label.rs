@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, ord::subcommand::wallet::label::Output};
 
 #[test]
 fn label() {
@@ -18,13 +18,94 @@ fn label() {
     .stdout_regex(".*")
     .run_and_extract_stdout();
 
-  assert!(
-    output.contains(r#"\"name\":\"nvtcsezkbth\",\"number\":5000000000,\"rarity\":\"uncommon\""#)
+  assert!(output.contains("rare sats: nvtcsezkbth (uncommon)"));
+
+  assert!(output.contains("rare sats: nvtccadxgaz (uncommon)"));
+
+  assert!(output.contains(&format!("inscriptions: {inscription}")));
+
+  for line in output.lines() {
+    let label = serde_json::from_str::<serde_json::Value>(line).unwrap();
+    assert_eq!(label["type"], "output");
+    assert_eq!(label["spendable"], false);
+  }
+}
+
+#[test]
+fn import_and_export_round_trip() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let txid = core.mine_blocks(1)[0].txdata[0].compute_txid();
+
+  let labels = format!(
+    "{{\"type\":\"output\",\"ref\":\"{txid}:0\",\"label\":\"block reward\",\"spendable\":true}}\n\
+     {{\"type\":\"tx\",\"ref\":\"{txid}\",\"label\":\"coinbase\",\"fee\":0,\"height\":1,\"rate\":{{\"USD\":100000.0}},\"time\":\"2009-01-09T02:54:25Z\"}}\n\
+     {{\"type\":\"xpub\",\"ref\":\"xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8\",\"label\":\"cold storage\"}}\n"
+  );
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  assert_eq!(
+    CommandBuilder::new("wallet label --import labels.jsonl")
+      .temp_dir(tempdir.clone())
+      .write("labels.jsonl", &labels)
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Output>(),
+    Output { imported: 3 },
   );
 
-  assert!(
-    output.contains(r#"\"name\":\"nvtccadxgaz\",\"number\":10000000000,\"rarity\":\"uncommon\""#)
+  assert_eq!(
+    CommandBuilder::new("wallet label")
+      .temp_dir(tempdir.clone())
+      .core(&core)
+      .ord(&ord)
+      .stdout_regex(".*")
+      .run_and_extract_stdout(),
+    labels,
   );
 
-  assert!(output.contains(&inscription.to_string()));
+  let outputs = CommandBuilder::new("wallet outputs")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<ord::subcommand::wallet::outputs::Output>>();
+
+  assert_eq!(outputs[0].label, Some("block reward".into()));
+
+  let transactions = CommandBuilder::new("wallet transactions")
+    .temp_dir(tempdir)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<ord::subcommand::wallet::transactions::Output>>();
+
+  assert_eq!(transactions[0].label, Some("coinbase".into()));
+}
+
+#[test]
+fn import_rejects_invalid_labels() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("wallet label --import labels.jsonl")
+    .write("labels.jsonl", "{\"type\":\"output\",\"ref\":\"foo\"}")
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(
+      "error: failed to import labels from `labels.jsonl`\n\
+       \n\
+       because:\n\
+       - line 1: invalid BIP-329 label\n\
+       - invalid output label ref `foo`\n\
+       - OutPoint not in <txid>:<vout> format\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
 }
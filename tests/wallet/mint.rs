@@ -576,3 +576,378 @@ fn minting_is_allowed_when_mint_begins_next_block() {
     }
   );
 }
+
+#[test]
+fn minting_rune_repeatedly_stops_at_cap() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  core.mine_blocks(1);
+
+  create_wallet(&core, &ord);
+
+  batch(
+    &core,
+    &ord,
+    batch::File {
+      etching: Some(batch::Etching {
+        divisibility: 0,
+        rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+        premine: "0".parse().unwrap(),
+        supply: "63".parse().unwrap(),
+        symbol: '¢',
+        turbo: false,
+        terms: Some(batch::Terms {
+          cap: 3,
+          offset: Some(batch::Range {
+            end: Some(10),
+            start: None,
+          }),
+          amount: "21".parse().unwrap(),
+          height: None,
+        }),
+      }),
+      inscriptions: vec![batch::Entry {
+        file: Some("inscription.jpeg".into()),
+        ..default()
+      }],
+      ..default()
+    },
+  );
+
+  let output = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet mint --fee-rate 1 --rune {} --count 5",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<mint::Output>();
+
+  pretty_assert_eq!(
+    output.pile,
+    Pile {
+      amount: 63,
+      divisibility: 0,
+      symbol: Some('¢'),
+    }
+  );
+
+  assert_eq!(output.chained.len(), 2);
+  assert_eq!(output.consolidation, None);
+  assert_eq!(
+    output.stopped.as_deref(),
+    Some("rune AAAAAAAAAAAAA limited to 3 mints")
+  );
+  assert_eq!(core.mempool().len(), 3);
+
+  core.mine_blocks(1);
+
+  let balance = CommandBuilder::new("--chain regtest --index-runes wallet balance")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::balance::Output>();
+
+  assert_eq!(
+    *balance.runes.unwrap().first_key_value().unwrap().1,
+    Decimal {
+      value: 63,
+      scale: 0,
+    }
+  );
+
+  assert_eq!(balance.runic.unwrap(), 30000);
+
+  CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet mint --fee-rate 1 --rune {} --until-cap",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr("error: rune AAAAAAAAAAAAA limited to 3 mints\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn minting_rune_until_cap_and_consolidating() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  core.mine_blocks(1);
+
+  create_wallet(&core, &ord);
+
+  batch(
+    &core,
+    &ord,
+    batch::File {
+      etching: Some(batch::Etching {
+        divisibility: 0,
+        rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+        premine: "0".parse().unwrap(),
+        supply: "84".parse().unwrap(),
+        symbol: '¢',
+        turbo: false,
+        terms: Some(batch::Terms {
+          cap: 4,
+          offset: Some(batch::Range {
+            end: Some(10),
+            start: None,
+          }),
+          amount: "21".parse().unwrap(),
+          height: None,
+        }),
+      }),
+      inscriptions: vec![batch::Entry {
+        file: Some("inscription.jpeg".into()),
+        ..default()
+      }],
+      ..default()
+    },
+  );
+
+  let destination: Address<NetworkUnchecked> = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw"
+    .parse()
+    .unwrap();
+
+  let output = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet mint --fee-rate 1 --rune {} --until-cap --consolidate \
+    --destination {}",
+    Rune(RUNE),
+    destination.clone().assume_checked(),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<mint::Output>();
+
+  assert_eq!(output.pile.amount, 84);
+  assert_eq!(output.chained.len(), 3);
+
+  let consolidation = output.consolidation.unwrap();
+
+  core.mine_blocks(1);
+
+  assert_eq!(
+    CommandBuilder::new("--regtest --index-runes balances")
+      .core(&core)
+      .run_and_deserialize_output::<ord::subcommand::balances::Output>(),
+    ord::subcommand::balances::Output {
+      runes: vec![(
+        SpacedRune::new(Rune(RUNE), 0),
+        vec![(
+          OutPoint {
+            txid: consolidation,
+            vout: 0
+          },
+          Pile {
+            amount: 84,
+            divisibility: 0,
+            symbol: Some('¢')
+          },
+        )]
+        .into_iter()
+        .collect()
+      )]
+      .into_iter()
+      .collect(),
+    }
+  );
+}
+
+#[test]
+fn minting_rune_once_and_consolidating_sends_to_destination() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  core.mine_blocks(1);
+
+  create_wallet(&core, &ord);
+
+  batch(
+    &core,
+    &ord,
+    batch::File {
+      etching: Some(batch::Etching {
+        divisibility: 0,
+        rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+        premine: "0".parse().unwrap(),
+        supply: "42".parse().unwrap(),
+        symbol: '¢',
+        turbo: false,
+        terms: Some(batch::Terms {
+          cap: 2,
+          offset: Some(batch::Range {
+            end: Some(10),
+            start: None,
+          }),
+          amount: "21".parse().unwrap(),
+          height: None,
+        }),
+      }),
+      inscriptions: vec![batch::Entry {
+        file: Some("inscription.jpeg".into()),
+        ..default()
+      }],
+      ..default()
+    },
+  );
+
+  let destination = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw"
+    .parse::<Address<NetworkUnchecked>>()
+    .unwrap()
+    .assume_checked();
+
+  let output = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet mint --fee-rate 1 --rune {} --count 1 --consolidate \
+    --destination {destination}",
+    Rune(RUNE),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<mint::Output>();
+
+  assert_eq!(output.consolidation, None);
+
+  let mint = output.mint;
+
+  core.mine_blocks(1);
+
+  // the mint closes after one more mint, which must still be consolidated
+  let output = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet mint --fee-rate 1 --rune {} --count 3 --consolidate \
+    --destination {destination}",
+    Rune(RUNE),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<mint::Output>();
+
+  assert!(output.chained.is_empty());
+  assert_eq!(
+    output.stopped.as_deref(),
+    Some("rune AAAAAAAAAAAAA limited to 2 mints")
+  );
+
+  let consolidation = output.consolidation.unwrap();
+
+  core.mine_blocks(1);
+
+  for outpoint in [
+    OutPoint {
+      txid: mint,
+      vout: 1,
+    },
+    OutPoint {
+      txid: consolidation,
+      vout: 0,
+    },
+  ] {
+    let output = ord
+      .json_request(format!("/output/{outpoint}"))
+      .json::<api::Output>()
+      .unwrap();
+
+    assert_eq!(output.address, Some(destination.as_unchecked().clone()));
+    assert_eq!(
+      output.runes,
+      Some(
+        [(
+          SpacedRune::new(Rune(RUNE), 0),
+          Pile {
+            amount: 21,
+            divisibility: 0,
+            symbol: Some('¢'),
+          }
+        )]
+        .into()
+      ),
+    );
+  }
+}
+
+#[test]
+fn minting_rune_repeatedly_stops_at_mempool_chain_limit() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  core.mine_blocks(1);
+
+  create_wallet(&core, &ord);
+
+  batch(
+    &core,
+    &ord,
+    batch::File {
+      etching: Some(batch::Etching {
+        divisibility: 0,
+        rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+        premine: "0".parse().unwrap(),
+        supply: "210".parse().unwrap(),
+        symbol: '¢',
+        turbo: false,
+        terms: Some(batch::Terms {
+          cap: 10,
+          offset: Some(batch::Range {
+            end: Some(10),
+            start: None,
+          }),
+          amount: "21".parse().unwrap(),
+          height: None,
+        }),
+      }),
+      inscriptions: vec![batch::Entry {
+        file: Some("inscription.jpeg".into()),
+        ..default()
+      }],
+      ..default()
+    },
+  );
+
+  core.state().mempool_chain_limit = Some(2);
+
+  let output = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet mint --fee-rate 1 --rune {} --until-cap",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<mint::Output>();
+
+  assert_eq!(output.pile.amount, 42);
+  assert_eq!(output.chained.len(), 1);
+  assert_eq!(
+    output.stopped.as_deref(),
+    Some("too-long-mempool-chain, too many unconfirmed ancestors [limit: 2]")
+  );
+  assert_eq!(core.mempool().len(), 2);
+
+  core.state().mempool_chain_limit = Some(0);
+
+  CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet mint --fee-rate 1 --rune {}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr(
+    "error: JSON-RPC error: RPC error response: RpcError { code: -26, message: \"too-long-mempool-chain, too many unconfirmed ancestors [limit: 0]\", data: None }\n",
+  )
+  .run_and_extract_stdout();
+}
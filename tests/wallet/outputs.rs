@@ -140,6 +140,7 @@ fn outputs_includes_runes_and_inscriptions() {
     address: etched.output.rune.unwrap().destination,
     amount: 10000,
     inscriptions: Some(Vec::new()),
+    label: None,
     runes: Some(
       vec![(
         SpacedRune { rune, spacers: 1 },
@@ -159,6 +160,7 @@ fn outputs_includes_runes_and_inscriptions() {
     address: Some(etched.output.inscriptions[0].destination.clone()),
     amount: 10000,
     inscriptions: Some(vec![etched.output.inscriptions[0].id]),
+    label: None,
     runes: Some(BTreeMap::new()),
     sat_ranges: None,
   }));
@@ -364,6 +364,7 @@ inscriptions:
         },
       ]),
       indexed: true,
+      pending_spend: None,
       runes: None,
       sat_ranges: Some(vec![(5_000_000_000, 5_000_030_000)]),
       script_pubkey: destination.assume_checked_ref().script_pubkey(),
@@ -483,6 +484,90 @@ fn send_btc_with_fee_rate() {
   assert_eq!(tx.output[0].value.to_sat(), 2 * COIN_VALUE);
 }
 
+#[test]
+fn send_btc_with_estimated_fee_rate() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  core.set_fee_estimate(7);
+
+  CommandBuilder::new(
+    "wallet send --fee-rate normal bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 2btc",
+  )
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  let tx = &core.mempool()[0];
+
+  let mut fee = Amount::ZERO;
+  for input in &tx.input {
+    fee += core.get_utxo_amount(&input.previous_output).unwrap();
+  }
+
+  for output in &tx.output {
+    fee -= output.value;
+  }
+
+  let fee_rate = fee.to_sat() as f64 / tx.vsize() as f64;
+
+  assert!(f64::abs(fee_rate - 7.0) < 0.1);
+}
+
+#[test]
+fn send_btc_fails_if_fee_rate_cannot_be_estimated() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(
+    "wallet send --fee-rate conf:3 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc",
+  )
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(
+    "error: failed to estimate fee rate for 3 block confirmation target: \
+      Insufficient data or no feerate found\n",
+  )
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn send_btc_fails_if_estimated_fee_rate_exceeds_maximum() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  core.set_fee_estimate(100);
+
+  CommandBuilder::new(
+    "--max-fee-rate 50 wallet send --fee-rate priority bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc",
+  )
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(
+    "error: estimated fee rate of 100 sats/vB exceeds maximum fee rate of 50 sats/vB\n",
+  )
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  assert!(core.mempool().is_empty());
+}
+
 #[test]
 fn send_btc_locks_inscriptions() {
   let core = mockcore::spawn();
@@ -0,0 +1,208 @@
+use super::*;
+
+type SendMany = ord::subcommand::wallet::send_many::Output;
+
+#[test]
+fn inscriptions_are_sent_in_one_transaction() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (a, _) = inscribe(&core, &ord);
+  let (b, _) = inscribe(&core, &ord);
+
+  let output = CommandBuilder::new("wallet send-many --fee-rate 1 --sends sends.csv")
+    .core(&core)
+    .ord(&ord)
+    .write(
+      "sends.csv",
+      format!(
+        "{a},bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4\n\
+         {b},bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv\n\
+         1000 sat,bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv\n"
+      ),
+    )
+    .run_and_deserialize_output::<SendMany>();
+
+  assert_eq!(output.sends, 3);
+  assert_eq!(output.transactions.len(), 1);
+  assert_eq!(output.fee, output.transactions[0].fee);
+
+  let txid = output.transactions[0].txid;
+
+  assert_eq!(core.mempool().len(), 1);
+  assert_eq!(core.mempool()[0].compute_txid(), txid);
+  assert_eq!(core.mempool()[0].output[2].value, Amount::from_sat(1000));
+
+  core.mine_blocks(1);
+
+  ord.assert_response_regex(
+    format!("/inscription/{a}"),
+    format!(".*<dt>location</dt>\\s*<dd><a class=collapse href=/satpoint/{txid}:0:0>.*"),
+  );
+
+  ord.assert_response_regex(
+    format!("/inscription/{b}"),
+    format!(".*<dt>location</dt>\\s*<dd><a class=collapse href=/satpoint/{txid}:1:0>.*"),
+  );
+}
+
+#[test]
+fn dry_run_does_not_broadcast() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  let output = CommandBuilder::new("wallet send-many --fee-rate 1 --dry-run --sends sends.yaml")
+    .core(&core)
+    .ord(&ord)
+    .write(
+      "sends.yaml",
+      format!(
+        "
+sends:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  asset: {inscription}
+"
+      ),
+    )
+    .run_and_deserialize_output::<SendMany>();
+
+  assert_eq!(output.transactions.len(), 1);
+  assert!(core.mempool().is_empty());
+}
+
+#[test]
+fn unrecognized_fields_are_forbidden() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("wallet send-many --fee-rate 1 --sends sends.yaml")
+    .core(&core)
+    .ord(&ord)
+    .write(
+      "sends.yaml",
+      "
+sends:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  asset: 1000 sat
+  foo:
+",
+    )
+    .stderr_regex(r"error: sends\[0\]: unknown field `foo`.*")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn runes_require_rune_index() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("wallet send-many --fee-rate 1 --sends sends.csv")
+    .core(&core)
+    .ord(&ord)
+    .write(
+      "sends.csv",
+      "1:FOO,bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+    )
+    .expected_stderr(
+      "error: sending runes with `ord wallet send-many` requires index created with `--index-runes`\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn runes_are_sent_to_many_recipients() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let rune = Rune(RUNE);
+  let spaced_rune = SpacedRune { rune, spacers: 0 };
+
+  let etched = etch(&core, &ord, rune);
+
+  let output = CommandBuilder::new("--regtest wallet send-many --fee-rate 1 --sends sends.yaml")
+    .core(&core)
+    .ord(&ord)
+    .write(
+      "sends.yaml",
+      format!(
+        "
+sends:
+- address: bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw
+  asset: 100:{spaced_rune}
+- address: bcrt1pyrmadgg78e38ewfv0an8c6eppk2fttv5vnuvz04yza60qau5va0saknu8k
+  asset: 200:{spaced_rune}
+"
+      ),
+    )
+    .run_and_deserialize_output::<SendMany>();
+
+  assert_eq!(output.transactions.len(), 1);
+
+  let txid = output.transactions[0].txid;
+
+  assert_eq!(
+    Runestone::decipher(&core.mempool()[0]),
+    Some(Artifact::Runestone(Runestone {
+      edicts: vec![
+        Edict {
+          id: etched.id,
+          amount: 100,
+          output: 0,
+        },
+        Edict {
+          id: etched.id,
+          amount: 200,
+          output: 1,
+        },
+      ],
+      pointer: Some(2),
+      ..default()
+    })),
+  );
+
+  core.mine_blocks(1);
+
+  let pile = |amount| Pile {
+    amount,
+    divisibility: 0,
+    symbol: Some('¢'),
+  };
+
+  pretty_assert_eq!(
+    CommandBuilder::new("--regtest --index-runes balances")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Balances>(),
+    Balances {
+      runes: [(
+        spaced_rune,
+        [
+          (OutPoint { txid, vout: 0 }, pile(100)),
+          (OutPoint { txid, vout: 1 }, pile(200)),
+          (OutPoint { txid, vout: 2 }, pile(700)),
+        ]
+        .into()
+      )]
+      .into(),
+    }
+  );
+}
@@ -1,4 +1,4 @@
-use {super::*, ord::subcommand::wallet::transactions::Output};
+use {super::*, ord::subcommand::wallet::transactions::Output, std::collections::BTreeSet};
 
 #[test]
 fn transactions() {
@@ -65,3 +65,301 @@ fn transactions_with_limit() {
 
   assert_eq!(output.len(), 1);
 }
+
+#[test]
+fn transactions_limit_counts_distinct_transactions() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(3);
+
+  let address = CommandBuilder::new("wallet receive")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::receive::Output>()
+    .addresses
+    .into_iter()
+    .next()
+    .unwrap();
+
+  CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 {} 1btc",
+    address.assume_checked()
+  ))
+  .core(&core)
+  .ord(&ord)
+  .stdout_regex(".*")
+  .run_and_extract_stdout();
+
+  core.mine_blocks(1);
+
+  let all = CommandBuilder::new("wallet transactions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<Output>>();
+
+  for limit in 1..=all.len() {
+    let output = CommandBuilder::new(format!("wallet transactions --limit {limit}"))
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Vec<Output>>();
+
+    assert_eq!(
+      output
+        .iter()
+        .map(|tx| tx.transaction)
+        .collect::<BTreeSet<Txid>>()
+        .len(),
+      limit,
+    );
+  }
+}
+
+#[test]
+fn transactions_are_annotated_with_inscription_activity() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}",
+  ))
+  .temp_dir(tempdir.clone())
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet transactions")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<Output>>();
+
+  let reveal = output.iter().find(|tx| tx.transaction == reveal).unwrap();
+
+  assert_eq!(reveal.inscriptions.created, [inscription]);
+  assert!(reveal.inscriptions.sent.is_empty());
+
+  let sent = output
+    .iter()
+    .find(|tx| tx.transaction == send.txid)
+    .unwrap();
+
+  assert_eq!(sent.inscriptions.sent, [inscription]);
+  assert_eq!(sent.fee, Some(send.fee));
+
+  let table = CommandBuilder::new("wallet transactions --table")
+    .temp_dir(tempdir)
+    .core(&core)
+    .ord(&ord)
+    .stdout_regex(".*")
+    .run_and_extract_stdout();
+
+  assert!(table.contains(&format!("inscriptions sent: {inscription}")));
+}
+
+#[test]
+fn transactions_are_annotated_with_rune_activity() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etched = etch(&core, &ord, Rune(RUNE));
+
+  let output = CommandBuilder::new("--regtest --index-runes wallet transactions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<Output>>();
+
+  let reveal = output
+    .iter()
+    .find(|tx| tx.transaction == etched.output.reveal)
+    .unwrap();
+
+  assert_eq!(
+    reveal.runes.etched,
+    [(
+      SpacedRune {
+        rune: Rune(RUNE),
+        spacers: 0
+      },
+      "1000".parse().unwrap()
+    )]
+    .into_iter()
+    .collect(),
+  );
+}
+
+#[test]
+fn outputs_are_recorded_before_they_are_spent() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, _reveal) = inscribe(&core, &ord);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  // the wallet records the inscription's output when `wallet send` builds it,
+  // before spending it
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}",
+  ))
+  .temp_dir(tempdir.clone())
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet transactions")
+    .temp_dir(tempdir)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<Output>>();
+
+  let sent = output
+    .iter()
+    .find(|tx| tx.transaction == send.txid)
+    .unwrap();
+
+  assert_eq!(sent.inscriptions.sent, [inscription]);
+  assert!(sent.unknown.is_empty());
+}
+
+#[test]
+fn outputs_spent_without_running_ord_are_unknown() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let coinbase = core.mine_blocks(1)[0].txdata[0].compute_txid();
+
+  let spend = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(1, 0, 0, Default::default())],
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet transactions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<Output>>();
+
+  let spent = output.iter().find(|tx| tx.transaction == spend).unwrap();
+
+  assert_eq!(
+    spent.unknown,
+    [OutPoint {
+      txid: coinbase,
+      vout: 0
+    }]
+  );
+}
+
+#[test]
+fn mints_after_cap_are_not_credited() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  core.mine_blocks(1);
+
+  create_wallet(&core, &ord);
+
+  batch(
+    &core,
+    &ord,
+    batch::File {
+      etching: Some(batch::Etching {
+        divisibility: 0,
+        rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+        premine: "0".parse().unwrap(),
+        supply: "21".parse().unwrap(),
+        symbol: '¢',
+        turbo: false,
+        terms: Some(batch::Terms {
+          cap: 1,
+          offset: None,
+          amount: "21".parse().unwrap(),
+          height: None,
+        }),
+      }),
+      inscriptions: vec![batch::Entry {
+        file: Some("inscription.jpeg".into()),
+        ..default()
+      }],
+      ..default()
+    },
+  );
+
+  let mints = (0..2)
+    .map(|_| {
+      CommandBuilder::new(format!(
+        "--regtest --index-runes wallet mint --fee-rate 1 --rune {}",
+        Rune(RUNE)
+      ))
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<ord::subcommand::wallet::mint::Output>()
+      .mint
+    })
+    .collect::<Vec<Txid>>();
+
+  let minted = |output: &[Output], txid| {
+    output
+      .iter()
+      .find(|tx| tx.transaction == txid)
+      .unwrap()
+      .runes
+      .minted
+      .clone()
+  };
+
+  let spaced_rune = SpacedRune {
+    rune: Rune(RUNE),
+    spacers: 0,
+  };
+
+  let output = CommandBuilder::new("--regtest --index-runes wallet transactions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<Output>>();
+
+  assert_eq!(
+    minted(&output, mints[0]),
+    [(spaced_rune, "21".parse().unwrap())].into()
+  );
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("--regtest --index-runes wallet transactions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<Output>>();
+
+  assert_eq!(
+    minted(&output, mints[0]),
+    [(spaced_rune, "21".parse().unwrap())].into()
+  );
+  assert!(minted(&output, mints[1]).is_empty());
+}